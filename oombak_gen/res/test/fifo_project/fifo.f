@@ -0,0 +1,3 @@
+// fifo_impl.sv holds module fifo, which Verilator cannot find by its file name
++define+DEPTH_LOG2=2
+rtl/fifo_impl.sv
@@ -0,0 +1,36 @@
+module fifo (
+    input  logic       clk,
+    input  logic       rst_n,
+    input  logic       push,
+    input  logic       pop,
+    input  logic [7:0] in,
+    output logic [7:0] out,
+    output logic       empty
+);
+`ifdef DEPTH_LOG2
+  localparam int DepthLog2 = `DEPTH_LOG2;
+`else
+  localparam int DepthLog2 = 1;
+`endif
+
+  logic [7:0] mem[2**DepthLog2];
+  logic [DepthLog2:0] head, tail;
+
+  assign out = mem[tail[DepthLog2-1:0]];
+  assign empty = head == tail;
+
+  always_ff @(posedge clk) begin
+    if (!rst_n) begin
+      head <= 0;
+      tail <= 0;
+    end else begin
+      if (push) begin
+        mem[head[DepthLog2-1:0]] <= in;
+        head <= head + 1;
+      end
+      if (pop && !empty) begin
+        tail <= tail + 1;
+      end
+    end
+  end
+endmodule
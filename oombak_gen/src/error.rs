@@ -8,8 +8,10 @@ pub enum Error {
     SvFilePathNotFound(PathBuf),
     #[error("invalid path given: {}", _0.to_string_lossy())]
     InvalidPath(PathBuf),
-    #[error("file name does not have .sv extension: {}", _0.to_string_lossy())]
+    #[error("file name does not have the .sv, .f or .vf extension: {}", _0.to_string_lossy())]
     ExtensionNotSv(PathBuf),
+    #[error("filelist {}: {}", _0.to_string_lossy(), _1)]
+    Filelist(PathBuf, String),
     #[error("IO error: {}", _0)]
     Io(std::io::Error),
     #[error("CMake error: {}", _0)]
@@ -0,0 +1,134 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::error::{Error, OombakGenResult};
+
+/// Sources of a design listed in a filelist, e.g. one passed to Verilator with `-f`.
+///
+/// Each line holds a source file path, a `+incdir+<dir>[+<dir>...]` include directory
+/// declaration, a `+define+<name>[=<value>][+...]` macro definition, or `-f <filelist>` to include
+/// another filelist. Relative paths are relative to the directory of the filelist, `//` and `#`
+/// start comments, and any other option is ignored.
+#[derive(Debug, Default, PartialEq)]
+pub struct Filelist {
+    pub source_paths: Vec<PathBuf>,
+    pub include_dirs: Vec<PathBuf>,
+    /// Macros as `<name>` or `<name>=<value>`
+    pub defines: Vec<String>,
+}
+
+impl Filelist {
+    pub fn read(path: &Path) -> OombakGenResult<Self> {
+        let mut filelist = Self::default();
+        filelist.read_into(path, &mut vec![])?;
+        Ok(filelist)
+    }
+
+    fn read_into(&mut self, path: &Path, visited: &mut Vec<PathBuf>) -> OombakGenResult<()> {
+        if visited.iter().any(|p| p == path) {
+            return Err(Error::Filelist(
+                path.to_path_buf(),
+                "includes itself".to_string(),
+            ));
+        }
+        visited.push(path.to_path_buf());
+        let content = fs::read_to_string(path)
+            .map_err(|e| Error::Filelist(path.to_path_buf(), e.to_string()))?;
+        let dir = path
+            .parent()
+            .ok_or(Error::InvalidPath(path.to_path_buf()))?;
+        self.parse(&content, dir, path, visited)?;
+        visited.pop();
+        Ok(())
+    }
+
+    fn parse(
+        &mut self,
+        content: &str,
+        dir: &Path,
+        path: &Path,
+        visited: &mut Vec<PathBuf>,
+    ) -> OombakGenResult<()> {
+        let mut words = content
+            .lines()
+            .map(|line| line.split("//").next().unwrap())
+            .map(|line| line.split('#').next().unwrap())
+            .flat_map(str::split_whitespace);
+        while let Some(word) = words.next() {
+            if let Some(include_dirs) = word.strip_prefix("+incdir+") {
+                self.include_dirs.extend(
+                    include_dirs
+                        .split('+')
+                        .filter(|d| !d.is_empty())
+                        .map(|d| dir.join(d)),
+                );
+            } else if let Some(defines) = word.strip_prefix("+define+") {
+                self.defines.extend(
+                    defines
+                        .split('+')
+                        .filter(|d| !d.is_empty())
+                        .map(str::to_string),
+                );
+            } else if word == "-f" || word == "-F" {
+                let nested_path = words.next().ok_or(Error::Filelist(
+                    path.to_path_buf(),
+                    format!("expected a filelist after {word}"),
+                ))?;
+                self.read_into(&dir.join(nested_path), visited)?;
+            } else if !word.starts_with(['-', '+']) {
+                self.source_paths.push(dir.join(word));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::{Path, PathBuf};
+
+    use super::Filelist;
+
+    #[test]
+    fn test_parse() {
+        let mut filelist = Filelist::default();
+        filelist
+            .parse(
+                "// FIFO sources\n\
+                 +incdir+include+../common\n\
+                 +define+DEPTH=4+SIM\n\
+                 rtl/fifo.sv   # the top\n\
+                 /abs/ram.sv -sv\n",
+                Path::new("/proj"),
+                Path::new("/proj/fifo.f"),
+                &mut vec![],
+            )
+            .unwrap();
+
+        assert_eq!(
+            filelist.source_paths,
+            vec![
+                PathBuf::from("/proj/rtl/fifo.sv"),
+                PathBuf::from("/abs/ram.sv")
+            ]
+        );
+        assert_eq!(
+            filelist.include_dirs,
+            vec![
+                PathBuf::from("/proj/include"),
+                PathBuf::from("/proj/../common")
+            ]
+        );
+        assert_eq!(filelist.defines, vec!["DEPTH=4", "SIM"]);
+        assert!(Filelist::default()
+            .parse(
+                "-f",
+                Path::new("/proj"),
+                Path::new("/proj/fifo.f"),
+                &mut vec![]
+            )
+            .is_err());
+    }
+}
@@ -1,6 +1,10 @@
-use std::{fs::File, io::Write, path::Path};
+use std::{
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
 
-use crate::{Error, OombakGenResult};
+use crate::{Error, Filelist, OombakGenResult};
 
 use oombak_rs::probe::{Probe, ProbePoint};
 use tempfile::TempDir;
@@ -151,22 +155,39 @@ macro_rules! multi_bit_dpc_getter_template {
     };
 }
 
-pub fn generate(sv_path: &Path, probe: &Probe) -> OombakGenResult<TempDir> {
-    Generator::new(probe, sv_path)?.generate()
+pub fn generate(
+    sources: &Filelist,
+    probe: &Probe,
+    build_dir: Option<&Path>,
+) -> OombakGenResult<TempDir> {
+    Generator::new(probe, sources, build_dir)?.generate()
 }
 
 struct Generator<'a> {
     temp_dir: TempDir,
     probe: &'a Probe,
-    sv_path: &'a Path,
+    sources: &'a Filelist,
 }
 
 impl<'a> Generator<'a> {
-    fn new(probe: &'a Probe, sv_path: &'a Path) -> OombakGenResult<Self> {
+    fn new(
+        probe: &'a Probe,
+        sources: &'a Filelist,
+        build_dir: Option<&Path>,
+    ) -> OombakGenResult<Self> {
+        let temp_dir = match build_dir {
+            Some(build_dir) => {
+                std::fs::create_dir_all(build_dir)?;
+                tempfile::Builder::new()
+                    .prefix("oombak-")
+                    .tempdir_in(build_dir)?
+            }
+            None => TempDir::new()?,
+        };
         Ok(Generator {
-            temp_dir: TempDir::new()?,
+            temp_dir,
             probe,
-            sv_path,
+            sources,
         })
     }
 
@@ -290,17 +311,7 @@ impl<'a> Generator<'a> {
     }
 
     fn put_cmakelists_txt(&self) -> OombakGenResult<()> {
-        let sv_dir = self
-            .sv_path
-            .parent()
-            .ok_or(Error::InvalidPath(self.sv_path.to_path_buf()))?;
-        let content = include_str!("templates/CMakeLists.txt.templated");
-        let content = content.replace(
-            "/*OMBAK_INCLUDE_DIRS*/",
-            sv_dir
-                .to_str()
-                .ok_or(Error::InvalidPath(self.sv_path.to_path_buf()))?,
-        );
+        let content = cmakelists_txt(self.sources)?;
         self.put_file("CMakeLists.txt", content.as_bytes())?;
         Ok(())
     }
@@ -388,3 +399,56 @@ impl<'a> Generator<'a> {
         Ok(())
     }
 }
+
+/// Verilator is given every source, rather than only the include directories in which it would
+/// look for a module in the file named after it.
+fn cmakelists_txt(sources: &Filelist) -> OombakGenResult<String> {
+    let source_paths = quoted_paths(&sources.source_paths)?;
+    let include_dirs = quoted_paths(&sources.include_dirs)?;
+    let defines: Vec<String> = sources
+        .defines
+        .iter()
+        .map(|define| format!("\"-D{define}\""))
+        .collect();
+    let content = include_str!("templates/CMakeLists.txt.templated");
+    let content = content.replace("/*OMBAK_SOURCES*/", &source_paths.join(" "));
+    let content = content.replace("/*OMBAK_DEFINES*/", &defines.join(" "));
+    let content = content.replace("/*OMBAK_INCLUDE_DIRS*/", &include_dirs.join(" "));
+    Ok(content)
+}
+
+fn quoted_paths(paths: &[PathBuf]) -> OombakGenResult<Vec<String>> {
+    paths
+        .iter()
+        .map(|path| {
+            let path = path
+                .to_str()
+                .ok_or(Error::InvalidPath(path.to_path_buf()))?;
+            Ok(format!("\"{path}\""))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use crate::Filelist;
+
+    use super::cmakelists_txt;
+
+    #[test]
+    fn test_cmakelists_txt() {
+        let sources = Filelist {
+            source_paths: vec![PathBuf::from("/proj/rtl/fifo_impl.sv")],
+            include_dirs: vec![PathBuf::from("/proj/include")],
+            defines: vec!["DEPTH=4".to_string(), "SIM".to_string()],
+        };
+        let content = cmakelists_txt(&sources).unwrap();
+
+        assert!(content
+            .contains("SOURCES ombak_dut.sv \"/proj/rtl/fifo_impl.sv\" TOP_MODULE ombak_dut"));
+        assert!(content.contains("VERILATOR_ARGS --timing \"-DDEPTH=4\" \"-DSIM\""));
+        assert!(content.contains("INCLUDE_DIRS \"/proj/include\""));
+    }
+}
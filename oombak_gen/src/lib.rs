@@ -1,4 +1,5 @@
 pub mod error;
+mod filelist;
 mod generator;
 
 use std::{
@@ -12,6 +13,7 @@ use tempfile::TempDir;
 use tokio::sync::mpsc::Sender;
 
 pub use error::{Error, OombakGenResult};
+pub use filelist::Filelist;
 
 pub struct TempGenDir {
     tempdir: TempDir,
@@ -21,6 +23,19 @@ pub struct TempGenDir {
 pub struct Builder {
     notification_channel: Option<NotificationChannel>,
     progress: Percentage,
+    options: BuildOptions,
+    top_module: Option<String>,
+}
+
+/// Tools and resources used to build `libdut`. Unset options fall back to the system's temporary
+/// directory, the `cmake` found in `PATH`, the Verilator found by CMake, and one job per core.
+#[derive(Clone, Debug, Default)]
+pub struct BuildOptions {
+    /// Directory in which the build directories are created
+    pub build_dir: Option<PathBuf>,
+    pub cmake: Option<PathBuf>,
+    pub verilator_root: Option<PathBuf>,
+    pub jobs: Option<usize>,
 }
 
 pub struct NotificationChannel {
@@ -33,10 +48,15 @@ impl Default for Builder {
         Self {
             notification_channel: None,
             progress: Percentage::new(4),
+            options: BuildOptions::default(),
+            top_module: None,
         }
     }
 }
 
+/// Extensions of the design files read as a [`Filelist`]
+pub const FILELIST_EXTENSIONS: [&str; 2] = ["f", "vf"];
+
 #[cfg(target_os = "linux")]
 static DYLIB_EXT: &str = "so";
 #[cfg(target_os = "macos")]
@@ -46,40 +66,71 @@ impl Builder {
     pub fn new(notification_channel: NotificationChannel) -> Self {
         Self {
             notification_channel: Some(notification_channel),
-            progress: Percentage::new(4),
+            ..Default::default()
         }
     }
 
-    pub fn build(self, sv_path: &Path) -> OombakGenResult<(TempGenDir, Probe)> {
-        let sv_path = get_absolute_canonicalized_path(sv_path)?;
-        let source_paths: Vec<String> = source_paths_from_sv_path(&sv_path)?
+    pub fn options(mut self, options: BuildOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Sets the top-level module, which is otherwise named after the design file.
+    pub fn top_module(mut self, top_module: Option<String>) -> Self {
+        self.top_module = top_module;
+        self
+    }
+
+    /// Builds a design given as its top-level `.sv` file, or as a [`Filelist`] with one of
+    /// `FILELIST_EXTENSIONS`. The modules of a `.sv` file are looked up in the `.sv` files of its
+    /// directory.
+    pub fn build(self, design_path: &Path) -> OombakGenResult<(TempGenDir, Probe)> {
+        let design_path = get_absolute_canonicalized_path(design_path)?;
+        let sources = sources_of_design(&design_path)?;
+        let source_paths = if is_sv(&design_path) {
+            source_paths_from_sv_path(&design_path)?
+        } else {
+            sources.source_paths.clone()
+        };
+        let source_paths: Vec<String> = source_paths
             .iter()
             .map(|p| p.to_string_lossy().to_string())
             .collect();
 
         self.notify_progress("Creating probe...");
-        let top_level_module_name = match sv_path.file_name().map(|f| f.to_string_lossy()) {
-            Some(file_name) if file_name.ends_with(".sv") => {
-                file_name.trim_end_matches(".sv").to_string()
-            }
-            Some(_) => return Err(Error::ExtensionNotSv(sv_path.to_path_buf())),
-            None => return Err(Error::InvalidPath(sv_path.to_path_buf())),
+        let top_level_module_name = match &self.top_module {
+            Some(top_module) => top_module.clone(),
+            None => design_path
+                .file_stem()
+                .ok_or(Error::InvalidPath(design_path.to_path_buf()))?
+                .to_string_lossy()
+                .to_string(),
         };
         let probe = Probe::try_from(&source_paths, &top_level_module_name)?;
 
-        Ok((self.build_with_probe(&sv_path, &probe)?, probe))
+        Ok((self.generate_and_build(&sources, &probe)?, probe))
     }
 
     pub fn build_with_probe(
+        self,
+        design_path: &Path,
+        probe: &Probe,
+    ) -> OombakGenResult<TempGenDir> {
+        let design_path = get_absolute_canonicalized_path(design_path)?;
+        let sources = sources_of_design(&design_path)?;
+        self.generate_and_build(&sources, probe)
+    }
+
+    fn generate_and_build(
         mut self,
-        sv_path: &Path,
+        sources: &Filelist,
         probe: &Probe,
     ) -> OombakGenResult<TempGenDir> {
         // Increment progress since Probe is already supplied
         self.progress.increment();
 
         self.notify_progress("Generating CMake project...");
-        let source_dir = generator::generate(sv_path, probe)?;
+        let source_dir = generator::generate(sources, probe, self.options.build_dir.as_deref())?;
         self.progress.increment();
 
         self.cmake(source_dir)
@@ -99,10 +150,17 @@ impl Builder {
 
     fn cmake_configure(&mut self, source_path: &Path) -> OombakGenResult<()> {
         self.notify_progress("Running CMake configure...");
-        let output = Command::new("cmake")
+        let mut command = self.cmake_command();
+        command
             .current_dir(source_path)
-            .args(["-S", ".", "-B", "build"])
-            .output()?;
+            .args(["-S", ".", "-B", "build"]);
+        if let Some(verilator_root) = &self.options.verilator_root {
+            command.arg(format!(
+                "-DVERILATOR_ROOT={}",
+                verilator_root.to_string_lossy()
+            ));
+        }
+        let output = command.output()?;
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr).to_string();
             return Err(Error::CMake(stderr));
@@ -113,10 +171,14 @@ impl Builder {
 
     fn cmake_build(&mut self, source_path: &Path) -> OombakGenResult<()> {
         self.notify_progress("Running CMake build...");
-        let num_cores = std::thread::available_parallelism().unwrap().get();
-        let output = Command::new("cmake")
+        let num_of_jobs = match self.options.jobs {
+            Some(jobs) => jobs,
+            None => std::thread::available_parallelism().unwrap().get(),
+        };
+        let output = self
+            .cmake_command()
             .current_dir(source_path)
-            .args(["--build", "build", "--parallel", &num_cores.to_string()])
+            .args(["--build", "build", "--parallel", &num_of_jobs.to_string()])
             .output()?;
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr).to_string();
@@ -126,6 +188,13 @@ impl Builder {
         Ok(())
     }
 
+    fn cmake_command(&self) -> Command {
+        match &self.options.cmake {
+            Some(cmake) => Command::new(cmake),
+            None => Command::new("cmake"),
+        }
+    }
+
     fn notify_progress(&self, message: &str) {
         if let Some(channel) = &self.notification_channel {
             let progress_payload =
@@ -149,6 +218,43 @@ impl NotificationChannel {
     }
 }
 
+fn is_sv(design_path: &Path) -> bool {
+    design_path.extension().is_some_and(|ext| ext == "sv")
+}
+
+fn sources_of_design(design_path: &Path) -> OombakGenResult<Filelist> {
+    if is_sv(design_path) {
+        if !design_path.is_file() {
+            return Err(Error::SvFilePathNotFound(design_path.to_path_buf()));
+        }
+        let parent_dir = design_path
+            .parent()
+            .ok_or(Error::InvalidPath(design_path.to_path_buf()))?;
+        // Verilator finds the other modules in the directory by their file name
+        return Ok(Filelist {
+            source_paths: vec![design_path.to_path_buf()],
+            include_dirs: vec![parent_dir.to_path_buf()],
+            defines: vec![],
+        });
+    }
+    let is_filelist = design_path
+        .extension()
+        .is_some_and(|ext| FILELIST_EXTENSIONS.iter().any(|e| ext == *e));
+    if !is_filelist {
+        return Err(Error::ExtensionNotSv(design_path.to_path_buf()));
+    }
+    let mut filelist = Filelist::read(design_path)?;
+    // Sources may include files next to them, as when given as a top-level `.sv` file
+    for source_path in filelist.source_paths.iter() {
+        if let Some(dir) = source_path.parent() {
+            if !filelist.include_dirs.iter().any(|d| d == dir) {
+                filelist.include_dirs.push(dir.to_path_buf());
+            }
+        }
+    }
+    Ok(filelist)
+}
+
 fn source_paths_from_sv_path(sv_path: &Path) -> OombakGenResult<Vec<PathBuf>> {
     if !sv_path.exists() || !sv_path.is_file() {
         return Err(Error::SvFilePathNotFound(sv_path.to_path_buf()));
@@ -188,7 +294,7 @@ impl TempGenDir {
 mod test {
     use std::{path::PathBuf, sync::OnceLock};
 
-    use crate::Builder;
+    use crate::{sources_of_design, Builder, Error};
 
     static SV_PROJECT_PATH: OnceLock<PathBuf> = OnceLock::new();
 
@@ -208,6 +314,30 @@ mod test {
         assert!(Builder::default().build(&sv_path).is_ok());
     }
 
+    #[test]
+    fn test_build_filelist() {
+        let mut filelist_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        filelist_path.push("res/test/fifo_project/fifo.f");
+
+        assert!(Builder::default().build(&filelist_path).is_ok());
+    }
+
+    #[test]
+    fn test_sources_of_design() {
+        let sources = sources_of_design(&sv_project_path().join("sample.sv")).unwrap();
+        assert_eq!(sources.source_paths, [sv_project_path().join("sample.sv")]);
+        assert_eq!(sources.include_dirs, [sv_project_path().clone()]);
+
+        let filelist_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("res/test/fifo_project");
+        let sources = sources_of_design(&filelist_path.join("fifo.f")).unwrap();
+        assert_eq!(sources.defines, ["DEPTH_LOG2=2"]);
+
+        assert!(matches!(
+            sources_of_design(&filelist_path.join("rtl/fifo_impl.v")),
+            Err(Error::ExtensionNotSv(_))
+        ));
+    }
+
     #[test]
     fn test_rebuild() {
         let mut sv_path = sv_project_path().clone();
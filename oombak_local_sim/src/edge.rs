@@ -0,0 +1,178 @@
+use bitvec::vec::BitVec;
+use oombak_sim::{Edge, StimulusSpec};
+
+use crate::stimulus::Stimulus;
+
+#[derive(Default)]
+pub struct EdgeScheduler {
+    clock_signal_name: Option<String>,
+    clock_level: bool,
+    num_of_rising_edges: usize,
+    pending_actions: Vec<EdgeAction>,
+}
+
+struct EdgeAction {
+    signal_name: String,
+    value: BitVec<u32>,
+    width: usize,
+    edge: Edge,
+    skew: usize,
+}
+
+impl EdgeScheduler {
+    pub fn set_clock(&mut self, signal_name: &str, clock_level: bool) {
+        self.clock_signal_name = Some(signal_name.to_string());
+        self.clock_level = clock_level;
+        self.pending_actions.clear();
+    }
+
+    pub fn clock_signal_name(&self) -> Option<&str> {
+        self.clock_signal_name.as_deref()
+    }
+
+    pub fn num_of_rising_edges(&self) -> usize {
+        self.num_of_rising_edges
+    }
+
+    /// Sets `signal_name`, `width` bits wide, to `value` after the next `edge` of the clock.
+    pub fn schedule(
+        &mut self,
+        signal_name: &str,
+        value: &BitVec<u32>,
+        width: usize,
+        edge: Edge,
+        skew: usize,
+    ) {
+        self.pending_actions
+            .retain(|action| action.signal_name != signal_name);
+        self.pending_actions.push(EdgeAction {
+            signal_name: signal_name.to_string(),
+            value: value.clone(),
+            width,
+            edge,
+            skew,
+        });
+    }
+
+    pub fn cancel(&mut self, signal_name: &str) {
+        self.pending_actions
+            .retain(|action| action.signal_name != signal_name);
+    }
+
+    /// Records a new value of `signal_name`, returning the stimuli scheduled relative to the clock
+    /// edge it produces, if any.
+    pub fn observe(
+        &mut self,
+        signal_name: &str,
+        value: &BitVec<u32>,
+        current_time: usize,
+    ) -> Vec<Stimulus> {
+        if self.clock_signal_name.as_deref() != Some(signal_name) {
+            return vec![];
+        }
+        let new_level = value.any();
+        let edge = match (self.clock_level, new_level) {
+            (false, true) => Edge::Rising,
+            (true, false) => Edge::Falling,
+            _ => return vec![],
+        };
+        self.clock_level = new_level;
+        if edge == Edge::Rising {
+            self.num_of_rising_edges += 1;
+        }
+
+        let (triggered, pending) = self
+            .pending_actions
+            .drain(..)
+            .partition(|action| action.edge == edge);
+        self.pending_actions = pending;
+        triggered
+            .into_iter()
+            .map(|action| action.into_stimulus(current_time))
+            .collect()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+impl EdgeAction {
+    fn into_stimulus(self, edge_time: usize) -> Stimulus {
+        let stimulus_spec = StimulusSpec::Sequence {
+            steps: vec![(self.value, 1)],
+            repeat: false,
+        };
+        Stimulus::new(
+            self.signal_name,
+            &stimulus_spec,
+            self.width,
+            edge_time + self.skew,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bitvec::{bitvec, order::Lsb0};
+    use oombak_sim::Edge;
+
+    use super::EdgeScheduler;
+    use crate::stimulus::StimulusGroup;
+
+    #[test]
+    fn test_schedule_relative_to_edge() {
+        let mut edge_scheduler = EdgeScheduler::default();
+        edge_scheduler.set_clock("clk", false);
+        edge_scheduler.schedule("data", &bitvec![u32, Lsb0; 1, 0, 1], 3, Edge::Rising, 1);
+
+        assert!(edge_scheduler
+            .observe("data", &bitvec![u32, Lsb0; 1], 5)
+            .is_empty());
+        let stimuli = edge_scheduler.observe("clk", &bitvec![u32, Lsb0; 1], 10);
+        assert_eq!(edge_scheduler.num_of_rising_edges(), 1);
+
+        let mut stimulus_group = StimulusGroup::default();
+        for stimulus in stimuli {
+            stimulus_group.insert(stimulus);
+        }
+        assert_eq!(stimulus_group.next_trigger_time(), Some(11));
+        assert_eq!(
+            stimulus_group.try_pop(11),
+            Some(("data".to_string(), bitvec![u32, Lsb0; 1, 0, 1]))
+        );
+
+        assert!(edge_scheduler
+            .observe("clk", &bitvec![u32, Lsb0; 0], 15)
+            .is_empty());
+        assert!(edge_scheduler
+            .observe("clk", &bitvec![u32, Lsb0; 1], 20)
+            .is_empty());
+        assert_eq!(edge_scheduler.num_of_rising_edges(), 2);
+    }
+
+    #[test]
+    fn test_scheduled_value_is_resized() {
+        let mut edge_scheduler = EdgeScheduler::default();
+        edge_scheduler.set_clock("clk", false);
+        edge_scheduler.schedule("data", &bitvec![u32, Lsb0; 1, 0, 1], 2, Edge::Rising, 0);
+        edge_scheduler.schedule("valid", &bitvec![u32, Lsb0; 1], 4, Edge::Rising, 0);
+
+        let mut stimulus_group = StimulusGroup::default();
+        for stimulus in edge_scheduler.observe("clk", &bitvec![u32, Lsb0; 1], 10) {
+            stimulus_group.insert(stimulus);
+        }
+        let mut values = vec![];
+        while let Some(value) = stimulus_group.try_pop(10) {
+            values.push(value);
+        }
+        values.sort();
+        assert_eq!(
+            values,
+            vec![
+                ("data".to_string(), bitvec![u32, Lsb0; 1, 0]),
+                ("valid".to_string(), bitvec![u32, Lsb0; 1, 0, 0, 0])
+            ]
+        );
+    }
+}
@@ -6,6 +6,14 @@ pub enum Error {
     DutNotLoaded,
     #[error("DUT is loading")]
     DutIsLoading,
+    #[error("invalid clock specification: {}", _0)]
+    InvalidClockSpec(String),
+    #[error("invalid stimulus specification: {}", _0)]
+    InvalidStimulusSpec(String),
+    #[error("reference clock not set")]
+    ReferenceClockNotSet,
+    #[error("reference clock {} is not driven by a clock or stimulus", _0)]
+    ReferenceClockNotDriven(String),
     #[error("oombak_gen: {}", _0)]
     OombakGen(oombak_gen::Error),
     #[error("oombak_rs: {}", _0)]
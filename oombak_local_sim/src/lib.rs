@@ -1,31 +1,39 @@
+mod edge;
 mod error;
 mod oscillator;
+mod stimulus;
 
 use std::path::{Path, PathBuf};
 
 use async_trait::async_trait;
 use bitvec::vec::BitVec;
 
+pub use oombak_gen::BuildOptions;
 use oombak_gen::{NotificationChannel, TempGenDir};
 use oombak_rs::{Dut, Probe};
 use oombak_sim::{
-    request, response, CompactWaveValue, LoadedDut, Message, ProbePointsModification, Request,
-    SimulationResult, Simulator, Wave,
+    request, response, ClockSpec, CompactWaveValue, Edge, InitSpec, LoadedDut, Message,
+    ProbePointsModification, Request, ResetSpec, SimulationResult, Simulator, StimulusSpec, Wave,
 };
 use tokio::{
     sync::{mpsc::Sender, RwLock, RwLockReadGuard, RwLockWriteGuard},
     task::spawn_blocking,
 };
 
+use crate::edge::EdgeScheduler;
 use crate::error::{Error, OombakSimResult};
 use crate::oscillator::{Oscillator, OscillatorGroup};
+use crate::stimulus::{Stimulus, StimulusGroup};
 
 #[derive(Default)]
 pub struct LocalSimulator {
+    build_options: BuildOptions,
     channel: RwLock<Option<Sender<Message>>>,
     simulation_result: RwLock<SimulationResult>,
     dut_state: RwLock<DutState>,
     oscillator_group: RwLock<OscillatorGroup>,
+    stimulus_group: RwLock<StimulusGroup>,
+    edge_scheduler: RwLock<EdgeScheduler>,
     is_dut_reloading: RwLock<bool>,
 }
 
@@ -42,6 +50,9 @@ impl Simulator for LocalSimulator {
     async fn serve(&self, request: &Request) {
         let payload = match &request.payload {
             request::Payload::Run(duration) => self.serve_run(*duration).await,
+            request::Payload::RunCycles(num_of_cycles) => {
+                self.serve_run_cycles(*num_of_cycles).await
+            }
             request::Payload::SetSignal(signal_name, value) => {
                 self.serve_set_signal(signal_name, value).await
             }
@@ -49,7 +60,23 @@ impl Simulator for LocalSimulator {
                 self.serve_set_periodic(signal_name, *period, low_value, high_value)
                     .await
             }
-            request::Payload::Load(path) => self.serve_load(path, request.id).await,
+            request::Payload::SetClock(signal_name, clock_spec) => {
+                self.serve_set_clock(signal_name, clock_spec).await
+            }
+            request::Payload::SetStimulus(signal_name, stimulus_spec) => {
+                self.serve_set_stimulus(signal_name, stimulus_spec).await
+            }
+            request::Payload::SetReferenceClock(signal_name) => {
+                self.serve_set_reference_clock(signal_name).await
+            }
+            request::Payload::SetAtEdge(signal_name, value, edge, skew) => {
+                self.serve_set_at_edge(signal_name, value, *edge, *skew)
+                    .await
+            }
+            request::Payload::Initialize(init_spec) => self.serve_initialize(init_spec).await,
+            request::Payload::Load(path, top_module) => {
+                self.serve_load(path, top_module.clone(), request.id).await
+            }
             request::Payload::ModifyProbedPoints(probe_modifications) => {
                 self.serve_modify_probe_points(probe_modifications, request.id)
                     .await
@@ -74,40 +101,117 @@ impl Simulator for LocalSimulator {
 }
 
 impl LocalSimulator {
+    pub fn new(build_options: BuildOptions) -> Self {
+        Self {
+            build_options,
+            ..Default::default()
+        }
+    }
+
     async fn serve_run(&self, duration: usize) -> response::Payload {
         let dut_state = self.dut_state.read().await;
         let mut simulation_result = self.simulation_result.write().await;
         let mut oscillator_group = self.oscillator_group.write().await;
+        let mut stimulus_group = self.stimulus_group.write().await;
+        let mut edge_scheduler = self.edge_scheduler.write().await;
         match self.run(
             duration,
             &mut simulation_result,
             &dut_state,
             &mut oscillator_group,
+            &mut stimulus_group,
+            &mut edge_scheduler,
+        ) {
+            Ok(current_time) => response::Payload::current_time(current_time),
+            Err(e) => response::Payload::Error(Box::new(e)),
+        }
+    }
+
+    async fn serve_run_cycles(&self, num_of_cycles: usize) -> response::Payload {
+        let dut_state = self.dut_state.read().await;
+        let mut simulation_result = self.simulation_result.write().await;
+        let mut oscillator_group = self.oscillator_group.write().await;
+        let mut stimulus_group = self.stimulus_group.write().await;
+        let mut edge_scheduler = self.edge_scheduler.write().await;
+        match self.run_cycles(
+            num_of_cycles,
+            &mut simulation_result,
+            &dut_state,
+            &mut oscillator_group,
+            &mut stimulus_group,
+            &mut edge_scheduler,
         ) {
             Ok(current_time) => response::Payload::current_time(current_time),
             Err(e) => response::Payload::Error(Box::new(e)),
         }
     }
 
+    fn run_cycles(
+        &self,
+        num_of_cycles: usize,
+        simulation_result: &mut RwLockWriteGuard<'_, SimulationResult>,
+        dut_state: &RwLockReadGuard<'_, DutState>,
+        oscillator_group: &mut RwLockWriteGuard<'_, OscillatorGroup>,
+        stimulus_group: &mut RwLockWriteGuard<'_, StimulusGroup>,
+        edge_scheduler: &mut RwLockWriteGuard<'_, EdgeScheduler>,
+    ) -> OombakSimResult<usize> {
+        let clock_signal_name = match edge_scheduler.clock_signal_name() {
+            Some(clock_signal_name) => clock_signal_name.to_string(),
+            None => return Err(Error::ReferenceClockNotSet),
+        };
+        let target_num_of_rising_edges = edge_scheduler.num_of_rising_edges() + num_of_cycles;
+        while edge_scheduler.num_of_rising_edges() < target_num_of_rising_edges {
+            if !oscillator_group.contains(&clock_signal_name)
+                && !stimulus_group.contains(&clock_signal_name)
+            {
+                return Err(Error::ReferenceClockNotDriven(clock_signal_name));
+            }
+            let next_trigger_time =
+                Self::next_trigger_time(oscillator_group, stimulus_group).unwrap();
+            let duration = next_trigger_time - simulation_result.current_time;
+            self.run(
+                duration,
+                simulation_result,
+                dut_state,
+                oscillator_group,
+                stimulus_group,
+                edge_scheduler,
+            )?;
+        }
+        Ok(simulation_result.current_time)
+    }
+
     fn run(
         &self,
         duration: usize,
         simulation_result: &mut RwLockWriteGuard<'_, SimulationResult>,
         dut_state: &RwLockReadGuard<'_, DutState>,
         oscillator_group: &mut RwLockWriteGuard<'_, OscillatorGroup>,
+        stimulus_group: &mut RwLockWriteGuard<'_, StimulusGroup>,
+        edge_scheduler: &mut RwLockWriteGuard<'_, EdgeScheduler>,
     ) -> OombakSimResult<usize> {
         let target_time = simulation_result.current_time + duration;
         while simulation_result.current_time != target_time {
             let current_time = simulation_result.current_time;
-            let run_duration =
-                Self::calculate_run_duration(current_time, target_time, oscillator_group);
+            let run_duration = Self::calculate_run_duration(
+                current_time,
+                target_time,
+                oscillator_group,
+                stimulus_group,
+            );
             let current_time = dut_state.run(run_duration)?;
             Self::append_new_values_to_simulation_result_until(
                 current_time,
                 simulation_result,
                 dut_state,
             )?;
-            Self::set_triggered_oscillator_values(current_time, dut_state, oscillator_group)?;
+            Self::set_triggered_values(
+                current_time,
+                dut_state,
+                oscillator_group,
+                stimulus_group,
+                edge_scheduler,
+            )?;
         }
         Ok(simulation_result.current_time)
     }
@@ -116,8 +220,9 @@ impl LocalSimulator {
         current_time: usize,
         target_time: usize,
         oscillator_group: &mut RwLockWriteGuard<'_, OscillatorGroup>,
+        stimulus_group: &mut RwLockWriteGuard<'_, StimulusGroup>,
     ) -> usize {
-        if let Some(next_trigger_time) = oscillator_group.next_trigger_time() {
+        if let Some(next_trigger_time) = Self::next_trigger_time(oscillator_group, stimulus_group) {
             if next_trigger_time < target_time {
                 return next_trigger_time - current_time;
             }
@@ -125,13 +230,34 @@ impl LocalSimulator {
         target_time - current_time
     }
 
-    fn set_triggered_oscillator_values(
+    fn next_trigger_time(
+        oscillator_group: &RwLockWriteGuard<'_, OscillatorGroup>,
+        stimulus_group: &RwLockWriteGuard<'_, StimulusGroup>,
+    ) -> Option<usize> {
+        [
+            oscillator_group.next_trigger_time(),
+            stimulus_group.next_trigger_time(),
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+    }
+
+    fn set_triggered_values(
         current_time: usize,
         dut_state: &RwLockReadGuard<'_, DutState>,
         oscillator_group: &mut RwLockWriteGuard<'_, OscillatorGroup>,
+        stimulus_group: &mut RwLockWriteGuard<'_, StimulusGroup>,
+        edge_scheduler: &mut RwLockWriteGuard<'_, EdgeScheduler>,
     ) -> OombakSimResult<()> {
-        while let Some((signal_name, value)) = oscillator_group.try_pop(current_time) {
+        while let Some((signal_name, value)) = oscillator_group
+            .try_pop(current_time)
+            .or_else(|| stimulus_group.try_pop(current_time))
+        {
             dut_state.set(&signal_name, &value)?;
+            for stimulus in edge_scheduler.observe(&signal_name, &value, current_time) {
+                stimulus_group.insert(stimulus);
+            }
         }
         Ok(())
     }
@@ -144,11 +270,7 @@ impl LocalSimulator {
         let new_values = Self::query_new_values(simulation_result, dut_state)?;
         let current_time = simulation_result.current_time;
         let duration = end_time - current_time;
-        for (wave, new_value) in simulation_result
-            .waves
-            .iter_mut()
-            .zip(new_values.into_iter())
-        {
+        for (wave, new_value) in simulation_result.waves.iter_mut().zip(new_values) {
             let compact_new_value = CompactWaveValue::new(new_value, duration);
             wave.append(compact_new_value);
         }
@@ -168,10 +290,15 @@ impl LocalSimulator {
         Ok(new_values)
     }
 
-    async fn serve_load(&self, sv_path: &Path, message_id: usize) -> response::Payload {
+    async fn serve_load(
+        &self,
+        path: &Path,
+        top_module: Option<String>,
+        message_id: usize,
+    ) -> response::Payload {
         match self.try_set_is_dut_reloading() {
             Ok(()) => {
-                let load_result = self.load_dut(sv_path, message_id).await;
+                let load_result = self.load_dut(path, top_module, message_id).await;
                 self.reset_is_dut_reloading().await;
                 match load_result {
                     Ok(dut) => response::Payload::from(dut),
@@ -182,14 +309,18 @@ impl LocalSimulator {
         }
     }
 
-    async fn load_dut(&self, path: &Path, message_id: usize) -> OombakSimResult<LoadedDut> {
+    async fn load_dut(
+        &self,
+        path: &Path,
+        top_module: Option<String>,
+        message_id: usize,
+    ) -> OombakSimResult<LoadedDut> {
         let path_buf = path.to_path_buf();
-        let notification_channel = self.channel.read().await.clone();
-        let (new_dut, temp_gen_dir, new_probe) = spawn_blocking(move || {
-            Self::generate_new_dut(&path_buf, notification_channel, message_id)
-        })
-        .await
-        .unwrap()?;
+        let builder = self.create_builder(message_id).await.top_module(top_module);
+        let (new_dut, temp_gen_dir, new_probe) =
+            spawn_blocking(move || Self::generate_new_dut(&path_buf, builder))
+                .await
+                .unwrap()?;
 
         {
             let mut dut_state = self.dut_state.write().await;
@@ -199,6 +330,10 @@ impl LocalSimulator {
         {
             let mut oscillator_group = self.oscillator_group.write().await;
             oscillator_group.clear();
+            let mut stimulus_group = self.stimulus_group.write().await;
+            stimulus_group.clear();
+            let mut edge_scheduler = self.edge_scheduler.write().await;
+            edge_scheduler.clear();
         }
 
         let dut_state = self.dut_state.read().await;
@@ -210,12 +345,10 @@ impl LocalSimulator {
 
     fn generate_new_dut(
         path: &Path,
-        notification_channel: Option<Sender<Message>>,
-        message_id: usize,
+        builder: oombak_gen::Builder,
     ) -> OombakSimResult<(LoadedDut, TempGenDir, Probe)> {
-        let builder = Self::create_builder(notification_channel, message_id);
         let (temp_gen_dir, probe) = builder.build(path)?;
-        let loaded_dut = LoadedDut::from(&probe);
+        let loaded_dut = LoadedDut::new(path, &probe);
         Ok((loaded_dut, temp_gen_dir, probe))
     }
 
@@ -244,12 +377,29 @@ impl LocalSimulator {
 
     async fn serve_set_signal(&self, signal_name: &str, value: &BitVec<u32>) -> response::Payload {
         let dut_state = self.dut_state.read().await;
+        let current_time = self.simulation_result.read().await.current_time;
         let mut oscillator_group = self.oscillator_group.write().await;
+        let mut stimulus_group = self.stimulus_group.write().await;
+        let mut edge_scheduler = self.edge_scheduler.write().await;
         oscillator_group.remove(signal_name);
-        match dut_state.set(signal_name, value) {
-            Ok(()) => response::Payload::empty(),
-            Err(e) => response::Payload::Error(Box::new(e)),
+        stimulus_group.remove(signal_name);
+        edge_scheduler.cancel(signal_name);
+        if let Err(e) = dut_state.set(signal_name, value) {
+            return response::Payload::Error(Box::new(e));
+        }
+        for stimulus in edge_scheduler.observe(signal_name, value, current_time) {
+            stimulus_group.insert(stimulus);
         }
+        if let Err(e) = Self::set_triggered_values(
+            current_time,
+            &dut_state,
+            &mut oscillator_group,
+            &mut stimulus_group,
+            &mut edge_scheduler,
+        ) {
+            return response::Payload::Error(Box::new(e));
+        }
+        response::Payload::empty()
     }
 
     async fn serve_set_periodic(
@@ -262,6 +412,7 @@ impl LocalSimulator {
         let dut_state = self.dut_state.read().await;
         let simulation_result = self.simulation_result.read().await;
         let mut oscillator_group = self.oscillator_group.write().await;
+        let mut stimulus_group = self.stimulus_group.write().await;
 
         if let Err(e) =
             Self::check_oscillator_parameters(signal_name, low_value, high_value, &dut_state)
@@ -277,9 +428,293 @@ impl LocalSimulator {
             high_value.clone(),
         );
         oscillator_group.insert(oscillator);
+        stimulus_group.remove(signal_name);
+        self.edge_scheduler.write().await.cancel(signal_name);
+        response::Payload::empty()
+    }
+
+    async fn serve_set_clock(
+        &self,
+        signal_name: &str,
+        clock_spec: &ClockSpec,
+    ) -> response::Payload {
+        let dut_state = self.dut_state.read().await;
+        let simulation_result = self.simulation_result.read().await;
+        let mut oscillator_group = self.oscillator_group.write().await;
+        let mut stimulus_group = self.stimulus_group.write().await;
+        let mut edge_scheduler = self.edge_scheduler.write().await;
+        match Self::set_clock(
+            signal_name,
+            clock_spec,
+            &dut_state,
+            simulation_result.current_time,
+            &mut oscillator_group,
+            &mut stimulus_group,
+            &mut edge_scheduler,
+        ) {
+            Ok(()) => response::Payload::empty(),
+            Err(e) => response::Payload::Error(Box::new(e)),
+        }
+    }
+
+    fn set_clock(
+        signal_name: &str,
+        clock_spec: &ClockSpec,
+        dut_state: &RwLockReadGuard<'_, DutState>,
+        current_time: usize,
+        oscillator_group: &mut RwLockWriteGuard<'_, OscillatorGroup>,
+        stimulus_group: &mut RwLockWriteGuard<'_, StimulusGroup>,
+        edge_scheduler: &mut RwLockWriteGuard<'_, EdgeScheduler>,
+    ) -> OombakSimResult<()> {
+        Self::check_clock_spec(clock_spec)?;
+        let low_value = BitVec::repeat(false, 1);
+        let high_value = BitVec::repeat(true, 1);
+        Self::check_oscillator_parameters(signal_name, &low_value, &high_value, dut_state)?;
+
+        // The clock replaces the other drivers of the signal only once it is known to be valid
+        stimulus_group.remove(signal_name);
+        edge_scheduler.cancel(signal_name);
+        let oscillator = Oscillator::from_clock_spec(
+            signal_name.to_string(),
+            clock_spec,
+            current_time,
+            low_value,
+            high_value,
+        );
+        oscillator_group.insert(oscillator);
+        Self::set_triggered_values(
+            current_time,
+            dut_state,
+            oscillator_group,
+            stimulus_group,
+            edge_scheduler,
+        )
+    }
+
+    async fn serve_set_stimulus(
+        &self,
+        signal_name: &str,
+        stimulus_spec: &StimulusSpec,
+    ) -> response::Payload {
+        let dut_state = self.dut_state.read().await;
+        let simulation_result = self.simulation_result.read().await;
+        let mut oscillator_group = self.oscillator_group.write().await;
+        let mut stimulus_group = self.stimulus_group.write().await;
+        let mut edge_scheduler = self.edge_scheduler.write().await;
+        match Self::set_stimulus(
+            signal_name,
+            stimulus_spec,
+            &dut_state,
+            &simulation_result,
+            &mut oscillator_group,
+            &mut stimulus_group,
+            &mut edge_scheduler,
+        ) {
+            Ok(()) => response::Payload::empty(),
+            Err(e) => response::Payload::Error(Box::new(e)),
+        }
+    }
+
+    fn set_stimulus(
+        signal_name: &str,
+        stimulus_spec: &StimulusSpec,
+        dut_state: &RwLockReadGuard<'_, DutState>,
+        simulation_result: &RwLockReadGuard<'_, SimulationResult>,
+        oscillator_group: &mut RwLockWriteGuard<'_, OscillatorGroup>,
+        stimulus_group: &mut RwLockWriteGuard<'_, StimulusGroup>,
+        edge_scheduler: &mut RwLockWriteGuard<'_, EdgeScheduler>,
+    ) -> OombakSimResult<()> {
+        Self::check_stimulus_spec(stimulus_spec)?;
+        let width = dut_state.get(signal_name)?.len();
+        let current_time = simulation_result.current_time;
+
+        // The stimulus replaces the other drivers of the signal only once it is known to be valid
+        oscillator_group.remove(signal_name);
+        edge_scheduler.cancel(signal_name);
+        let stimulus = Stimulus::new(signal_name.to_string(), stimulus_spec, width, current_time);
+        stimulus_group.insert(stimulus);
+        Self::set_triggered_values(
+            current_time,
+            dut_state,
+            oscillator_group,
+            stimulus_group,
+            edge_scheduler,
+        )
+    }
+
+    async fn serve_set_reference_clock(&self, signal_name: &str) -> response::Payload {
+        let dut_state = self.dut_state.read().await;
+        let mut edge_scheduler = self.edge_scheduler.write().await;
+        match dut_state.get(signal_name) {
+            Ok(value) => {
+                edge_scheduler.set_clock(signal_name, value.any());
+                response::Payload::empty()
+            }
+            Err(e) => response::Payload::Error(Box::new(e)),
+        }
+    }
+
+    async fn serve_initialize(&self, init_spec: &InitSpec) -> response::Payload {
+        let dut_state = self.dut_state.read().await;
+        let mut simulation_result = self.simulation_result.write().await;
+        let mut oscillator_group = self.oscillator_group.write().await;
+        let mut stimulus_group = self.stimulus_group.write().await;
+        let mut edge_scheduler = self.edge_scheduler.write().await;
+        match self.initialize(
+            init_spec,
+            &mut simulation_result,
+            &dut_state,
+            &mut oscillator_group,
+            &mut stimulus_group,
+            &mut edge_scheduler,
+        ) {
+            Ok(current_time) => response::Payload::current_time(current_time),
+            Err(e) => response::Payload::Error(Box::new(e)),
+        }
+    }
+
+    fn initialize(
+        &self,
+        init_spec: &InitSpec,
+        simulation_result: &mut RwLockWriteGuard<'_, SimulationResult>,
+        dut_state: &RwLockReadGuard<'_, DutState>,
+        oscillator_group: &mut RwLockWriteGuard<'_, OscillatorGroup>,
+        stimulus_group: &mut RwLockWriteGuard<'_, StimulusGroup>,
+        edge_scheduler: &mut RwLockWriteGuard<'_, EdgeScheduler>,
+    ) -> OombakSimResult<usize> {
+        let mut default_values = vec![];
+        if let Some(input_default) = &init_spec.input_default {
+            for point in dut_state.probe()?.get_settable_points() {
+                let mut value = input_default.clone();
+                value.resize(point.bit_width(), false);
+                default_values.push((point.path().to_string(), value));
+            }
+        }
+        default_values.extend(init_spec.default_values.iter().cloned());
+        for (signal_name, value) in default_values.iter() {
+            oscillator_group.remove(signal_name);
+            stimulus_group.remove(signal_name);
+            edge_scheduler.cancel(signal_name);
+            dut_state.set(signal_name, value)?;
+        }
+
+        if let Some((signal_name, clock_spec)) = &init_spec.clock {
+            edge_scheduler.set_clock(signal_name, false);
+            Self::set_clock(
+                signal_name,
+                clock_spec,
+                dut_state,
+                simulation_result.current_time,
+                oscillator_group,
+                stimulus_group,
+                edge_scheduler,
+            )?;
+        }
+
+        if let Some(reset_spec) = &init_spec.reset {
+            self.apply_reset(
+                reset_spec,
+                simulation_result,
+                dut_state,
+                oscillator_group,
+                stimulus_group,
+                edge_scheduler,
+            )?;
+        }
+        Ok(simulation_result.current_time)
+    }
+
+    fn apply_reset(
+        &self,
+        reset_spec: &ResetSpec,
+        simulation_result: &mut RwLockWriteGuard<'_, SimulationResult>,
+        dut_state: &RwLockReadGuard<'_, DutState>,
+        oscillator_group: &mut RwLockWriteGuard<'_, OscillatorGroup>,
+        stimulus_group: &mut RwLockWriteGuard<'_, StimulusGroup>,
+        edge_scheduler: &mut RwLockWriteGuard<'_, EdgeScheduler>,
+    ) -> OombakSimResult<()> {
+        let signal_name = &reset_spec.signal_name;
+        oscillator_group.remove(signal_name);
+        stimulus_group.remove(signal_name);
+        edge_scheduler.cancel(signal_name);
+        let width = dut_state.get(signal_name)?.len();
+        let mut active_value = reset_spec.active_value.clone();
+        let mut inactive_value = reset_spec.inactive_value.clone();
+        active_value.resize(width, false);
+        inactive_value.resize(width, false);
+        dut_state.set(signal_name, &active_value)?;
+        self.run_cycles(
+            reset_spec.num_of_cycles,
+            simulation_result,
+            dut_state,
+            oscillator_group,
+            stimulus_group,
+            edge_scheduler,
+        )?;
+        dut_state.set(signal_name, &inactive_value)?;
+        Ok(())
+    }
+
+    async fn serve_set_at_edge(
+        &self,
+        signal_name: &str,
+        value: &BitVec<u32>,
+        edge: Edge,
+        skew: usize,
+    ) -> response::Payload {
+        let dut_state = self.dut_state.read().await;
+        let mut edge_scheduler = self.edge_scheduler.write().await;
+        if edge_scheduler.clock_signal_name().is_none() {
+            return response::Payload::Error(Box::new(Error::ReferenceClockNotSet));
+        }
+        let width = match dut_state.get(signal_name) {
+            Ok(current_value) => current_value.len(),
+            Err(e) => return response::Payload::Error(Box::new(e)),
+        };
+        edge_scheduler.schedule(signal_name, value, width, edge, skew);
         response::Payload::empty()
     }
 
+    fn check_stimulus_spec(stimulus_spec: &StimulusSpec) -> OombakSimResult<()> {
+        let has_zero_duration = match stimulus_spec {
+            StimulusSpec::Sequence { steps, .. } => steps.iter().any(|(_, d)| *d == 0),
+            StimulusSpec::Ramp { step_duration, .. }
+            | StimulusSpec::Random { step_duration, .. } => *step_duration == 0,
+        };
+        if has_zero_duration {
+            return Err(Error::InvalidStimulusSpec(
+                "step durations must be non-zero".to_string(),
+            ));
+        }
+        if let StimulusSpec::Sequence { steps, .. } = stimulus_spec {
+            if steps.is_empty() {
+                return Err(Error::InvalidStimulusSpec(
+                    "sequence must have at least one step".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_clock_spec(clock_spec: &ClockSpec) -> OombakSimResult<()> {
+        if clock_spec.high_duration == 0 || clock_spec.low_duration == 0 {
+            return Err(Error::InvalidClockSpec(
+                "high and low durations must be non-zero".to_string(),
+            ));
+        }
+        if 2 * clock_spec.jitter >= clock_spec.high_duration.min(clock_spec.low_duration) {
+            return Err(Error::InvalidClockSpec(
+                "jitter must be less than half of the shortest clock phase".to_string(),
+            ));
+        }
+        if clock_spec.num_of_cycles == Some(0) {
+            return Err(Error::InvalidClockSpec(
+                "number of cycles must be non-zero".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
     fn check_oscillator_parameters(
         signal_name: &str,
         low_value: &BitVec<u32>,
@@ -330,13 +765,11 @@ impl LocalSimulator {
             PathBuf::from(path)
         };
 
-        let notification_channel = self.channel.read().await.clone();
-
-        let (new_dut, temp_gen_dir) = spawn_blocking(move || {
-            Self::regenerate_dut(&path, &new_probe, notification_channel, message_id)
-        })
-        .await
-        .unwrap()?;
+        let builder = self.create_builder(message_id).await;
+        let (new_dut, temp_gen_dir) =
+            spawn_blocking(move || Self::regenerate_dut(&path, &new_probe, builder))
+                .await
+                .unwrap()?;
 
         {
             let mut dut_state = self.dut_state.write().await;
@@ -346,6 +779,10 @@ impl LocalSimulator {
         {
             let mut oscillator_group = self.oscillator_group.write().await;
             oscillator_group.clear();
+            let mut stimulus_group = self.stimulus_group.write().await;
+            stimulus_group.clear();
+            let mut edge_scheduler = self.edge_scheduler.write().await;
+            edge_scheduler.clear();
         }
 
         let dut_state = self.dut_state.read().await;
@@ -372,12 +809,10 @@ impl LocalSimulator {
     fn regenerate_dut(
         path: &Path,
         probe: &Probe,
-        notification_channel: Option<Sender<Message>>,
-        message_id: usize,
+        builder: oombak_gen::Builder,
     ) -> OombakSimResult<(LoadedDut, TempGenDir)> {
-        let builder = Self::create_builder(notification_channel, message_id);
         let temp_gen_dir = builder.build_with_probe(path, probe)?;
-        let loaded_dut = LoadedDut::from(probe);
+        let loaded_dut = LoadedDut::new(path, probe);
         Ok((loaded_dut, temp_gen_dir))
     }
 
@@ -389,16 +824,14 @@ impl LocalSimulator {
         Ok(())
     }
 
-    fn create_builder(
-        message_channel: Option<Sender<Message>>,
-        message_id: usize,
-    ) -> oombak_gen::Builder {
-        match message_channel {
+    async fn create_builder(&self, message_id: usize) -> oombak_gen::Builder {
+        let builder = match self.channel.read().await.clone() {
             Some(message_channel) => {
                 oombak_gen::Builder::new(NotificationChannel::new(message_channel, message_id))
             }
             None => oombak_gen::Builder::default(),
-        }
+        };
+        builder.options(self.build_options.clone())
     }
 
     async fn reset_is_dut_reloading(&self) {
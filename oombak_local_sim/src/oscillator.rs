@@ -1,6 +1,7 @@
 use std::collections::BinaryHeap;
 
 use bitvec::vec::BitVec;
+use oombak_sim::ClockSpec;
 
 #[derive(Default)]
 pub struct OscillatorGroup {
@@ -10,8 +11,12 @@ pub struct OscillatorGroup {
 #[derive(Eq, PartialEq)]
 pub struct Oscillator {
     signal_name: String,
-    period: usize,
+    high_duration: usize,
+    low_duration: usize,
+    remaining_cycles: Option<usize>,
+    jitter: Jitter,
     next_state: State,
+    next_edge_time: usize,
     next_trigger_time: usize,
     low_state_value: BitVec<u32>,
     high_state_value: BitVec<u32>,
@@ -23,16 +28,30 @@ enum State {
     High,
 }
 
+#[derive(Eq, PartialEq)]
+struct Jitter {
+    amplitude: usize,
+    seed: u64,
+}
+
 impl OscillatorGroup {
     pub fn insert(&mut self, oscillator: Oscillator) {
         self.remove(&oscillator.signal_name);
-        self.priority_queue.push(oscillator);
+        if !oscillator.is_finished() {
+            self.priority_queue.push(oscillator);
+        }
     }
 
     pub fn remove(&mut self, signal_name: &str) {
         self.priority_queue.retain(|e| e.signal_name != signal_name);
     }
 
+    pub fn contains(&self, signal_name: &str) -> bool {
+        self.priority_queue
+            .iter()
+            .any(|e| e.signal_name == signal_name)
+    }
+
     pub fn next_trigger_time(&self) -> Option<usize> {
         self.priority_queue.peek().map(|e| e.next_trigger_time)
     }
@@ -43,7 +62,9 @@ impl OscillatorGroup {
                 let mut oscillator = self.priority_queue.pop().unwrap();
                 let popped_name = oscillator.signal_name.clone();
                 let popped_value = oscillator.trip();
-                self.priority_queue.push(oscillator);
+                if !oscillator.is_finished() {
+                    self.priority_queue.push(oscillator);
+                }
                 return Some((popped_name, popped_value));
             }
         }
@@ -63,28 +84,97 @@ impl Oscillator {
         low_state_value: BitVec<u32>,
         high_state_value: BitVec<u32>,
     ) -> Self {
-        Self {
+        let clock_spec = ClockSpec {
+            phase: period,
+            ..ClockSpec::new(period, period)
+        };
+        Self::from_clock_spec(
+            signal_name,
+            &clock_spec,
+            current_time,
+            low_state_value,
+            high_state_value,
+        )
+    }
+
+    pub fn from_clock_spec(
+        signal_name: String,
+        clock_spec: &ClockSpec,
+        current_time: usize,
+        low_state_value: BitVec<u32>,
+        high_state_value: BitVec<u32>,
+    ) -> Self {
+        let jitter = Jitter::new(clock_spec.jitter, &signal_name);
+        let next_edge_time = current_time + clock_spec.phase;
+        let mut oscillator = Self {
             signal_name,
-            period,
+            high_duration: clock_spec.high_duration,
+            low_duration: clock_spec.low_duration,
+            remaining_cycles: clock_spec.num_of_cycles,
+            jitter,
             next_state: State::High,
-            next_trigger_time: current_time + period,
+            next_edge_time,
+            next_trigger_time: current_time,
             low_state_value,
             high_state_value,
-        }
+        };
+        oscillator.next_trigger_time = oscillator.jittered(next_edge_time, current_time);
+        oscillator
     }
 
     fn trip(&mut self) -> BitVec<u32> {
-        let popped_value = match self.next_state {
-            State::Low => self.low_state_value.clone(),
-            State::High => self.high_state_value.clone(),
+        let (popped_value, duration) = match self.next_state {
+            State::Low => {
+                if let Some(remaining_cycles) = self.remaining_cycles.as_mut() {
+                    *remaining_cycles = remaining_cycles.saturating_sub(1);
+                }
+                (self.low_state_value.clone(), self.low_duration)
+            }
+            State::High => (self.high_state_value.clone(), self.high_duration),
         };
-        self.next_trigger_time += self.period;
+        let last_trigger_time = self.next_trigger_time;
+        self.next_edge_time += duration;
+        self.next_trigger_time = self.jittered(self.next_edge_time, last_trigger_time + 1);
         self.next_state = match self.next_state {
             State::Low => State::High,
             State::High => State::Low,
         };
         popped_value
     }
+
+    fn is_finished(&self) -> bool {
+        self.remaining_cycles == Some(0)
+    }
+
+    fn jittered(&mut self, edge_time: usize, earliest_time: usize) -> usize {
+        let offset = self.jitter.next_offset();
+        let trigger_time = edge_time.saturating_add_signed(offset);
+        trigger_time.max(earliest_time)
+    }
+}
+
+impl Jitter {
+    fn new(amplitude: usize, signal_name: &str) -> Self {
+        // Seed from the signal name so that a given clock always produces the same edges
+        let seed = signal_name
+            .bytes()
+            .fold(0xcbf29ce484222325, |hash: u64, b| {
+                (hash ^ b as u64).wrapping_mul(0x100000001b3)
+            })
+            .max(1);
+        Self { amplitude, seed }
+    }
+
+    fn next_offset(&mut self) -> isize {
+        if self.amplitude == 0 {
+            return 0;
+        }
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        let span = 2 * self.amplitude as u64 + 1;
+        (self.seed % span) as isize - self.amplitude as isize
+    }
 }
 
 impl Ord for Oscillator {
@@ -101,3 +191,105 @@ impl PartialOrd for Oscillator {
         Some(self.cmp(other))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use bitvec::{bitvec, order::Lsb0, vec::BitVec};
+    use oombak_sim::ClockSpec;
+
+    use super::{Oscillator, OscillatorGroup};
+
+    fn pop_all_until(group: &mut OscillatorGroup, end_time: usize) -> Vec<(usize, BitVec<u32>)> {
+        let mut events = vec![];
+        while let Some(time) = group.next_trigger_time() {
+            if time > end_time {
+                break;
+            }
+            while let Some((_, value)) = group.try_pop(time) {
+                events.push((time, value));
+            }
+        }
+        events
+    }
+
+    fn clock(clock_spec: &ClockSpec, current_time: usize) -> Oscillator {
+        Oscillator::from_clock_spec(
+            "clk".to_string(),
+            clock_spec,
+            current_time,
+            bitvec![u32, Lsb0; 0],
+            bitvec![u32, Lsb0; 1],
+        )
+    }
+
+    #[test]
+    fn test_periodic() {
+        let mut group = OscillatorGroup::default();
+        group.insert(Oscillator::new(
+            "clk".to_string(),
+            5,
+            10,
+            bitvec![u32, Lsb0; 0],
+            bitvec![u32, Lsb0; 1],
+        ));
+        let times: Vec<usize> = pop_all_until(&mut group, 30)
+            .into_iter()
+            .map(|(t, _)| t)
+            .collect();
+        assert_eq!(times, vec![15, 20, 25, 30]);
+    }
+
+    #[test]
+    fn test_duty_cycle_and_phase() {
+        let clock_spec = ClockSpec {
+            phase: 2,
+            ..ClockSpec::new(3, 7)
+        };
+        let mut group = OscillatorGroup::default();
+        group.insert(clock(&clock_spec, 0));
+        let events = pop_all_until(&mut group, 22);
+        assert_eq!(
+            events,
+            vec![
+                (2, bitvec![u32, Lsb0; 1]),
+                (5, bitvec![u32, Lsb0; 0]),
+                (12, bitvec![u32, Lsb0; 1]),
+                (15, bitvec![u32, Lsb0; 0]),
+                (22, bitvec![u32, Lsb0; 1]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_finite_cycles() {
+        let clock_spec = ClockSpec {
+            num_of_cycles: Some(2),
+            ..ClockSpec::new(1, 1)
+        };
+        let mut group = OscillatorGroup::default();
+        group.insert(clock(&clock_spec, 0));
+        let times: Vec<usize> = pop_all_until(&mut group, 100)
+            .into_iter()
+            .map(|(t, _)| t)
+            .collect();
+        assert_eq!(times, vec![0, 1, 2, 3]);
+        assert_eq!(group.next_trigger_time(), None);
+    }
+
+    #[test]
+    fn test_jitter_stays_within_bounds() {
+        let clock_spec = ClockSpec {
+            phase: 10,
+            jitter: 2,
+            ..ClockSpec::new(10, 10)
+        };
+        let mut group = OscillatorGroup::default();
+        group.insert(clock(&clock_spec, 0));
+        let events = pop_all_until(&mut group, 1000);
+        for (i, (time, _)) in events.iter().enumerate() {
+            let nominal_time = 10 + 10 * i;
+            assert!(time.abs_diff(nominal_time) <= 2);
+        }
+        assert!(events.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+}
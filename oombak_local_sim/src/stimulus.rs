@@ -0,0 +1,336 @@
+use std::collections::BinaryHeap;
+
+use bitvec::vec::BitVec;
+use oombak_sim::StimulusSpec;
+
+#[derive(Default)]
+pub struct StimulusGroup {
+    priority_queue: BinaryHeap<Stimulus>,
+}
+
+#[derive(Eq, PartialEq)]
+pub struct Stimulus {
+    signal_name: String,
+    generator: Generator,
+    next_trigger_time: usize,
+}
+
+#[derive(Eq, PartialEq)]
+enum Generator {
+    Sequence {
+        steps: Vec<(BitVec<u32>, usize)>,
+        index: usize,
+        repeat: bool,
+    },
+    Ramp {
+        value: BitVec<u32>,
+        increment: BitVec<u32>,
+        step_duration: usize,
+        remaining_steps: Option<usize>,
+    },
+    Random {
+        lfsr: u64,
+        width: usize,
+        step_duration: usize,
+        remaining_steps: Option<usize>,
+    },
+}
+
+impl StimulusGroup {
+    pub fn insert(&mut self, stimulus: Stimulus) {
+        self.remove(&stimulus.signal_name);
+        if !stimulus.is_finished() {
+            self.priority_queue.push(stimulus);
+        }
+    }
+
+    pub fn remove(&mut self, signal_name: &str) {
+        self.priority_queue.retain(|e| e.signal_name != signal_name);
+    }
+
+    pub fn contains(&self, signal_name: &str) -> bool {
+        self.priority_queue
+            .iter()
+            .any(|e| e.signal_name == signal_name)
+    }
+
+    pub fn next_trigger_time(&self) -> Option<usize> {
+        self.priority_queue.peek().map(|e| e.next_trigger_time)
+    }
+
+    pub fn try_pop(&mut self, current_time: usize) -> Option<(String, BitVec<u32>)> {
+        if let Some(stimulus) = self.priority_queue.peek() {
+            if stimulus.next_trigger_time == current_time {
+                let mut stimulus = self.priority_queue.pop().unwrap();
+                let popped_name = stimulus.signal_name.clone();
+                let popped_value = stimulus.trip();
+                if !stimulus.is_finished() {
+                    self.priority_queue.push(stimulus);
+                }
+                return Some((popped_name, popped_value));
+            }
+        }
+        None
+    }
+
+    pub fn clear(&mut self) {
+        self.priority_queue.clear();
+    }
+}
+
+impl Stimulus {
+    pub fn new(
+        signal_name: String,
+        stimulus_spec: &StimulusSpec,
+        width: usize,
+        current_time: usize,
+    ) -> Self {
+        let generator = match stimulus_spec {
+            StimulusSpec::Sequence { steps, repeat } => Generator::Sequence {
+                steps: steps
+                    .iter()
+                    .map(|(value, duration)| (resized(value, width), *duration))
+                    .collect(),
+                index: 0,
+                repeat: *repeat,
+            },
+            StimulusSpec::Ramp {
+                start,
+                increment,
+                step_duration,
+                num_of_steps,
+            } => Generator::Ramp {
+                value: resized(start, width),
+                increment: bitvec_from_i64(*increment, width),
+                step_duration: *step_duration,
+                remaining_steps: *num_of_steps,
+            },
+            StimulusSpec::Random {
+                seed,
+                step_duration,
+                num_of_steps,
+            } => Generator::Random {
+                lfsr: (*seed).max(1),
+                width,
+                step_duration: *step_duration,
+                remaining_steps: *num_of_steps,
+            },
+        };
+        Self {
+            signal_name,
+            generator,
+            next_trigger_time: current_time,
+        }
+    }
+
+    fn trip(&mut self) -> BitVec<u32> {
+        let (value, duration) = self.generator.next_value();
+        self.next_trigger_time += duration;
+        value
+    }
+
+    fn is_finished(&self) -> bool {
+        match &self.generator {
+            Generator::Sequence {
+                steps,
+                index,
+                repeat,
+            } => steps.is_empty() || (!repeat && *index >= steps.len()),
+            Generator::Ramp {
+                remaining_steps, ..
+            }
+            | Generator::Random {
+                remaining_steps, ..
+            } => *remaining_steps == Some(0),
+        }
+    }
+}
+
+impl Generator {
+    fn next_value(&mut self) -> (BitVec<u32>, usize) {
+        match self {
+            Generator::Sequence {
+                steps,
+                index,
+                repeat,
+            } => {
+                let (value, duration) = steps[*index].clone();
+                *index += 1;
+                if *repeat && *index == steps.len() {
+                    *index = 0;
+                }
+                (value, duration)
+            }
+            Generator::Ramp {
+                value,
+                increment,
+                step_duration,
+                remaining_steps,
+            } => {
+                let popped_value = value.clone();
+                *value = wrapping_add(value, increment);
+                decrement(remaining_steps);
+                (popped_value, *step_duration)
+            }
+            Generator::Random {
+                lfsr,
+                width,
+                step_duration,
+                remaining_steps,
+            } => {
+                let mut value = BitVec::with_capacity(*width);
+                for _ in 0..*width {
+                    value.push(step_lfsr(lfsr));
+                }
+                decrement(remaining_steps);
+                (value, *step_duration)
+            }
+        }
+    }
+}
+
+impl Ord for Stimulus {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other
+            .next_trigger_time
+            .cmp(&self.next_trigger_time)
+            .then_with(|| self.signal_name.cmp(&other.signal_name))
+    }
+}
+
+impl PartialOrd for Stimulus {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn decrement(remaining_steps: &mut Option<usize>) {
+    if let Some(remaining_steps) = remaining_steps.as_mut() {
+        *remaining_steps = remaining_steps.saturating_sub(1);
+    }
+}
+
+/// Galois LFSR with the maximal-length polynomial x^64 + x^63 + x^61 + x^60 + 1
+fn step_lfsr(lfsr: &mut u64) -> bool {
+    let output = *lfsr & 1 == 1;
+    *lfsr >>= 1;
+    if output {
+        *lfsr ^= 0xD800_0000_0000_0000;
+    }
+    output
+}
+
+fn wrapping_add(lhs: &BitVec<u32>, rhs: &BitVec<u32>) -> BitVec<u32> {
+    let mut carry = false;
+    let mut result = BitVec::with_capacity(lhs.len());
+    for (a, b) in lhs.iter().by_vals().zip(rhs.iter().by_vals()) {
+        result.push(a ^ b ^ carry);
+        carry = (a && b) || (carry && (a ^ b));
+    }
+    result
+}
+
+fn bitvec_from_i64(value: i64, width: usize) -> BitVec<u32> {
+    (0..width)
+        .map(|i| {
+            if i < 64 {
+                (value >> i) & 1 == 1
+            } else {
+                value < 0
+            }
+        })
+        .collect()
+}
+
+fn resized(bit_vec: &BitVec<u32>, width: usize) -> BitVec<u32> {
+    let mut bit_vec = bit_vec.clone();
+    bit_vec.resize(width, false);
+    bit_vec
+}
+
+#[cfg(test)]
+mod test {
+    use bitvec::{bitvec, order::Lsb0, vec::BitVec};
+    use oombak_sim::StimulusSpec;
+
+    use super::{Stimulus, StimulusGroup};
+
+    fn pop_all(group: &mut StimulusGroup) -> Vec<(usize, BitVec<u32>)> {
+        let mut events = vec![];
+        while let Some(time) = group.next_trigger_time() {
+            while let Some((_, value)) = group.try_pop(time) {
+                events.push((time, value));
+            }
+        }
+        events
+    }
+
+    #[test]
+    fn test_sequence() {
+        let stimulus_spec = StimulusSpec::Sequence {
+            steps: vec![(bitvec![u32, Lsb0; 1, 0], 3), (bitvec![u32, Lsb0; 0, 1], 5)],
+            repeat: false,
+        };
+        let mut group = StimulusGroup::default();
+        group.insert(Stimulus::new("data".to_string(), &stimulus_spec, 2, 10));
+        assert_eq!(
+            pop_all(&mut group),
+            vec![
+                (10, bitvec![u32, Lsb0; 1, 0]),
+                (13, bitvec![u32, Lsb0; 0, 1])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sequence_is_resized() {
+        let stimulus_spec = StimulusSpec::Sequence {
+            steps: vec![(bitvec![u32, Lsb0; 1, 0, 1], 1), (bitvec![u32, Lsb0; 1], 1)],
+            repeat: false,
+        };
+        let mut group = StimulusGroup::default();
+        group.insert(Stimulus::new("data".to_string(), &stimulus_spec, 2, 0));
+        assert_eq!(
+            pop_all(&mut group),
+            vec![(0, bitvec![u32, Lsb0; 1, 0]), (1, bitvec![u32, Lsb0; 1, 0])]
+        );
+    }
+
+    #[test]
+    fn test_ramp_wraps_around() {
+        let stimulus_spec = StimulusSpec::Ramp {
+            start: bitvec![u32, Lsb0; 0, 1],
+            increment: -1,
+            step_duration: 2,
+            num_of_steps: Some(4),
+        };
+        let mut group = StimulusGroup::default();
+        group.insert(Stimulus::new("data".to_string(), &stimulus_spec, 2, 0));
+        assert_eq!(
+            pop_all(&mut group),
+            vec![
+                (0, bitvec![u32, Lsb0; 0, 1]),
+                (2, bitvec![u32, Lsb0; 1, 0]),
+                (4, bitvec![u32, Lsb0; 0, 0]),
+                (6, bitvec![u32, Lsb0; 1, 1]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_random_is_reproducible() {
+        let stimulus_spec = StimulusSpec::Random {
+            seed: 42,
+            step_duration: 1,
+            num_of_steps: Some(16),
+        };
+        let mut group = StimulusGroup::default();
+        group.insert(Stimulus::new("data".to_string(), &stimulus_spec, 8, 0));
+        let first = pop_all(&mut group);
+        group.insert(Stimulus::new("data".to_string(), &stimulus_spec, 8, 0));
+        let second = pop_all(&mut group);
+        assert_eq!(first.len(), 16);
+        assert_eq!(first, second);
+        assert!(first.iter().all(|(_, value)| value.len() == 8));
+    }
+}
@@ -0,0 +1,35 @@
+//! An example plugin library, showing values as Gray code. Build it with
+//! `cargo build -p oombak_plugin_example` and load it with
+//! `--plugin target/debug/liboombak_plugin_example.so`.
+
+use oombak_tui::plugin::{BitVec, Registrar, ValueFormatter};
+
+struct Gray;
+
+impl ValueFormatter for Gray {
+    fn name(&self) -> &str {
+        "gray"
+    }
+
+    fn format(&self, value: &BitVec<u32>, width: usize) -> String {
+        let mut binary = false;
+        let bits: String = (0..width)
+            .rev()
+            .map(|i| {
+                binary ^= value.get(i).is_some_and(|bit| *bit);
+                if binary {
+                    '1'
+                } else {
+                    '0'
+                }
+            })
+            .collect();
+        format!("0b{bits}")
+    }
+}
+
+fn register(registrar: &mut Registrar) {
+    registrar.register_formatter(Box::new(Gray));
+}
+
+oombak_tui::declare_plugin!(register);
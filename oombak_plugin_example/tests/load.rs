@@ -0,0 +1,27 @@
+use std::{
+    env::consts::{DLL_PREFIX, DLL_SUFFIX},
+    path::PathBuf,
+};
+
+use oombak_tui::plugin::{self, BitVec};
+
+/// The library built along with this test, next to the test executable
+fn library_path() -> PathBuf {
+    let test_path = std::env::current_exe().unwrap();
+    let deps_dir = test_path.parent().unwrap();
+    deps_dir.join(format!("{DLL_PREFIX}oombak_plugin_example{DLL_SUFFIX}"))
+}
+
+#[test]
+fn test_load() {
+    assert_eq!(
+        plugin::load(&library_path()),
+        Ok(vec!["formatter gray".to_string()])
+    );
+
+    let formatter = plugin::find_formatter("gray").unwrap();
+    let value: BitVec<u32> = [false, true, true].into_iter().collect();
+    assert_eq!(formatter.format(&value, 3), "0b100");
+
+    assert!(plugin::load(&library_path()).is_err());
+}
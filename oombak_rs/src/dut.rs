@@ -81,7 +81,7 @@ impl Dut {
     }
 
     fn bitvec_from(words_ptr: *const u32, n_bits: usize) -> BitVec<u32> {
-        let num_of_words = n_bits / 32 + if n_bits % 32 != 0 { 1 } else { 0 };
+        let num_of_words = n_bits / 32 + if !n_bits.is_multiple_of(32) { 1 } else { 0 };
         let slice = unsafe { std::slice::from_raw_parts(words_ptr, num_of_words) };
         let mut bit_vec = BitVec::from_slice(slice);
         bit_vec.truncate(n_bits);
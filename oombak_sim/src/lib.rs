@@ -8,7 +8,7 @@ pub use request::Request;
 pub use response::Response;
 
 pub use oombak_rs::probe::{InstanceNode, Probe, Signal, SignalType};
-pub use request::ProbePointsModification;
+pub use request::{ClockSpec, Edge, InitSpec, ProbePointsModification, ResetSpec, StimulusSpec};
 pub use response::{CompactWaveValue, LoadedDut, SimulationResult, Wave};
 
 #[async_trait]
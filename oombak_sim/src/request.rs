@@ -14,14 +14,71 @@ pub struct Request {
 #[derive(Clone, PartialEq, Eq)]
 pub enum Payload {
     Run(usize),
+    RunCycles(usize),
     SetSignal(String, BitVec<u32>),
     SetPeriodic(String, usize, BitVec<u32>, BitVec<u32>),
-    Load(PathBuf),
+    SetClock(String, ClockSpec),
+    SetStimulus(String, StimulusSpec),
+    SetReferenceClock(String),
+    SetAtEdge(String, BitVec<u32>, Edge, usize),
+    Initialize(InitSpec),
+    /// Loads the design file, with the top-level module named after the file unless given
+    Load(PathBuf, Option<String>),
     ModifyProbedPoints(ProbePointsModification),
     GetSimulationResult,
     Terminate,
 }
 
+#[derive(Clone, PartialEq, Eq)]
+pub struct ClockSpec {
+    pub high_duration: usize,
+    pub low_duration: usize,
+    pub phase: usize,
+    pub num_of_cycles: Option<usize>,
+    pub jitter: usize,
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub enum StimulusSpec {
+    Sequence {
+        steps: Vec<(BitVec<u32>, usize)>,
+        repeat: bool,
+    },
+    Ramp {
+        start: BitVec<u32>,
+        increment: i64,
+        step_duration: usize,
+        num_of_steps: Option<usize>,
+    },
+    Random {
+        seed: u64,
+        step_duration: usize,
+        num_of_steps: Option<usize>,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Edge {
+    Rising,
+    Falling,
+}
+
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct InitSpec {
+    pub input_default: Option<BitVec<u32>>,
+    pub default_values: Vec<(String, BitVec<u32>)>,
+    pub clock: Option<(String, ClockSpec)>,
+    pub reset: Option<ResetSpec>,
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct ResetSpec {
+    pub signal_name: String,
+    pub active_value: BitVec<u32>,
+    pub inactive_value: BitVec<u32>,
+    pub num_of_cycles: usize,
+}
+
 #[derive(Clone, PartialEq, Eq)]
 pub struct ProbePointsModification {
     pub to_add: Vec<String>,
@@ -35,6 +92,12 @@ impl Request {
         Message::Request(Self { id, payload })
     }
 
+    pub fn run_cycles(num_of_cycles: usize) -> Message {
+        let id = Self::random_id();
+        let payload = Payload::RunCycles(num_of_cycles);
+        Message::Request(Self { id, payload })
+    }
+
     pub fn set_signal(signal_name: String, value: BitVec<u32>) -> Message {
         let id = Self::random_id();
         let payload = Payload::SetSignal(signal_name, value);
@@ -52,9 +115,44 @@ impl Request {
         Message::Request(Self { id, payload })
     }
 
-    pub fn load(sv_path: PathBuf) -> Message {
+    pub fn set_clock(signal_name: String, clock_spec: ClockSpec) -> Message {
+        let id = Self::random_id();
+        let payload = Payload::SetClock(signal_name, clock_spec);
+        Message::Request(Self { id, payload })
+    }
+
+    pub fn set_stimulus(signal_name: String, stimulus_spec: StimulusSpec) -> Message {
+        let id = Self::random_id();
+        let payload = Payload::SetStimulus(signal_name, stimulus_spec);
+        Message::Request(Self { id, payload })
+    }
+
+    pub fn set_reference_clock(signal_name: String) -> Message {
+        let id = Self::random_id();
+        let payload = Payload::SetReferenceClock(signal_name);
+        Message::Request(Self { id, payload })
+    }
+
+    pub fn set_at_edge(
+        signal_name: String,
+        value: BitVec<u32>,
+        edge: Edge,
+        skew: usize,
+    ) -> Message {
+        let id = Self::random_id();
+        let payload = Payload::SetAtEdge(signal_name, value, edge, skew);
+        Message::Request(Self { id, payload })
+    }
+
+    pub fn initialize(init_spec: InitSpec) -> Message {
+        let id = Self::random_id();
+        let payload = Payload::Initialize(init_spec);
+        Message::Request(Self { id, payload })
+    }
+
+    pub fn load(design_path: PathBuf, top_module: Option<String>) -> Message {
         let id = Self::random_id();
-        let payload = Payload::Load(sv_path);
+        let payload = Payload::Load(design_path, top_module);
         Message::Request(Self { id, payload })
     }
 
@@ -82,6 +180,41 @@ impl Request {
     }
 }
 
+impl ClockSpec {
+    pub fn new(high_duration: usize, low_duration: usize) -> Self {
+        Self {
+            high_duration,
+            low_duration,
+            phase: 0,
+            num_of_cycles: None,
+            jitter: 0,
+        }
+    }
+
+    pub fn period(&self) -> usize {
+        self.high_duration + self.low_duration
+    }
+}
+
+impl std::fmt::Display for StimulusSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StimulusSpec::Sequence { steps, .. } => write!(f, "Sequence({})", steps.len()),
+            StimulusSpec::Ramp { increment, .. } => write!(f, "Ramp({increment})"),
+            StimulusSpec::Random { seed, .. } => write!(f, "Random({seed})"),
+        }
+    }
+}
+
+impl std::fmt::Display for Edge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Edge::Rising => write!(f, "rising"),
+            Edge::Falling => write!(f, "falling"),
+        }
+    }
+}
+
 impl std::fmt::Display for Payload {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -90,7 +223,24 @@ impl std::fmt::Display for Payload {
             Payload::SetPeriodic(signal_name, period, _, _) => {
                 write!(f, "SetPeriodic({signal_name}, {period})")
             }
-            Payload::Load(path) => write!(f, "Load({})", path.to_str().unwrap()),
+            Payload::SetClock(signal_name, clock_spec) => {
+                write!(f, "SetClock({signal_name}, {})", clock_spec.period())
+            }
+            Payload::SetStimulus(signal_name, stimulus_spec) => {
+                write!(f, "SetStimulus({signal_name}, {stimulus_spec})")
+            }
+            Payload::RunCycles(num_of_cycles) => write!(f, "RunCycles({num_of_cycles})"),
+            Payload::SetReferenceClock(signal_name) => {
+                write!(f, "SetReferenceClock({signal_name})")
+            }
+            Payload::SetAtEdge(signal_name, _, edge, skew) => {
+                write!(f, "SetAtEdge({signal_name}, {edge}, {skew})")
+            }
+            Payload::Initialize(_) => write!(f, "Initialize"),
+            Payload::Load(path, None) => write!(f, "Load({})", path.to_str().unwrap()),
+            Payload::Load(path, Some(top_module)) => {
+                write!(f, "Load({}, {top_module})", path.to_str().unwrap())
+            }
             Payload::ModifyProbedPoints(_) => write!(f, "ModifyProbedPoints"),
             Payload::GetSimulationResult => write!(f, "GetSimulationResult"),
             Payload::Terminate => write!(f, "Terminate"),
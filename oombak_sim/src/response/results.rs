@@ -1,8 +1,13 @@
+use std::path::{Path, PathBuf};
+
 use oombak_rs::probe::{InstanceNode, Probe};
 
 use bitvec::vec::BitVec;
 
+use crate::Edge;
+
 pub struct LoadedDut {
+    pub path: PathBuf,
     pub root_node: InstanceNode,
     pub probed_points: Vec<String>,
 }
@@ -28,8 +33,8 @@ pub struct CompactWaveValue {
     duration: usize,
 }
 
-impl From<&Probe> for LoadedDut {
-    fn from(probe: &Probe) -> Self {
+impl LoadedDut {
+    pub fn new(path: &Path, probe: &Probe) -> Self {
         let probed_points = probe
             .get_probed_points()
             .iter()
@@ -37,6 +42,7 @@ impl From<&Probe> for LoadedDut {
             .collect();
         let root_node = probe.root_node().clone();
         LoadedDut {
+            path: path.to_path_buf(),
             probed_points,
             root_node,
         }
@@ -45,16 +51,20 @@ impl From<&Probe> for LoadedDut {
 
 impl From<oombak_rs::dut::Signal> for Wave {
     fn from(signal: oombak_rs::dut::Signal) -> Self {
+        Wave::new(signal.name, signal.width as usize)
+    }
+}
+
+impl Wave {
+    pub fn new(signal_name: String, width: usize) -> Self {
         Wave {
-            signal_name: signal.name,
-            width: signal.width as usize,
+            signal_name,
+            width,
             compact_values: vec![],
             start_times: vec![],
         }
     }
-}
 
-impl Wave {
     pub fn slice(&self, start_time: usize, end_time: usize) -> Option<Vec<CompactWaveValue>> {
         let (start_idx, start_offset) = match self.find(start_time) {
             Some((a, b)) => (a, b),
@@ -133,6 +143,71 @@ impl Wave {
         }
     }
 
+    /// Returns the time of the first value change after `time`.
+    pub fn next_change(&self, time: usize) -> Option<usize> {
+        self.next_start_time(time, |idx| idx > 0)
+    }
+
+    /// Returns the time of the last value change before `time`.
+    pub fn previous_change(&self, time: usize) -> Option<usize> {
+        self.previous_start_time(time, |idx| idx > 0)
+    }
+
+    /// Returns the time of the first `edge` after `time`. Only meaningful for 1-bit waves.
+    pub fn next_edge(&self, time: usize, edge: Edge) -> Option<usize> {
+        self.next_start_time(time, |idx| self.is_edge(idx, edge))
+    }
+
+    /// Returns the time of the last `edge` before `time`. Only meaningful for 1-bit waves.
+    pub fn previous_edge(&self, time: usize, edge: Edge) -> Option<usize> {
+        self.previous_start_time(time, |idx| self.is_edge(idx, edge))
+    }
+
+    /// Returns the time at which the wave next takes `value`, after `time`.
+    pub fn next_occurrence(&self, time: usize, value: &BitVec<u32>) -> Option<usize> {
+        let value = self.resized(value);
+        self.next_start_time(time, |idx| self.compact_values[idx].value == value)
+    }
+
+    /// Returns the time at which the wave last took `value`, before `time`.
+    pub fn previous_occurrence(&self, time: usize, value: &BitVec<u32>) -> Option<usize> {
+        let value = self.resized(value);
+        self.previous_start_time(time, |idx| self.compact_values[idx].value == value)
+    }
+
+    fn next_start_time(&self, time: usize, is_match: impl Fn(usize) -> bool) -> Option<usize> {
+        let start_idx = self.start_times.partition_point(|t| *t <= time);
+        (start_idx..self.start_times.len())
+            .find(|idx| is_match(*idx))
+            .map(|idx| self.start_times[idx])
+    }
+
+    fn previous_start_time(&self, time: usize, is_match: impl Fn(usize) -> bool) -> Option<usize> {
+        let end_idx = self.start_times.partition_point(|t| *t < time);
+        (0..end_idx)
+            .rev()
+            .find(|idx| is_match(*idx))
+            .map(|idx| self.start_times[idx])
+    }
+
+    fn is_edge(&self, idx: usize, edge: Edge) -> bool {
+        if idx == 0 {
+            return false;
+        }
+        let previous_level = self.compact_values[idx - 1].value.any();
+        let level = self.compact_values[idx].value.any();
+        match edge {
+            Edge::Rising => !previous_level && level,
+            Edge::Falling => previous_level && !level,
+        }
+    }
+
+    fn resized(&self, value: &BitVec<u32>) -> BitVec<u32> {
+        let mut value = value.clone();
+        value.resize(self.width, false);
+        value
+    }
+
     fn find(&self, time: usize) -> Option<(usize, usize)> {
         if self.start_times.is_empty() || time > self.end_time() {
             return None;
@@ -156,3 +231,37 @@ impl CompactWaveValue {
         self.duration
     }
 }
+
+#[cfg(test)]
+mod test {
+    use bitvec::{bitvec, order::Lsb0, vec::BitVec};
+
+    use super::{CompactWaveValue, Wave};
+    use crate::Edge;
+
+    fn wave() -> Wave {
+        let mut wave = Wave::new("clk".to_string(), 1);
+        for (value, duration) in [(false, 5), (true, 5), (false, 5), (true, 5)] {
+            wave.append(CompactWaveValue::new(BitVec::repeat(value, 1), duration));
+        }
+        wave
+    }
+
+    #[test]
+    fn test_search() {
+        let wave = wave();
+        assert_eq!(wave.next_change(0), Some(5));
+        assert_eq!(wave.next_change(5), Some(10));
+        assert_eq!(wave.next_change(15), None);
+        assert_eq!(wave.previous_change(12), Some(10));
+        assert_eq!(wave.previous_change(10), Some(5));
+        assert_eq!(wave.previous_change(5), None);
+        assert_eq!(wave.next_edge(5, Edge::Rising), Some(15));
+        assert_eq!(wave.previous_edge(20, Edge::Falling), Some(10));
+        assert_eq!(wave.next_occurrence(0, &bitvec![u32, Lsb0; 1, 0]), Some(5));
+        assert_eq!(
+            wave.previous_occurrence(10, &bitvec![u32, Lsb0; 0]),
+            Some(0)
+        );
+    }
+}
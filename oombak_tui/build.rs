@@ -0,0 +1,14 @@
+use std::{env, process::Command};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    // Plugin libraries must have been built by the same compiler, see `plugin::BUILD_ID`
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let output = Command::new(rustc).arg("--version").output().unwrap();
+    let rustc_version = String::from_utf8(output.stdout).unwrap();
+    println!(
+        "cargo:rustc-env=OOMBAK_RUSTC_VERSION={}",
+        rustc_version.trim()
+    );
+}
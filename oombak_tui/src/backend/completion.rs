@@ -0,0 +1,122 @@
+use super::interpreter;
+
+#[derive(Debug, PartialEq)]
+pub enum Completion {
+    /// Text to insert at the cursor
+    Insert(String),
+    /// Alternatives sharing no longer prefix than the word typed so far
+    Candidates(Vec<String>),
+    None,
+}
+
+/// Completes the word before the cursor: the command name, an option name of the command, or a
+/// signal path of the loaded design, also as an option value. Signal paths are completed one
+/// hierarchy level at a time.
+pub fn complete(line: &str, signal_paths: &[String]) -> Completion {
+    let word_start = line
+        .rfind(char::is_whitespace)
+        .map(|idx| idx + 1)
+        .unwrap_or(0);
+    let word = &line[word_start..];
+    let mut words = line.split_whitespace();
+    let is_first_word = line[..word_start].trim().is_empty();
+
+    let (word, mut candidates) = if is_first_word {
+        let candidates = interpreter::command_names()
+            .filter(|name| name.starts_with(word))
+            .map(|name| format!("{name} "))
+            .collect();
+        (word, candidates)
+    } else if let Some((_, value)) = word.split_once('=') {
+        (value, signal_path_candidates(value, signal_paths))
+    } else {
+        let command_name = words.next().unwrap_or_default();
+        let mut candidates: Vec<String> = interpreter::option_names(command_name)
+            .into_iter()
+            .filter(|name| name.starts_with(word))
+            .map(|name| format!("{name}="))
+            .collect();
+        candidates.extend(signal_path_candidates(word, signal_paths));
+        (word, candidates)
+    };
+    candidates.sort();
+    candidates.dedup();
+
+    match &candidates[..] {
+        [] => Completion::None,
+        [candidate] => Completion::Insert(candidate[word.len()..].to_string()),
+        [first, ..] => {
+            let common_prefix_len = candidates.iter().fold(first.len(), |len, candidate| {
+                common_prefix_len(&first[..len], candidate)
+            });
+            if common_prefix_len > word.len() {
+                Completion::Insert(first[word.len()..common_prefix_len].to_string())
+            } else {
+                let candidates = candidates.iter().map(|c| c.trim_end().to_string());
+                Completion::Candidates(candidates.collect())
+            }
+        }
+    }
+}
+
+fn signal_path_candidates(word: &str, signal_paths: &[String]) -> Vec<String> {
+    signal_paths
+        .iter()
+        .filter_map(|path| path.strip_prefix(word).map(|rest| (path, rest)))
+        .map(|(path, rest)| match rest.find('.') {
+            Some(idx) => path[..word.len() + idx + 1].to_string(),
+            None => format!("{path} "),
+        })
+        .collect()
+}
+
+fn common_prefix_len(a: &str, b: &str) -> usize {
+    a.char_indices()
+        .zip(b.chars())
+        .find(|((_, a), b)| a != b)
+        .map(|((idx, _), _)| idx)
+        .unwrap_or(a.len().min(b.len()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{complete, Completion};
+
+    #[test]
+    fn test_complete() {
+        let signal_paths: Vec<String> = ["top.clk", "top.fifo.count", "top.fifo.full"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let complete = |line: &str| complete(line, &signal_paths);
+
+        assert_eq!(complete("ru"), Completion::Insert("n ".to_string()));
+        assert_eq!(complete("set-r"), Completion::Insert("a".to_string()));
+        assert_eq!(
+            complete("set-ra"),
+            Completion::Candidates(vec!["set-ramp".to_string(), "set-random".to_string()])
+        );
+        assert_eq!(complete("xyz"), Completion::None);
+        assert_eq!(complete("set t"), Completion::Insert("op.".to_string()));
+        assert_eq!(
+            complete("set top."),
+            Completion::Candidates(vec!["top.clk".to_string(), "top.fifo.".to_string()])
+        );
+        assert_eq!(
+            complete("set top.f"),
+            Completion::Insert("ifo.".to_string())
+        );
+        assert_eq!(
+            complete("set top.fifo.c"),
+            Completion::Insert("ount ".to_string())
+        );
+        assert_eq!(
+            complete("find change s"),
+            Completion::Insert("ignal=".to_string())
+        );
+        assert_eq!(
+            complete("find change signal=top.c"),
+            Completion::Insert("lk ".to_string())
+        );
+    }
+}
@@ -0,0 +1,419 @@
+mod ahb;
+mod apb;
+mod axi;
+mod i2c;
+mod spi;
+mod stream;
+mod uart;
+mod wishbone;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+    sync::{Arc, OnceLock, RwLock},
+};
+
+use bitvec::vec::BitVec;
+use oombak_sim::{response::Wave, Edge};
+
+use crate::{components::models::SimulationSpec, utils::bitvec_str};
+
+/// Decodes the recorded waves of the signals of a bus into transactions. Besides the built-in
+/// ones, decoders can be registered by plugins.
+pub trait WaveDecoder: Send + Sync {
+    /// Name of the protocol, as given to the `decode` command
+    fn protocol(&self) -> &'static str;
+
+    fn description(&self) -> &'static str;
+
+    fn signal_roles(&self) -> &'static [SignalRole];
+
+    /// Names of the arguments that are not signals, e.g. a baud rate
+    fn parameters(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn decode(&self, input: &DecoderInput) -> Result<Vec<Transaction>, String>;
+}
+
+pub struct SignalRole {
+    pub name: &'static str,
+    pub is_optional: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Transaction {
+    pub start_time: usize,
+    pub end_time: usize,
+    pub label: String,
+    pub is_error: bool,
+}
+
+/// The waves bound to the signal roles of a protocol, and the parameters.
+pub struct DecoderInput<'a> {
+    waves: HashMap<&'static str, &'a Wave>,
+    parameters: HashMap<&'a str, &'a str>,
+    time_step_ps: usize,
+}
+
+pub struct Decoded {
+    pub transactions: Vec<Transaction>,
+    /// Names of the signals bound to the roles of the protocol
+    pub signal_names: Vec<String>,
+}
+
+static DECODERS: OnceLock<RwLock<Vec<Arc<dyn WaveDecoder>>>> = OnceLock::new();
+
+fn registry() -> &'static RwLock<Vec<Arc<dyn WaveDecoder>>> {
+    DECODERS.get_or_init(|| {
+        RwLock::new(vec![
+            Arc::new(axi::Axi { is_lite: false }),
+            Arc::new(axi::Axi { is_lite: true }),
+            Arc::new(apb::Apb),
+            Arc::new(ahb::Ahb),
+            Arc::new(wishbone::Wishbone),
+            Arc::new(stream::Stream),
+            Arc::new(uart::Uart),
+            Arc::new(spi::Spi),
+            Arc::new(i2c::I2c),
+        ])
+    })
+}
+
+pub fn decoders() -> Vec<Arc<dyn WaveDecoder>> {
+    registry().read().unwrap().clone()
+}
+
+pub fn find(protocol: &str) -> Option<Arc<dyn WaveDecoder>> {
+    registry()
+        .read()
+        .unwrap()
+        .iter()
+        .find(|decoder| decoder.protocol() == protocol)
+        .cloned()
+}
+
+/// Adds a decoder after the built-in ones. A protocol can only be registered once.
+pub fn register(decoder: Arc<dyn WaveDecoder>) -> Result<(), String> {
+    let mut decoders = registry().write().unwrap();
+    let protocol = decoder.protocol();
+    if decoders.iter().any(|d| d.protocol() == protocol) {
+        return Err(format!(
+            "a decoder of protocol \"{protocol}\" is already registered"
+        ));
+    }
+    decoders.push(decoder);
+    Ok(())
+}
+
+/// Lists the protocols with their signal roles, optional ones in brackets, and parameters.
+pub fn usage() -> String {
+    let mut usage = String::new();
+    for decoder in decoders() {
+        let roles = decoder.signal_roles().iter().map(|role| match role {
+            SignalRole {
+                name,
+                is_optional: true,
+            } => format!("[{name}]"),
+            SignalRole { name, .. } => name.to_string(),
+        });
+        let parameters = decoder.parameters().iter().map(|name| format!("{name}="));
+        let arguments: Vec<String> = roles.chain(parameters).collect();
+        usage += &format!(
+            "{}: {}\n    {}\n",
+            decoder.protocol(),
+            decoder.description(),
+            arguments.join(" ")
+        );
+    }
+    usage.trim_end().to_string()
+}
+
+/// Decodes the signals bound in the arguments, `role=signal name` pairs and parameters. With a
+/// `prefix` argument, a role left unbound is bound to the signal named the prefix followed by the
+/// role name, if there is one.
+pub fn decode(
+    protocol: &str,
+    arguments: &BTreeMap<String, String>,
+    simulation_spec: &SimulationSpec,
+) -> Result<Decoded, String> {
+    let decoder = find(protocol).ok_or(format!("unknown protocol \"{protocol}\""))?;
+    let roles = decoder.signal_roles();
+    for name in arguments.keys() {
+        let is_known = name == "prefix"
+            || roles.iter().any(|role| role.name == name)
+            || decoder.parameters().contains(&&name[..]);
+        if !is_known {
+            return Err(format!("{protocol} has no signal or parameter \"{name}\""));
+        }
+    }
+
+    let prefix = arguments.get("prefix");
+    let mut waves = HashMap::new();
+    let mut signal_names = vec![];
+    for role in roles {
+        let signal_name = match (arguments.get(role.name), prefix) {
+            (Some(signal_name), _) => Some(signal_name.clone()),
+            (None, Some(prefix)) => Some(format!("{prefix}{}", role.name))
+                .filter(|signal_name| simulation_spec.get_wave_spec(signal_name).is_some()),
+            (None, None) => None,
+        };
+        let Some(signal_name) = signal_name else {
+            if !role.is_optional {
+                return Err(format!(
+                    "{protocol} needs a {}=<signal> argument",
+                    role.name
+                ));
+            }
+            continue;
+        };
+        let wave_spec = simulation_spec
+            .get_wave_spec(&signal_name)
+            .ok_or(format!("unknown signal \"{signal_name}\""))?;
+        waves.insert(role.name, &wave_spec.wave);
+        signal_names.push(signal_name);
+    }
+
+    let parameters = arguments
+        .iter()
+        .filter(|(name, _)| decoder.parameters().contains(&&name[..]))
+        .map(|(name, value)| (&name[..], &value[..]))
+        .collect();
+    let input = DecoderInput {
+        waves,
+        parameters,
+        time_step_ps: simulation_spec.time_step_ps(),
+    };
+    let mut transactions = decoder.decode(&input)?;
+    transactions.sort_by_key(|t| t.start_time);
+    Ok(Decoded {
+        transactions,
+        signal_names,
+    })
+}
+
+impl SignalRole {
+    pub const fn required(name: &'static str) -> Self {
+        Self {
+            name,
+            is_optional: false,
+        }
+    }
+
+    pub const fn optional(name: &'static str) -> Self {
+        Self {
+            name,
+            is_optional: true,
+        }
+    }
+}
+
+impl Transaction {
+    pub fn new(start_time: usize, end_time: usize, label: String) -> Self {
+        Self {
+            start_time,
+            end_time,
+            label,
+            is_error: false,
+        }
+    }
+
+    pub fn with_error(self, is_error: bool) -> Self {
+        Self { is_error, ..self }
+    }
+}
+
+impl<'a> DecoderInput<'a> {
+    pub fn wave(&self, role: &str) -> Result<&'a Wave, String> {
+        self.optional_wave(role)
+            .ok_or(format!("needs a {role}=<signal> argument"))
+    }
+
+    pub fn optional_wave(&self, role: &str) -> Option<&'a Wave> {
+        self.waves.get(role).copied()
+    }
+
+    /// Parses a parameter, which is required unless it has a default.
+    pub fn parameter<T: FromStr>(&self, name: &str, default: Option<T>) -> Result<T, String> {
+        match (self.parameters.get(name), default) {
+            (Some(value), _) => value
+                .parse()
+                .map_err(|_| format!("invalid {name} \"{value}\"")),
+            (None, Some(default)) => Ok(default),
+            (None, None) => Err(format!("needs a {name}=<value> argument")),
+        }
+    }
+
+    pub fn time_step_ps(&self) -> usize {
+        self.time_step_ps
+    }
+}
+
+/// Returns the start of each clock cycle, from the previous rising edge of the clock, along with
+/// the rising edge that ends it.
+pub fn cycles(clock: &Wave) -> Vec<(usize, usize)> {
+    let mut cycles = vec![];
+    let mut cycle_start = 0;
+    while let Some(edge) = clock.next_edge(cycle_start, Edge::Rising) {
+        cycles.push((cycle_start, edge));
+        cycle_start = edge;
+    }
+    cycles
+}
+
+/// Returns the value of the wave sampled by a clock edge, i.e. just before it.
+pub fn sample(wave: &Wave, edge: usize) -> Option<&BitVec<u32>> {
+    wave.at(edge.saturating_sub(1))
+}
+
+pub fn is_high(wave: &Wave, edge: usize) -> bool {
+    sample(wave, edge).is_some_and(|value| value.any())
+}
+
+/// Whether an optional signal is high, or its default if not bound.
+pub fn is_high_or(wave: Option<&Wave>, edge: usize, default: bool) -> bool {
+    wave.map_or(default, |wave| is_high(wave, edge))
+}
+
+pub fn to_usize(value: Option<&BitVec<u32>>) -> usize {
+    value.map_or(0, |value| {
+        value
+            .iter()
+            .by_vals()
+            .take(usize::BITS as usize)
+            .rev()
+            .fold(0, |acc, bit| (acc << 1) | usize::from(bit))
+    })
+}
+
+pub fn hex(value: Option<&BitVec<u32>>) -> String {
+    match value {
+        Some(value) => {
+            let option = bitvec_str::Option {
+                radix: bitvec_str::Radix::Hexadecimal,
+                width: value.len(),
+                twos_complement: false,
+                ..Default::default()
+            };
+            format!("0x{}", bitvec_str::from(value, &option))
+        }
+        None => "?".to_string(),
+    }
+}
+
+/// Labels a read or write, e.g. `W 0x10 = 0x5`.
+pub fn access_label(
+    is_write: bool,
+    address: Option<&BitVec<u32>>,
+    data: Option<&BitVec<u32>>,
+) -> String {
+    let direction = if is_write { "W" } else { "R" };
+    match data {
+        Some(data) => format!("{direction} {} = {}", hex(address), hex(Some(data))),
+        None => format!("{direction} {}", hex(address)),
+    }
+}
+
+/// Tracks a valid/ready handshake across clock cycles.
+#[derive(Default)]
+pub struct Handshake {
+    valid_since: Option<usize>,
+}
+
+impl Handshake {
+    /// Returns the start of the cycle in which valid was raised, when a transfer completes in the
+    /// cycle.
+    pub fn transfer(
+        &mut self,
+        cycle_start: usize,
+        is_valid: bool,
+        is_ready: bool,
+    ) -> Option<usize> {
+        if !is_valid {
+            self.valid_since = None;
+            return None;
+        }
+        let valid_since = *self.valid_since.get_or_insert(cycle_start);
+        if is_ready {
+            self.valid_since = None;
+            Some(valid_since)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use std::collections::BTreeMap;
+
+    use bitvec::vec::BitVec;
+    use oombak_sim::response::{CompactWaveValue, Wave};
+
+    use crate::components::models::{SimulationSpec, WaveSpec};
+
+    use super::{decode, Transaction};
+
+    /// A clock of period 10, with rising edges at 5, 15, 25, ...
+    pub fn clock(num_of_cycles: usize) -> Wave {
+        wave("clk", &[0, 1].repeat(num_of_cycles), 1, 5)
+    }
+
+    /// A wave holding each value for `duration`.
+    pub fn wave(name: &str, values: &[usize], width: usize, duration: usize) -> Wave {
+        let mut wave = Wave::new(name.to_string(), width);
+        for value in values {
+            let value: BitVec<u32> = (0..width).map(|i| (value >> i) & 1 == 1).collect();
+            wave.append(CompactWaveValue::new(value, duration));
+        }
+        wave
+    }
+
+    pub fn spec(waves: Vec<Wave>) -> SimulationSpec {
+        SimulationSpec {
+            wave_specs: waves.into_iter().map(WaveSpec::new).collect(),
+            time_step_ps: 1,
+            ..Default::default()
+        }
+    }
+
+    pub fn arguments(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    pub fn labels(transactions: &[Transaction]) -> Vec<&str> {
+        transactions.iter().map(|t| &t.label[..]).collect()
+    }
+
+    #[test]
+    fn test_decode_stream() {
+        // valid is raised in the second cycle, ready in the third
+        let spec = spec(vec![
+            clock(5),
+            wave("s_valid", &[0, 1, 1, 1, 0], 1, 10),
+            wave("s_ready", &[0, 0, 1, 1, 1], 1, 10),
+            wave("s_data", &[0, 0xa, 0xa, 0xb, 0], 8, 10),
+        ]);
+        let decoded = decode(
+            "stream",
+            &arguments(&[("clk", "clk"), ("prefix", "s_")]),
+            &spec,
+        )
+        .unwrap();
+        assert_eq!(
+            decoded.signal_names,
+            ["clk", "s_valid", "s_ready", "s_data"]
+        );
+        assert_eq!(labels(&decoded.transactions), ["0x0A", "0x0B"]);
+        assert_eq!(decoded.transactions[0].start_time, 5);
+        assert_eq!(decoded.transactions[0].end_time, 24);
+
+        assert!(decode("stream", &arguments(&[("clk", "clk")]), &spec).is_err());
+        let arguments = arguments(&[("clk", "clk"), ("valid", "s_valid"), ("baud", "9600")]);
+        assert!(decode("stream", &arguments, &spec).is_err());
+        assert!(decode("pcie", &BTreeMap::new(), &spec).is_err());
+    }
+}
@@ -0,0 +1,86 @@
+use bitvec::vec::BitVec;
+
+use super::{
+    access_label, cycles, is_high, is_high_or, sample, to_usize, DecoderInput, SignalRole,
+    Transaction, WaveDecoder,
+};
+
+/// AMBA AHB-Lite, with a transfer from its address phase to the end of its data phase.
+pub struct Ahb;
+
+const SIGNAL_ROLES: [SignalRole; 8] = [
+    SignalRole::required("hclk"),
+    SignalRole::required("htrans"),
+    SignalRole::required("haddr"),
+    SignalRole::required("hwrite"),
+    SignalRole::optional("hwdata"),
+    SignalRole::optional("hrdata"),
+    SignalRole::optional("hready"),
+    SignalRole::optional("hresp"),
+];
+
+/// HTRANS of a transfer, NONSEQ or SEQ, rather than IDLE or BUSY
+const MIN_ACTIVE_HTRANS: usize = 2;
+
+struct DataPhase {
+    start: usize,
+    address: Option<BitVec<u32>>,
+    is_write: bool,
+    is_error: bool,
+}
+
+impl WaveDecoder for Ahb {
+    fn protocol(&self) -> &'static str {
+        "ahb"
+    }
+
+    fn description(&self) -> &'static str {
+        "AMBA AHB-Lite reads and writes"
+    }
+
+    fn signal_roles(&self) -> &'static [SignalRole] {
+        &SIGNAL_ROLES
+    }
+
+    fn decode(&self, input: &DecoderInput) -> Result<Vec<Transaction>, String> {
+        let hclk = input.wave("hclk")?;
+        let htrans = input.wave("htrans")?;
+        let haddr = input.wave("haddr")?;
+        let hwrite = input.wave("hwrite")?;
+        let hwdata = input.optional_wave("hwdata");
+        let hrdata = input.optional_wave("hrdata");
+        let hready = input.optional_wave("hready");
+        let hresp = input.optional_wave("hresp");
+
+        let mut transactions = vec![];
+        let mut data_phase: Option<DataPhase> = None;
+        for (cycle_start, edge) in cycles(hclk) {
+            if let Some(data_phase) = data_phase.as_mut() {
+                data_phase.is_error |= is_high_or(hresp, edge, false);
+            }
+            if !is_high_or(hready, edge, true) {
+                continue;
+            }
+            if let Some(data_phase) = data_phase.take() {
+                let data = if data_phase.is_write { hwdata } else { hrdata };
+                let data = data.and_then(|data| sample(data, edge));
+                let mut label =
+                    access_label(data_phase.is_write, data_phase.address.as_ref(), data);
+                if data_phase.is_error {
+                    label += " ERROR";
+                }
+                let transaction = Transaction::new(data_phase.start, edge - 1, label);
+                transactions.push(transaction.with_error(data_phase.is_error));
+            }
+            if to_usize(sample(htrans, edge)) >= MIN_ACTIVE_HTRANS {
+                data_phase = Some(DataPhase {
+                    start: cycle_start,
+                    address: sample(haddr, edge).cloned(),
+                    is_write: is_high(hwrite, edge),
+                    is_error: false,
+                });
+            }
+        }
+        Ok(transactions)
+    }
+}
@@ -0,0 +1,100 @@
+use super::{
+    access_label, cycles, is_high, is_high_or, sample, DecoderInput, SignalRole, Transaction,
+    WaveDecoder,
+};
+
+/// AMBA APB, with a transfer from its setup phase to the end of its access phase.
+pub struct Apb;
+
+const SIGNAL_ROLES: [SignalRole; 9] = [
+    SignalRole::required("pclk"),
+    SignalRole::required("psel"),
+    SignalRole::required("penable"),
+    SignalRole::required("paddr"),
+    SignalRole::required("pwrite"),
+    SignalRole::optional("pwdata"),
+    SignalRole::optional("prdata"),
+    SignalRole::optional("pready"),
+    SignalRole::optional("pslverr"),
+];
+
+impl WaveDecoder for Apb {
+    fn protocol(&self) -> &'static str {
+        "apb"
+    }
+
+    fn description(&self) -> &'static str {
+        "AMBA APB reads and writes"
+    }
+
+    fn signal_roles(&self) -> &'static [SignalRole] {
+        &SIGNAL_ROLES
+    }
+
+    fn decode(&self, input: &DecoderInput) -> Result<Vec<Transaction>, String> {
+        let pclk = input.wave("pclk")?;
+        let psel = input.wave("psel")?;
+        let penable = input.wave("penable")?;
+        let paddr = input.wave("paddr")?;
+        let pwrite = input.wave("pwrite")?;
+        let pwdata = input.optional_wave("pwdata");
+        let prdata = input.optional_wave("prdata");
+        let pready = input.optional_wave("pready");
+        let pslverr = input.optional_wave("pslverr");
+
+        let mut transactions = vec![];
+        let mut setup_start = None;
+        for (cycle_start, edge) in cycles(pclk) {
+            if !is_high(psel, edge) {
+                setup_start = None;
+                continue;
+            }
+            let start = *setup_start.get_or_insert(cycle_start);
+            if !is_high(penable, edge) || !is_high_or(pready, edge, true) {
+                continue;
+            }
+            setup_start = None;
+            let is_write = is_high(pwrite, edge);
+            let data = if is_write { pwdata } else { prdata };
+            let data = data.and_then(|data| sample(data, edge));
+            let mut label = access_label(is_write, sample(paddr, edge), data);
+            let is_error = is_high_or(pslverr, edge, false);
+            if is_error {
+                label += " SLVERR";
+            }
+            transactions.push(Transaction::new(start, edge - 1, label).with_error(is_error));
+        }
+        Ok(transactions)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::backend::decoder::{
+        decode,
+        test::{arguments, clock, labels, spec, wave},
+    };
+
+    #[test]
+    fn test_decode() {
+        // A write with no wait state, then a read with one
+        let spec = spec(vec![
+            clock(6),
+            wave("psel", &[0, 1, 1, 1, 1, 1], 1, 10),
+            wave("penable", &[0, 0, 1, 0, 1, 1], 1, 10),
+            wave("pready", &[0, 0, 1, 0, 0, 1], 1, 10),
+            wave("pwrite", &[0, 1, 1, 0, 0, 0], 1, 10),
+            wave("paddr", &[0, 4, 4, 8, 8, 8], 8, 10),
+            wave("pwdata", &[0, 5, 5, 0, 0, 0], 8, 10),
+            wave("prdata", &[0, 0, 0, 0, 0, 7], 8, 10),
+        ]);
+        let arguments = arguments(&[("prefix", ""), ("pclk", "clk")]);
+        let decoded = decode("apb", &arguments, &spec).unwrap();
+        assert_eq!(
+            labels(&decoded.transactions),
+            ["W 0x04 = 0x05", "R 0x08 = 0x07"]
+        );
+        assert_eq!(decoded.transactions[1].start_time, 25);
+        assert_eq!(decoded.transactions[1].end_time, 54);
+    }
+}
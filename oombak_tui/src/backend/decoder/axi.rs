@@ -0,0 +1,337 @@
+use std::collections::VecDeque;
+
+use bitvec::vec::BitVec;
+use oombak_sim::response::Wave;
+
+use super::{
+    access_label, cycles, hex, is_high, sample, to_usize, DecoderInput, Handshake, SignalRole,
+    Transaction, WaveDecoder,
+};
+
+/// AMBA AXI4 or AXI4-Lite, with a write from its address to its response, and a read from its
+/// address to its last data beat. Responses are matched to addresses in order, regardless of IDs.
+pub struct Axi {
+    pub is_lite: bool,
+}
+
+const SIGNAL_ROLES: [SignalRole; 21] = [
+    SignalRole::required("aclk"),
+    SignalRole::optional("awvalid"),
+    SignalRole::optional("awready"),
+    SignalRole::optional("awaddr"),
+    SignalRole::optional("awlen"),
+    SignalRole::optional("wvalid"),
+    SignalRole::optional("wready"),
+    SignalRole::optional("wdata"),
+    SignalRole::optional("wlast"),
+    SignalRole::optional("bvalid"),
+    SignalRole::optional("bready"),
+    SignalRole::optional("bresp"),
+    SignalRole::optional("arvalid"),
+    SignalRole::optional("arready"),
+    SignalRole::optional("araddr"),
+    SignalRole::optional("arlen"),
+    SignalRole::optional("rvalid"),
+    SignalRole::optional("rready"),
+    SignalRole::optional("rdata"),
+    SignalRole::optional("rresp"),
+    SignalRole::optional("rlast"),
+];
+
+/// Signals of AXI4 that AXI4-Lite lacks
+const BURST_SIGNAL_ROLES: [&str; 4] = ["awlen", "wlast", "arlen", "rlast"];
+
+struct Address {
+    start: usize,
+    address: Option<BitVec<u32>>,
+    num_of_beats: usize,
+}
+
+struct Burst {
+    start: usize,
+    beats: Vec<Option<BitVec<u32>>>,
+    /// Worst response of the beats
+    response: usize,
+}
+
+impl WaveDecoder for Axi {
+    fn protocol(&self) -> &'static str {
+        if self.is_lite {
+            "axi-lite"
+        } else {
+            "axi4"
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        if self.is_lite {
+            "AMBA AXI4-Lite writes and reads, on either or both of the write and read channels"
+        } else {
+            "AMBA AXI4 write and read bursts, on either or both of the write and read channels"
+        }
+    }
+
+    fn signal_roles(&self) -> &'static [SignalRole] {
+        &SIGNAL_ROLES
+    }
+
+    fn decode(&self, input: &DecoderInput) -> Result<Vec<Transaction>, String> {
+        let aclk = input.wave("aclk")?;
+        let burst_signal = |role: &str| match self.is_lite {
+            true if BURST_SIGNAL_ROLES.contains(&role) => None,
+            _ => input.optional_wave(role),
+        };
+        let has_writes = input.optional_wave("awvalid").is_some();
+        let has_reads = input.optional_wave("arvalid").is_some();
+        if !has_writes && !has_reads {
+            return Err("needs the signals of the write or read channels".to_string());
+        }
+
+        let mut transactions = vec![];
+        if has_writes {
+            let write_channels = WriteChannels {
+                awvalid: input.wave("awvalid")?,
+                awready: input.wave("awready")?,
+                awaddr: input.wave("awaddr")?,
+                awlen: burst_signal("awlen"),
+                wvalid: input.wave("wvalid")?,
+                wready: input.wave("wready")?,
+                wdata: input.optional_wave("wdata"),
+                wlast: burst_signal("wlast"),
+                bvalid: input.wave("bvalid")?,
+                bready: input.wave("bready")?,
+                bresp: input.optional_wave("bresp"),
+            };
+            write_channels.decode(aclk, &mut transactions);
+        }
+        if has_reads {
+            let read_channels = ReadChannels {
+                arvalid: input.wave("arvalid")?,
+                arready: input.wave("arready")?,
+                araddr: input.wave("araddr")?,
+                arlen: burst_signal("arlen"),
+                rvalid: input.wave("rvalid")?,
+                rready: input.wave("rready")?,
+                rdata: input.optional_wave("rdata"),
+                rresp: input.optional_wave("rresp"),
+                rlast: burst_signal("rlast"),
+            };
+            read_channels.decode(aclk, &mut transactions);
+        }
+        Ok(transactions)
+    }
+}
+
+struct WriteChannels<'a> {
+    awvalid: &'a Wave,
+    awready: &'a Wave,
+    awaddr: &'a Wave,
+    awlen: Option<&'a Wave>,
+    wvalid: &'a Wave,
+    wready: &'a Wave,
+    wdata: Option<&'a Wave>,
+    wlast: Option<&'a Wave>,
+    bvalid: &'a Wave,
+    bready: &'a Wave,
+    bresp: Option<&'a Wave>,
+}
+
+impl WriteChannels<'_> {
+    fn decode(&self, aclk: &Wave, transactions: &mut Vec<Transaction>) {
+        let mut addresses = VecDeque::new();
+        let mut bursts = VecDeque::new();
+        let mut burst: Option<Burst> = None;
+        let (mut aw, mut w, mut b): (Handshake, Handshake, Handshake) = Default::default();
+        for (cycle_start, edge) in cycles(aclk) {
+            let is_transfer = |handshake: &mut Handshake, valid, ready| {
+                handshake.transfer(cycle_start, is_high(valid, edge), is_high(ready, edge))
+            };
+            if let Some(start) = is_transfer(&mut aw, self.awvalid, self.awready) {
+                addresses.push_back(Address {
+                    start,
+                    address: sample(self.awaddr, edge).cloned(),
+                    num_of_beats: self.awlen.map_or(0, |awlen| to_usize(sample(awlen, edge))) + 1,
+                });
+            }
+            if let Some(start) = is_transfer(&mut w, self.wvalid, self.wready) {
+                let current = burst.get_or_insert(Burst::new(start));
+                current
+                    .beats
+                    .push(self.wdata.and_then(|wdata| sample(wdata, edge).cloned()));
+                // Without wlast, the burst length comes from its address
+                let is_last = match self.wlast {
+                    Some(wlast) => is_high(wlast, edge),
+                    None => {
+                        let address: Option<&Address> = addresses.get(bursts.len());
+                        current.beats.len() >= address.map_or(1, |a| a.num_of_beats)
+                    }
+                };
+                if is_last {
+                    bursts.extend(burst.take());
+                }
+            }
+            if is_transfer(&mut b, self.bvalid, self.bready).is_some() {
+                let (Some(address), Some(mut burst)) = (addresses.pop_front(), bursts.pop_front())
+                else {
+                    continue;
+                };
+                burst.response = self.bresp.map_or(0, |bresp| to_usize(sample(bresp, edge)));
+                let start = address.start.min(burst.start);
+                transactions.push(burst.transaction(true, address, start, edge - 1));
+            }
+        }
+    }
+}
+
+struct ReadChannels<'a> {
+    arvalid: &'a Wave,
+    arready: &'a Wave,
+    araddr: &'a Wave,
+    arlen: Option<&'a Wave>,
+    rvalid: &'a Wave,
+    rready: &'a Wave,
+    rdata: Option<&'a Wave>,
+    rresp: Option<&'a Wave>,
+    rlast: Option<&'a Wave>,
+}
+
+impl ReadChannels<'_> {
+    fn decode(&self, aclk: &Wave, transactions: &mut Vec<Transaction>) {
+        let mut addresses = VecDeque::new();
+        let mut burst: Option<Burst> = None;
+        let (mut ar, mut r): (Handshake, Handshake) = Default::default();
+        for (cycle_start, edge) in cycles(aclk) {
+            let is_address_transfer = ar.transfer(
+                cycle_start,
+                is_high(self.arvalid, edge),
+                is_high(self.arready, edge),
+            );
+            if let Some(start) = is_address_transfer {
+                addresses.push_back(Address {
+                    start,
+                    address: sample(self.araddr, edge).cloned(),
+                    num_of_beats: self.arlen.map_or(0, |arlen| to_usize(sample(arlen, edge))) + 1,
+                });
+            }
+            let is_data_transfer = r.transfer(
+                cycle_start,
+                is_high(self.rvalid, edge),
+                is_high(self.rready, edge),
+            );
+            if let Some(start) = is_data_transfer {
+                let current = burst.get_or_insert(Burst::new(start));
+                current
+                    .beats
+                    .push(self.rdata.and_then(|rdata| sample(rdata, edge).cloned()));
+                let response = self.rresp.map_or(0, |rresp| to_usize(sample(rresp, edge)));
+                current.response = current.response.max(response);
+                let is_last = match self.rlast {
+                    Some(rlast) => is_high(rlast, edge),
+                    None => current.beats.len() >= addresses.front().map_or(1, |a| a.num_of_beats),
+                };
+                if !is_last {
+                    continue;
+                }
+                let burst = burst.take().unwrap();
+                if let Some(address) = addresses.pop_front() {
+                    let start = address.start;
+                    transactions.push(burst.transaction(false, address, start, edge - 1));
+                }
+            }
+        }
+    }
+}
+
+impl Burst {
+    fn new(start: usize) -> Self {
+        Self {
+            start,
+            beats: vec![],
+            response: 0,
+        }
+    }
+
+    fn transaction(
+        self,
+        is_write: bool,
+        address: Address,
+        start: usize,
+        end: usize,
+    ) -> Transaction {
+        let mut label = match &self.beats[..] {
+            [data] => access_label(is_write, address.address.as_ref(), data.as_ref()),
+            beats => {
+                let direction = if is_write { "W" } else { "R" };
+                let address = hex(address.address.as_ref());
+                format!("{direction} {address} [{} beats]", beats.len())
+            }
+        };
+        // SLVERR and DECERR, rather than OKAY or EXOKAY
+        let is_error = self.response >= 2;
+        match self.response {
+            1 => label += " EXOKAY",
+            2 => label += " SLVERR",
+            3 => label += " DECERR",
+            _ => (),
+        }
+        Transaction::new(start, end, label).with_error(is_error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::backend::decoder::{
+        decode,
+        test::{arguments, clock, labels, spec, wave},
+    };
+
+    #[test]
+    fn test_decode() {
+        // A single-beat write, overlapping a two-beat read that ends with an error
+        let spec = spec(vec![
+            clock(6),
+            wave("awvalid", &[0, 1, 0, 0, 0, 0], 1, 10),
+            wave("awready", &[0, 1, 1, 1, 1, 1], 1, 10),
+            wave("awaddr", &[0, 4, 0, 0, 0, 0], 8, 10),
+            wave("wvalid", &[0, 0, 1, 0, 0, 0], 1, 10),
+            wave("wready", &[1, 1, 1, 1, 1, 1], 1, 10),
+            wave("wdata", &[0, 0, 9, 0, 0, 0], 8, 10),
+            wave("bvalid", &[0, 0, 0, 1, 0, 0], 1, 10),
+            wave("bready", &[1, 1, 1, 1, 1, 1], 1, 10),
+            wave("arvalid", &[0, 1, 0, 0, 0, 0], 1, 10),
+            wave("arready", &[0, 1, 0, 0, 0, 0], 1, 10),
+            wave("araddr", &[0, 8, 0, 0, 0, 0], 8, 10),
+            wave("arlen", &[0, 1, 0, 0, 0, 0], 8, 10),
+            wave("rvalid", &[0, 0, 0, 1, 1, 0], 1, 10),
+            wave("rready", &[1, 1, 1, 1, 1, 1], 1, 10),
+            wave("rresp", &[0, 0, 0, 0, 2, 0], 2, 10),
+        ]);
+        let decoded = decode(
+            "axi4",
+            &arguments(&[("prefix", ""), ("aclk", "clk")]),
+            &spec,
+        );
+        let transactions = decoded.unwrap().transactions;
+        assert_eq!(
+            labels(&transactions),
+            ["W 0x04 = 0x09", "R 0x08 [2 beats] SLVERR"]
+        );
+        assert_eq!(
+            (transactions[0].start_time, transactions[0].end_time),
+            (5, 34)
+        );
+        assert!(transactions[1].is_error);
+
+        // The burst length is ignored with AXI4-Lite, leaving the second beat without an address
+        let decoded = decode(
+            "axi-lite",
+            &arguments(&[("prefix", ""), ("aclk", "clk")]),
+            &spec,
+        );
+        assert_eq!(
+            labels(&decoded.unwrap().transactions),
+            ["W 0x04 = 0x09", "R 0x08"]
+        );
+        assert!(decode("axi4", &arguments(&[("aclk", "clk")]), &spec).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+use oombak_sim::{response::Wave, Edge};
+
+use super::{DecoderInput, SignalRole, Transaction, WaveDecoder};
+
+/// I2C bytes between START and STOP conditions, the first after a START being the address.
+pub struct I2c;
+
+const SIGNAL_ROLES: [SignalRole; 2] = [SignalRole::required("scl"), SignalRole::required("sda")];
+
+enum Event {
+    /// sda falls while scl is high
+    Start,
+    /// sda rises while scl is high
+    Stop,
+    /// scl rises, sampling sda
+    Bit(bool),
+}
+
+struct Byte {
+    start: usize,
+    bits: Vec<bool>,
+}
+
+impl WaveDecoder for I2c {
+    fn protocol(&self) -> &'static str {
+        "i2c"
+    }
+
+    fn description(&self) -> &'static str {
+        "I2C addresses and data bytes, each followed by its ACK or NAK"
+    }
+
+    fn signal_roles(&self) -> &'static [SignalRole] {
+        &SIGNAL_ROLES
+    }
+
+    fn decode(&self, input: &DecoderInput) -> Result<Vec<Transaction>, String> {
+        let scl = input.wave("scl")?;
+        let sda = input.wave("sda")?;
+
+        let mut transactions = vec![];
+        // None outside of START and STOP conditions
+        let mut byte: Option<Byte> = None;
+        let mut is_address = false;
+        for (time, event) in events(scl, sda) {
+            match event {
+                Event::Start => {
+                    byte = Some(Byte::new(time));
+                    is_address = true;
+                }
+                Event::Stop => byte = None,
+                Event::Bit(bit) => {
+                    let Some(current) = &mut byte else {
+                        continue;
+                    };
+                    current.bits.push(bit);
+                    if current.bits.len() < 9 {
+                        continue;
+                    }
+                    let end = scl.next_edge(time, Edge::Falling).unwrap_or(time);
+                    transactions.push(current.transaction(is_address, end));
+                    byte = Some(Byte::new(end));
+                    is_address = false;
+                }
+            }
+        }
+        Ok(transactions)
+    }
+}
+
+impl Byte {
+    fn new(start: usize) -> Self {
+        Self {
+            start,
+            bits: vec![],
+        }
+    }
+
+    /// The byte is sent MSB first, then the ACK bit, low for an ACK.
+    fn transaction(&self, is_address: bool, end: usize) -> Transaction {
+        let value = self.bits[..8]
+            .iter()
+            .fold(0, |acc, bit| (acc << 1) | u8::from(*bit));
+        let is_nak = self.bits[8];
+        let mut label = if is_address {
+            let direction = if value & 1 == 1 { "R" } else { "W" };
+            format!("addr 0x{:02X} {direction}", value >> 1)
+        } else {
+            format!("0x{value:02X}")
+        };
+        if is_nak {
+            label += " NAK";
+        }
+        Transaction::new(self.start, end.saturating_sub(1).max(self.start), label)
+    }
+}
+
+/// Returns the START, STOP and bit events in the order they occur.
+fn events(scl: &Wave, sda: &Wave) -> Vec<(usize, Event)> {
+    let mut events = vec![];
+    let mut time = 0;
+    while let Some(change) = sda.next_change(time) {
+        time = change;
+        if is_high_at(scl, change) && is_high_at(scl, change - 1) {
+            let event = match is_high_at(sda, change) {
+                true => Event::Stop,
+                false => Event::Start,
+            };
+            events.push((change, event));
+        }
+    }
+    time = 0;
+    while let Some(edge) = scl.next_edge(time, Edge::Rising) {
+        time = edge;
+        events.push((edge, Event::Bit(is_high_at(sda, edge))));
+    }
+    // On a tie, the bit comes first, as sda only changes while scl is low
+    events.sort_by_key(|(time, event)| (*time, !matches!(event, Event::Bit(_))));
+    events
+}
+
+fn is_high_at(wave: &Wave, time: usize) -> bool {
+    wave.at(time).is_some_and(|value| value.any())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::backend::decoder::{
+        decode,
+        test::{arguments, labels, spec, wave},
+    };
+
+    /// The scl and sda levels of a transfer, a START, the bits, then a STOP, with a bit period of
+    /// 4 time steps.
+    fn transfer(bits: &[usize]) -> (Vec<usize>, Vec<usize>) {
+        let (mut scl, mut sda) = (vec![1, 1, 1], vec![1, 1, 0]);
+        for bit in bits {
+            scl.extend([0, 0, 1, 1]);
+            sda.extend([*bit; 4]);
+        }
+        scl.extend([0, 1, 1]);
+        sda.extend([0, 0, 1]);
+        (scl, sda)
+    }
+
+    #[test]
+    fn test_decode() {
+        // A write to 0x50, ACKed, then 0xA5, NAKed
+        let bits = [1, 0, 1, 0, 0, 0, 0, 0, 0, 1, 0, 1, 0, 0, 1, 0, 1, 1];
+        let (scl, sda) = transfer(&bits);
+        let spec = spec(vec![wave("scl", &scl, 1, 1), wave("sda", &sda, 1, 1)]);
+        let arguments = arguments(&[("scl", "scl"), ("sda", "sda")]);
+        let transactions = decode("i2c", &arguments, &spec).unwrap().transactions;
+        assert_eq!(labels(&transactions), ["addr 0x50 W", "0xA5 NAK"]);
+        assert_eq!(
+            (transactions[0].start_time, transactions[0].end_time),
+            (2, 38)
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use bitvec::vec::BitVec;
+use oombak_sim::{response::Wave, Edge};
+
+use super::{hex, is_high, DecoderInput, SignalRole, Transaction, WaveDecoder};
+
+/// SPI words, MSB first, shifted while the active low chip select is asserted.
+pub struct Spi;
+
+const SIGNAL_ROLES: [SignalRole; 4] = [
+    SignalRole::required("sclk"),
+    SignalRole::required("cs"),
+    SignalRole::optional("mosi"),
+    SignalRole::optional("miso"),
+];
+const PARAMETERS: [&str; 2] = ["mode", "bits"];
+
+#[derive(Default)]
+struct Word {
+    start: usize,
+    mosi: BitVec<u32>,
+    miso: BitVec<u32>,
+}
+
+impl WaveDecoder for Spi {
+    fn protocol(&self) -> &'static str {
+        "spi"
+    }
+
+    fn description(&self) -> &'static str {
+        "SPI words on mosi and/or miso, with the mode (0 by default) and 8 bits by default"
+    }
+
+    fn signal_roles(&self) -> &'static [SignalRole] {
+        &SIGNAL_ROLES
+    }
+
+    fn parameters(&self) -> &'static [&'static str] {
+        &PARAMETERS
+    }
+
+    fn decode(&self, input: &DecoderInput) -> Result<Vec<Transaction>, String> {
+        let sclk = input.wave("sclk")?;
+        let cs = input.wave("cs")?;
+        let mosi = input.optional_wave("mosi");
+        let miso = input.optional_wave("miso");
+        if mosi.is_none() && miso.is_none() {
+            return Err("needs a mosi=<signal> or miso=<signal> argument".to_string());
+        }
+        let mode: usize = input.parameter("mode", Some(0))?;
+        let num_of_bits: usize = input.parameter("bits", Some(8))?;
+        if mode > 3 {
+            return Err(format!("invalid mode \"{mode}\""));
+        }
+        if num_of_bits == 0 {
+            return Err("invalid bits \"0\"".to_string());
+        }
+        // Modes 0 and 3 sample on the rising edge, modes 1 and 2 on the falling edge
+        let sample_edge = match mode {
+            0 | 3 => Edge::Rising,
+            _ => Edge::Falling,
+        };
+
+        let mut transactions = vec![];
+        for (frame_start, frame_end) in frames(cs) {
+            let mut word: Option<Word> = None;
+            // The first word starts with the frame, any later one just after the previous one
+            let mut time = frame_start;
+            while let Some(edge) = sclk.next_edge(time, sample_edge).filter(|e| *e < frame_end) {
+                let current = word.get_or_insert_with(|| Word {
+                    start: if time == frame_start { time } else { time + 1 },
+                    ..Default::default()
+                });
+                time = edge;
+                let bit_of = |wave: Option<&Wave>| wave.is_some_and(|wave| is_high(wave, edge));
+                // Shifted MSB first, so each bit goes below the previous ones
+                current.mosi.insert(0, bit_of(mosi));
+                current.miso.insert(0, bit_of(miso));
+                if current.mosi.len() == num_of_bits {
+                    let word = word.take().unwrap();
+                    transactions.push(Transaction::new(word.start, edge, word.label(mosi, miso)));
+                }
+            }
+            if let Some(word) = word {
+                let label = format!("{} [{} bits]", word.label(mosi, miso), word.mosi.len());
+                let transaction = Transaction::new(word.start, frame_end - 1, label);
+                transactions.push(transaction.with_error(true));
+            }
+        }
+        Ok(transactions)
+    }
+}
+
+impl Word {
+    fn label(&self, mosi: Option<&Wave>, miso: Option<&Wave>) -> String {
+        let mut labels = vec![];
+        if mosi.is_some() {
+            labels.push(format!("mosi {}", hex(Some(&self.mosi))));
+        }
+        if miso.is_some() {
+            labels.push(format!("miso {}", hex(Some(&self.miso))));
+        }
+        labels.join(" ")
+    }
+}
+
+/// Returns the start and end of each frame in which the chip select is low.
+fn frames(cs: &Wave) -> Vec<(usize, usize)> {
+    let mut frames = vec![];
+    let mut start = match cs.at(0) {
+        Some(value) if !value.any() => Some(0),
+        _ => cs.next_edge(0, Edge::Falling),
+    };
+    while let Some(frame_start) = start {
+        let Some(frame_end) = cs.next_edge(frame_start, Edge::Rising) else {
+            frames.push((frame_start, cs.end_time() + 1));
+            break;
+        };
+        frames.push((frame_start, frame_end));
+        start = cs.next_edge(frame_end, Edge::Falling);
+    }
+    frames
+}
+
+#[cfg(test)]
+mod test {
+    use crate::backend::decoder::{
+        decode,
+        test::{arguments, clock, labels, spec, wave},
+    };
+
+    #[test]
+    fn test_decode() {
+        // cs is low for one 4-bit word and the first bit of another
+        let spec = spec(vec![
+            clock(6),
+            wave("cs_n", &[0, 0, 0, 0, 0, 1], 1, 10),
+            wave("mosi", &[1, 0, 1, 1, 0, 0], 1, 10),
+        ]);
+        let arguments = arguments(&[
+            ("sclk", "clk"),
+            ("cs", "cs_n"),
+            ("mosi", "mosi"),
+            ("bits", "4"),
+        ]);
+        let transactions = decode("spi", &arguments, &spec).unwrap().transactions;
+        assert_eq!(labels(&transactions), ["mosi 0xB", "mosi 0x0 [1 bits]"]);
+        assert_eq!(
+            (transactions[0].start_time, transactions[0].end_time),
+            (0, 35)
+        );
+        assert!(transactions[1].is_error);
+    }
+}
@@ -0,0 +1,56 @@
+use super::{
+    cycles, hex, is_high, is_high_or, sample, DecoderInput, Handshake, SignalRole, Transaction,
+    WaveDecoder,
+};
+
+/// A valid/ready stream, e.g. AXI4-Stream, with a transfer in each cycle where both are high.
+pub struct Stream;
+
+const SIGNAL_ROLES: [SignalRole; 5] = [
+    SignalRole::required("clk"),
+    SignalRole::required("valid"),
+    SignalRole::optional("ready"),
+    SignalRole::optional("data"),
+    SignalRole::optional("last"),
+];
+
+impl WaveDecoder for Stream {
+    fn protocol(&self) -> &'static str {
+        "stream"
+    }
+
+    fn description(&self) -> &'static str {
+        "valid/ready stream, always ready if ready is not given"
+    }
+
+    fn signal_roles(&self) -> &'static [SignalRole] {
+        &SIGNAL_ROLES
+    }
+
+    fn decode(&self, input: &DecoderInput) -> Result<Vec<Transaction>, String> {
+        let clk = input.wave("clk")?;
+        let valid = input.wave("valid")?;
+        let ready = input.optional_wave("ready");
+        let data = input.optional_wave("data");
+        let last = input.optional_wave("last");
+
+        let mut transactions = vec![];
+        let mut handshake = Handshake::default();
+        for (cycle_start, edge) in cycles(clk) {
+            let is_ready = is_high_or(ready, edge, true);
+            let Some(start) = handshake.transfer(cycle_start, is_high(valid, edge), is_ready)
+            else {
+                continue;
+            };
+            let mut label = match data {
+                Some(data) => hex(sample(data, edge)),
+                None => "transfer".to_string(),
+            };
+            if is_high_or(last, edge, false) {
+                label += " last";
+            }
+            transactions.push(Transaction::new(start, edge - 1, label));
+        }
+        Ok(transactions)
+    }
+}
@@ -0,0 +1,137 @@
+use bitvec::vec::BitVec;
+use oombak_sim::Edge;
+
+use super::{hex, to_usize, DecoderInput, SignalRole, Transaction, WaveDecoder};
+
+/// An asynchronous serial line, idle high, with one start bit, LSB first data and one stop bit.
+pub struct Uart;
+
+const SIGNAL_ROLES: [SignalRole; 1] = [SignalRole::required("line")];
+const PARAMETERS: [&str; 3] = ["baud", "bits", "parity"];
+
+#[derive(Clone, Copy, PartialEq)]
+enum Parity {
+    None,
+    Even,
+    Odd,
+}
+
+impl WaveDecoder for Uart {
+    fn protocol(&self) -> &'static str {
+        "uart"
+    }
+
+    fn description(&self) -> &'static str {
+        "serial line, with the baud rate, 8 data bits by default, and none/even/odd parity"
+    }
+
+    fn signal_roles(&self) -> &'static [SignalRole] {
+        &SIGNAL_ROLES
+    }
+
+    fn parameters(&self) -> &'static [&'static str] {
+        &PARAMETERS
+    }
+
+    fn decode(&self, input: &DecoderInput) -> Result<Vec<Transaction>, String> {
+        let line = input.wave("line")?;
+        let baud: f64 = input.parameter("baud", None)?;
+        let num_of_bits: usize = input.parameter("bits", Some(8))?;
+        let parity = match &input.parameter("parity", Some("none".to_string()))?[..] {
+            "none" => Parity::None,
+            "even" => Parity::Even,
+            "odd" => Parity::Odd,
+            parity => return Err(format!("invalid parity \"{parity}\"")),
+        };
+        if !(1..=32).contains(&num_of_bits) {
+            return Err(format!("invalid bits \"{num_of_bits}\""));
+        }
+        // In time steps, which must be long enough to sample the centre of each bit
+        let bit_period = 1e12 / baud / input.time_step_ps() as f64;
+        if !bit_period.is_finite() || bit_period < 2.0 {
+            return Err("the baud rate is too high for the time step".to_string());
+        }
+
+        let bit_at = |start: usize, idx: usize| {
+            let time = start + ((idx as f64 + 0.5) * bit_period) as usize;
+            line.at(time).is_none_or(|value| value.any())
+        };
+        let num_of_frame_bits = 1 + num_of_bits + usize::from(parity != Parity::None) + 1;
+        let mut transactions = vec![];
+        let mut time = 0;
+        while let Some(start) = line.next_edge(time, Edge::Falling) {
+            let end = start + (num_of_frame_bits as f64 * bit_period) as usize;
+            if end - 1 > line.end_time() {
+                break;
+            }
+            let data: BitVec<u32> = (1..=num_of_bits).map(|idx| bit_at(start, idx)).collect();
+            let is_parity_error = match parity {
+                Parity::None => false,
+                parity => {
+                    let num_of_ones =
+                        data.count_ones() + usize::from(bit_at(start, num_of_bits + 1));
+                    (num_of_ones % 2 == 1) != (parity == Parity::Odd)
+                }
+            };
+            let is_framing_error = !bit_at(start, num_of_frame_bits - 1);
+
+            let mut label = hex(Some(&data));
+            let c = char::from_u32(to_usize(Some(&data)) as u32);
+            if let Some(c) = c.filter(|c| c.is_ascii_graphic() || *c == ' ') {
+                label += &format!(" '{c}'");
+            }
+            if is_parity_error {
+                label += " parity error";
+            }
+            if is_framing_error {
+                label += " framing error";
+            }
+            let is_error = is_parity_error || is_framing_error;
+            transactions.push(Transaction::new(start, end - 1, label).with_error(is_error));
+            // The next start bit follows at least half a stop bit later
+            time = end - (bit_period / 2.0) as usize;
+        }
+        Ok(transactions)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::backend::decoder::{
+        decode,
+        test::{arguments, labels, spec, wave},
+    };
+
+    /// Frames of 8 bits, then the parity bit if any, with a bit period of 10 time steps.
+    fn frames(bytes: &[(usize, bool)]) -> Vec<usize> {
+        let mut bits = vec![1, 1];
+        for (byte, parity) in bytes {
+            bits.push(0);
+            bits.extend((0..8).map(|i| (byte >> i) & 1));
+            bits.push(usize::from(*parity));
+            bits.push(1);
+        }
+        bits
+    }
+
+    #[test]
+    fn test_decode() {
+        // 'A' has two ones, so an odd parity bit is set, then a parity error
+        let line = wave("tx", &frames(&[(0x41, true), (0x42, false)]), 1, 10);
+        let spec = spec(vec![line]);
+        let mut arguments = arguments(&[("line", "tx"), ("baud", "100e9"), ("parity", "odd")]);
+        let decoded = decode("uart", &arguments, &spec).unwrap();
+        assert_eq!(
+            labels(&decoded.transactions),
+            ["0x41 'A'", "0x42 'B' parity error"]
+        );
+        assert_eq!(decoded.transactions[0].start_time, 20);
+        assert_eq!(decoded.transactions[0].end_time, 129);
+        assert!(decoded.transactions[1].is_error);
+
+        arguments.insert("baud".to_string(), "1e12".to_string());
+        assert!(decode("uart", &arguments, &spec).is_err());
+        arguments.remove("baud");
+        assert!(decode("uart", &arguments, &spec).is_err());
+    }
+}
@@ -0,0 +1,65 @@
+use super::{
+    access_label, cycles, is_high, is_high_or, sample, DecoderInput, Handshake, SignalRole,
+    Transaction, WaveDecoder,
+};
+
+/// Wishbone classic cycles, with a transfer from the strobe to its acknowledge.
+pub struct Wishbone;
+
+const SIGNAL_ROLES: [SignalRole; 9] = [
+    SignalRole::required("clk"),
+    SignalRole::required("cyc"),
+    SignalRole::required("stb"),
+    SignalRole::required("we"),
+    SignalRole::required("adr"),
+    SignalRole::optional("dat_w"),
+    SignalRole::optional("dat_r"),
+    SignalRole::required("ack"),
+    SignalRole::optional("err"),
+];
+
+impl WaveDecoder for Wishbone {
+    fn protocol(&self) -> &'static str {
+        "wishbone"
+    }
+
+    fn description(&self) -> &'static str {
+        "Wishbone classic reads and writes, dat_w and dat_r being the data written and read"
+    }
+
+    fn signal_roles(&self) -> &'static [SignalRole] {
+        &SIGNAL_ROLES
+    }
+
+    fn decode(&self, input: &DecoderInput) -> Result<Vec<Transaction>, String> {
+        let clk = input.wave("clk")?;
+        let cyc = input.wave("cyc")?;
+        let stb = input.wave("stb")?;
+        let we = input.wave("we")?;
+        let adr = input.wave("adr")?;
+        let dat_w = input.optional_wave("dat_w");
+        let dat_r = input.optional_wave("dat_r");
+        let ack = input.wave("ack")?;
+        let err = input.optional_wave("err");
+
+        let mut transactions = vec![];
+        let mut handshake = Handshake::default();
+        for (cycle_start, edge) in cycles(clk) {
+            let is_strobed = is_high(cyc, edge) && is_high(stb, edge);
+            let is_error = is_high_or(err, edge, false);
+            let is_acknowledged = is_high(ack, edge) || is_error;
+            let Some(start) = handshake.transfer(cycle_start, is_strobed, is_acknowledged) else {
+                continue;
+            };
+            let is_write = is_high(we, edge);
+            let data = if is_write { dat_w } else { dat_r };
+            let data = data.and_then(|data| sample(data, edge));
+            let mut label = access_label(is_write, sample(adr, edge), data);
+            if is_error {
+                label += " ERR";
+            }
+            transactions.push(Transaction::new(start, edge - 1, label).with_error(is_error));
+        }
+        Ok(transactions)
+    }
+}
@@ -0,0 +1,480 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use bitvec::vec::BitVec;
+use oombak_sim::response::Wave;
+
+use crate::{components::models::SimulationSpec, utils::bitvec_str};
+
+pub enum Expression {
+    Signal(String),
+    Literal(BitVec<u32>),
+    Unary(UnaryOperator, Box<Expression>),
+    Binary(BinaryOperator, Box<Expression>, Box<Expression>),
+    Slice(Box<Expression>, usize, usize),
+    Concatenation(Vec<Expression>),
+}
+
+#[derive(Clone, Copy)]
+pub enum UnaryOperator {
+    LogicalNot,
+    BitwiseNot,
+    Negate,
+    ReduceAnd,
+    ReduceOr,
+    ReduceXor,
+}
+
+#[derive(Clone, Copy)]
+pub enum BinaryOperator {
+    LogicalOr,
+    LogicalAnd,
+    BitwiseOr,
+    BitwiseXor,
+    BitwiseAnd,
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Add,
+    Subtract,
+    Multiply,
+}
+
+#[derive(Clone, PartialEq)]
+enum Token {
+    Identifier(String),
+    Number(String),
+    Operator(&'static str),
+}
+
+const OPERATORS: [&str; 24] = [
+    "&&", "||", "==", "!=", "<=", ">=", "<", ">", "!", "~", "&", "|", "^", "+", "-", "*", "(", ")",
+    "[", "]", ":", "{", "}", ",",
+];
+
+const BINARY_OPERATORS: [(&str, BinaryOperator, u8); 14] = [
+    ("||", BinaryOperator::LogicalOr, 1),
+    ("&&", BinaryOperator::LogicalAnd, 2),
+    ("|", BinaryOperator::BitwiseOr, 3),
+    ("^", BinaryOperator::BitwiseXor, 4),
+    ("&", BinaryOperator::BitwiseAnd, 5),
+    ("==", BinaryOperator::Equal, 6),
+    ("!=", BinaryOperator::NotEqual, 6),
+    ("<", BinaryOperator::Less, 7),
+    ("<=", BinaryOperator::LessEqual, 7),
+    (">", BinaryOperator::Greater, 7),
+    (">=", BinaryOperator::GreaterEqual, 7),
+    ("+", BinaryOperator::Add, 8),
+    ("-", BinaryOperator::Subtract, 8),
+    ("*", BinaryOperator::Multiply, 9),
+];
+
+pub fn parse(text: &str) -> Result<Expression, String> {
+    let tokens = tokenize(text)?;
+    let mut parser = Parser { tokens, idx: 0 };
+    let expression = parser.parse_binary(0)?;
+    match parser.peek() {
+        None => Ok(expression),
+        Some(token) => Err(format!("unexpected {token}")),
+    }
+}
+
+impl Expression {
+    pub fn signal_names(&self) -> Vec<&str> {
+        let mut signal_names = vec![];
+        self.collect_signal_names(&mut signal_names);
+        signal_names.sort();
+        signal_names.dedup();
+        signal_names
+    }
+
+    /// Evaluates the expression with the signal values given by `values`. Returns `None` if a
+    /// signal value is unknown.
+    pub fn evaluate(&self, values: &HashMap<&str, &BitVec<u32>>) -> Option<BitVec<u32>> {
+        match self {
+            Expression::Signal(signal_name) => values.get(&signal_name[..]).map(|v| (*v).clone()),
+            Expression::Literal(value) => Some(value.clone()),
+            Expression::Unary(operator, operand) => {
+                let operand = operand.evaluate(values)?;
+                Some(match operator {
+                    UnaryOperator::LogicalNot => from_bool(operand.not_any()),
+                    UnaryOperator::BitwiseNot => !operand,
+                    UnaryOperator::Negate => negate(operand),
+                    UnaryOperator::ReduceAnd => from_bool(operand.all()),
+                    UnaryOperator::ReduceOr => from_bool(operand.any()),
+                    UnaryOperator::ReduceXor => from_bool(operand.count_ones() % 2 == 1),
+                })
+            }
+            Expression::Binary(operator, lhs, rhs) => {
+                let lhs = lhs.evaluate(values)?;
+                let rhs = rhs.evaluate(values)?;
+                Some(operator.apply(lhs, rhs))
+            }
+            Expression::Slice(operand, msb, lsb) => {
+                let operand = resized(operand.evaluate(values)?, msb + 1);
+                Some(operand[*lsb..=*msb].to_bitvec())
+            }
+            Expression::Concatenation(operands) => {
+                let mut result = BitVec::new();
+                for operand in operands.iter().rev() {
+                    result.extend_from_bitslice(&operand.evaluate(values)?);
+                }
+                Some(result)
+            }
+        }
+    }
+
+    /// Evaluates the expression over the whole simulation, returning the value of each interval
+    /// (start time, end time) in which all referenced signals hold their value.
+    pub fn evaluate_intervals(
+        &self,
+        simulation_spec: &SimulationSpec,
+    ) -> Result<Vec<(usize, usize, BitVec<u32>)>, String> {
+        let mut waves: Vec<(&str, &Wave)> = vec![];
+        for signal_name in self.signal_names() {
+            match simulation_spec.get_wave_spec(signal_name) {
+                Some(wave_spec) => waves.push((signal_name, &wave_spec.wave)),
+                None => return Err(format!("unknown signal \"{signal_name}\"")),
+            }
+        }
+
+        let end_time = waves.iter().map(|(_, w)| w.end_time()).min().unwrap_or(0);
+        let mut start_times = vec![0];
+        for (_, wave) in waves.iter() {
+            let mut time = 0;
+            while let Some(change_time) = wave.next_change(time).filter(|t| *t <= end_time) {
+                start_times.push(change_time);
+                time = change_time;
+            }
+        }
+        start_times.sort();
+        start_times.dedup();
+
+        let mut intervals = vec![];
+        for (idx, start_time) in start_times.iter().enumerate() {
+            let values: Option<HashMap<&str, &BitVec<u32>>> = waves
+                .iter()
+                .map(|(signal_name, wave)| wave.at(*start_time).map(|v| (*signal_name, v)))
+                .collect();
+            let value = match values.and_then(|values| self.evaluate(&values)) {
+                Some(value) => value,
+                None => continue,
+            };
+            let interval_end_time = start_times.get(idx + 1).map_or(end_time, |t| t - 1);
+            intervals.push((*start_time, interval_end_time, value));
+        }
+        Ok(intervals)
+    }
+
+    /// Returns the (start time, end time) intervals in which the expression is true.
+    pub fn find_intervals(
+        &self,
+        simulation_spec: &SimulationSpec,
+    ) -> Result<Vec<(usize, usize)>, String> {
+        let mut matches: Vec<(usize, usize)> = vec![];
+        for (start_time, end_time, value) in self.evaluate_intervals(simulation_spec)? {
+            if value.not_any() {
+                continue;
+            }
+            match matches.last_mut() {
+                Some(last) if last.1 + 1 == start_time => last.1 = end_time,
+                _ => matches.push((start_time, end_time)),
+            }
+        }
+        Ok(matches)
+    }
+
+    fn collect_signal_names<'a>(&'a self, signal_names: &mut Vec<&'a str>) {
+        match self {
+            Expression::Signal(signal_name) => signal_names.push(signal_name),
+            Expression::Literal(_) => (),
+            Expression::Unary(_, operand) => operand.collect_signal_names(signal_names),
+            Expression::Binary(_, lhs, rhs) => {
+                lhs.collect_signal_names(signal_names);
+                rhs.collect_signal_names(signal_names);
+            }
+            Expression::Slice(operand, _, _) => operand.collect_signal_names(signal_names),
+            Expression::Concatenation(operands) => operands
+                .iter()
+                .for_each(|operand| operand.collect_signal_names(signal_names)),
+        }
+    }
+}
+
+impl BinaryOperator {
+    fn apply(self, lhs: BitVec<u32>, rhs: BitVec<u32>) -> BitVec<u32> {
+        if let BinaryOperator::Multiply = self {
+            return multiply(&lhs, &rhs);
+        }
+        let width = lhs.len().max(rhs.len());
+        let (lhs, rhs) = (resized(lhs, width), resized(rhs, width));
+        match self {
+            BinaryOperator::LogicalOr => from_bool(lhs.any() || rhs.any()),
+            BinaryOperator::LogicalAnd => from_bool(lhs.any() && rhs.any()),
+            BinaryOperator::BitwiseOr => lhs | rhs,
+            BinaryOperator::BitwiseXor => lhs ^ rhs,
+            BinaryOperator::BitwiseAnd => lhs & rhs,
+            BinaryOperator::Equal => from_bool(lhs == rhs),
+            BinaryOperator::NotEqual => from_bool(lhs != rhs),
+            BinaryOperator::Less => from_bool(compare(&lhs, &rhs) == Ordering::Less),
+            BinaryOperator::LessEqual => from_bool(compare(&lhs, &rhs) != Ordering::Greater),
+            BinaryOperator::Greater => from_bool(compare(&lhs, &rhs) == Ordering::Greater),
+            BinaryOperator::GreaterEqual => from_bool(compare(&lhs, &rhs) != Ordering::Less),
+            BinaryOperator::Add => add(&lhs, &rhs, false),
+            BinaryOperator::Subtract => add(&lhs, &!rhs, true),
+            BinaryOperator::Multiply => unreachable!(),
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    idx: usize,
+}
+
+impl Parser {
+    fn parse_binary(&mut self, min_precedence: u8) -> Result<Expression, String> {
+        let mut lhs = self.parse_unary()?;
+        while let Some((operator, precedence)) = self.peek_binary_operator() {
+            if precedence <= min_precedence {
+                break;
+            }
+            self.idx += 1;
+            let rhs = self.parse_binary(precedence)?;
+            lhs = Expression::Binary(operator, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expression, String> {
+        let operator = match self.peek() {
+            Some(Token::Operator("!")) => UnaryOperator::LogicalNot,
+            Some(Token::Operator("~")) => UnaryOperator::BitwiseNot,
+            Some(Token::Operator("-")) => UnaryOperator::Negate,
+            Some(Token::Operator("&")) => UnaryOperator::ReduceAnd,
+            Some(Token::Operator("|")) => UnaryOperator::ReduceOr,
+            Some(Token::Operator("^")) => UnaryOperator::ReduceXor,
+            _ => return self.parse_postfix(),
+        };
+        self.idx += 1;
+        Ok(Expression::Unary(operator, Box::new(self.parse_unary()?)))
+    }
+
+    fn parse_postfix(&mut self) -> Result<Expression, String> {
+        let mut expression = self.parse_primary()?;
+        while self.peek() == Some(&Token::Operator("[")) {
+            self.idx += 1;
+            let msb = self.parse_index()?;
+            let lsb = match self.next() {
+                Some(Token::Operator(":")) => {
+                    let lsb = self.parse_index()?;
+                    self.expect("]")?;
+                    lsb
+                }
+                Some(Token::Operator("]")) => msb,
+                _ => return Err("expected : or ]".to_string()),
+            };
+            if lsb > msb {
+                return Err(format!("slice [{msb}:{lsb}] is reversed"));
+            }
+            expression = Expression::Slice(Box::new(expression), msb, lsb);
+        }
+        Ok(expression)
+    }
+
+    fn parse_primary(&mut self) -> Result<Expression, String> {
+        let token = self.next().ok_or("unexpected end of expression")?;
+        match token {
+            Token::Identifier(name) => Ok(Expression::Signal(name)),
+            Token::Number(number) => Ok(Expression::Literal(parse_literal(&number)?)),
+            Token::Operator("(") => {
+                let expression = self.parse_binary(0)?;
+                self.expect(")")?;
+                Ok(expression)
+            }
+            Token::Operator("{") => {
+                let mut operands = vec![self.parse_binary(0)?];
+                while self.peek() == Some(&Token::Operator(",")) {
+                    self.idx += 1;
+                    operands.push(self.parse_binary(0)?);
+                }
+                self.expect("}")?;
+                Ok(Expression::Concatenation(operands))
+            }
+            token => Err(format!("unexpected {token}")),
+        }
+    }
+
+    fn parse_index(&mut self) -> Result<usize, String> {
+        match self.next() {
+            Some(Token::Number(number)) => number
+                .parse()
+                .map_err(|_| format!("expected a decimal bit index, got {number}")),
+            _ => Err("expected a bit index".to_string()),
+        }
+    }
+
+    fn expect(&mut self, symbol: &str) -> Result<(), String> {
+        match self.next() {
+            Some(Token::Operator(s)) if s == symbol => Ok(()),
+            _ => Err(format!("expected {symbol}")),
+        }
+    }
+
+    fn peek_binary_operator(&self) -> Option<(BinaryOperator, u8)> {
+        match self.peek() {
+            Some(Token::Operator(symbol)) => BINARY_OPERATORS
+                .iter()
+                .find(|(s, _, _)| s == symbol)
+                .map(|(_, operator, precedence)| (*operator, *precedence)),
+            _ => None,
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.idx)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.idx).cloned();
+        self.idx += 1;
+        token
+    }
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Identifier(name) => write!(f, "\"{name}\""),
+            Token::Number(number) => write!(f, "{number}"),
+            Token::Operator(symbol) => write!(f, "\"{symbol}\""),
+        }
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut rest = text.trim_start();
+    while !rest.is_empty() {
+        let first = rest.chars().next().unwrap();
+        let word_length = rest
+            .find(|c: char| !(c.is_alphanumeric() || "_.$".contains(c)))
+            .unwrap_or(rest.len());
+        if first.is_ascii_digit() {
+            let number = rest[..word_length].replace('_', "");
+            tokens.push(Token::Number(number));
+            rest = &rest[word_length..];
+        } else if first.is_alphabetic() || first == '_' {
+            tokens.push(Token::Identifier(rest[..word_length].to_string()));
+            rest = &rest[word_length..];
+        } else if let Some(operator) = OPERATORS.iter().find(|o| rest.starts_with(*o)) {
+            tokens.push(Token::Operator(operator));
+            rest = &rest[operator.len()..];
+        } else {
+            return Err(format!("unexpected character '{first}'"));
+        }
+        rest = rest.trim_start();
+    }
+    Ok(tokens)
+}
+
+/// Parses a literal, giving decimal literals the smallest width that holds their value.
+fn parse_literal(number: &str) -> Result<BitVec<u32>, String> {
+    let mut value = bitvec_str::parse(number)?;
+    if !number.starts_with('0') || number.len() == 1 {
+        value.truncate(value.last_one().map_or(1, |idx| idx + 1));
+    }
+    Ok(value)
+}
+
+fn from_bool(value: bool) -> BitVec<u32> {
+    BitVec::repeat(value, 1)
+}
+
+fn resized(mut bit_vec: BitVec<u32>, width: usize) -> BitVec<u32> {
+    bit_vec.resize(width, false);
+    bit_vec
+}
+
+fn add(lhs: &BitVec<u32>, rhs: &BitVec<u32>, carry_in: bool) -> BitVec<u32> {
+    let mut carry = carry_in;
+    let mut result = BitVec::with_capacity(lhs.len());
+    for (a, b) in lhs.iter().by_vals().zip(rhs.iter().by_vals()) {
+        result.push(a ^ b ^ carry);
+        carry = (a && b) || (carry && (a ^ b));
+    }
+    result
+}
+
+fn negate(operand: BitVec<u32>) -> BitVec<u32> {
+    let zero = BitVec::repeat(false, operand.len());
+    add(&zero, &!operand, true)
+}
+
+fn multiply(lhs: &BitVec<u32>, rhs: &BitVec<u32>) -> BitVec<u32> {
+    let width = lhs.len() + rhs.len();
+    let mut result = BitVec::repeat(false, width);
+    for idx in rhs.iter_ones() {
+        let mut partial_product = BitVec::repeat(false, idx);
+        partial_product.extend_from_bitslice(lhs);
+        result = add(&result, &resized(partial_product, width), false);
+    }
+    result
+}
+
+fn compare(lhs: &BitVec<u32>, rhs: &BitVec<u32>) -> Ordering {
+    lhs.iter().rev().cmp(rhs.iter().rev())
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use bitvec::{bitvec, order::Lsb0};
+
+    use super::parse;
+
+    #[test]
+    fn test_evaluate() {
+        let state = bitvec![u32, Lsb0; 1, 1, 0];
+        let valid = bitvec![u32, Lsb0; 1];
+        let values = HashMap::from([("top.state", &state), ("top.valid", &valid)]);
+
+        let expression = parse("top.state == 3 && top.valid").unwrap();
+        assert_eq!(expression.signal_names(), vec!["top.state", "top.valid"]);
+        assert!(expression.evaluate(&values).unwrap().any());
+
+        let expression = parse("!(top.state > 0b11) && ~top.valid").unwrap();
+        assert!(expression.evaluate(&values).unwrap().not_any());
+
+        let expression = parse("top.state & 0x2 | top.valid ^ 1").unwrap();
+        assert_eq!(expression.evaluate(&values).unwrap()[..3], bitvec![0, 1, 0]);
+
+        assert!(parse("top.state ==").is_err());
+        assert!(parse("top.state[0:1]").is_err());
+        assert!(parse("{top.state, top.valid").is_err());
+        assert!(parse("(top.state").is_err());
+        assert!(parse("top.state = 3").is_err());
+    }
+
+    #[test]
+    fn test_evaluate_derived() {
+        let data = bitvec![u32, Lsb0; 0, 1, 1, 0, 1, 0, 0, 1];
+        let valid = bitvec![u32, Lsb0; 1];
+        let values = HashMap::from([("data", &data), ("valid", &valid)]);
+        let evaluate = |text| parse(text).unwrap().evaluate(&values).unwrap();
+
+        assert_eq!(evaluate("data[3:1]"), bitvec![u32, Lsb0; 1, 1, 0]);
+        assert_eq!(evaluate("data[4]"), bitvec![u32, Lsb0; 1]);
+        assert_eq!(evaluate("{valid, data[1:0]}"), bitvec![u32, Lsb0; 0, 1, 1]);
+        assert_eq!(evaluate("data[3:0] + 10"), bitvec![u32, Lsb0; 0, 0, 0, 0]);
+        assert_eq!(evaluate("data[3:0] - 7"), bitvec![u32, Lsb0; 1, 1, 1, 1]);
+        assert_eq!(evaluate("data[1:0] * 3"), bitvec![u32, Lsb0; 0, 1, 1, 0]);
+        assert_eq!(evaluate("-valid"), bitvec![u32, Lsb0; 1]);
+        assert_eq!(evaluate("^data"), bitvec![u32, Lsb0; 0]);
+        assert_eq!(evaluate("&data[2:1] && |data"), bitvec![u32, Lsb0; 1]);
+        assert_eq!(evaluate("data[3:0] + 1 == 7"), bitvec![u32, Lsb0; 1]);
+    }
+}
@@ -0,0 +1,137 @@
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::utils::config;
+
+const MAX_NUM_OF_ENTRIES: usize = 1000;
+
+/// Commands entered in the terminal, kept across runs in `history` in the data directory.
+#[derive(Default)]
+pub struct History {
+    entries: Vec<String>,
+    path: Option<PathBuf>,
+    /// Index of the entry shown while browsing, and the line edited before browsing started
+    browsing: Option<(usize, String)>,
+}
+
+impl History {
+    /// Reads the saved history. Failing to read or write it only costs the history, so errors
+    /// are ignored.
+    pub fn load() -> Self {
+        let path = config::data_dir().map(|dir| dir.join("history"));
+        let mut entries: Vec<String> = path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|content| content.lines().map(str::to_string).collect())
+            .unwrap_or_default();
+        if entries.len() > MAX_NUM_OF_ENTRIES {
+            entries.drain(..entries.len() - MAX_NUM_OF_ENTRIES);
+            if let Some(path) = &path {
+                let _ = fs::write(path, entries.join("\n") + "\n");
+            }
+        }
+        Self {
+            entries,
+            path,
+            browsing: None,
+        }
+    }
+
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Replaces the entries, e.g. with those of a session, without saving them.
+    pub fn set_entries(&mut self, entries: Vec<String>) {
+        self.entries = entries;
+        self.browsing = None;
+    }
+
+    pub fn push(&mut self, entry: &str) {
+        self.browsing = None;
+        if entry.trim().is_empty() || self.entries.last().is_some_and(|last| last == entry) {
+            return;
+        }
+        self.entries.push(entry.to_string());
+        if let Some(path) = &self.path {
+            let _ = Self::append_to_file(path, entry);
+        }
+    }
+
+    /// Steps back to the previous entry. `line` is the line being edited, restored once
+    /// browsing goes past the latest entry.
+    pub fn previous(&mut self, line: &str) -> Option<&str> {
+        let idx = match &self.browsing {
+            Some((0, _)) => return None,
+            Some((idx, _)) => idx - 1,
+            None if self.entries.is_empty() => return None,
+            None => self.entries.len() - 1,
+        };
+        let edited_line = match self.browsing.take() {
+            Some((_, edited_line)) => edited_line,
+            None => line.to_string(),
+        };
+        self.browsing = Some((idx, edited_line));
+        Some(&self.entries[idx])
+    }
+
+    pub fn next(&mut self) -> Option<String> {
+        let (idx, edited_line) = self.browsing.take()?;
+        if idx + 1 < self.entries.len() {
+            self.browsing = Some((idx + 1, edited_line));
+            Some(self.entries[idx + 1].clone())
+        } else {
+            Some(edited_line)
+        }
+    }
+
+    /// Index of the latest entry before `before` that contains `query`.
+    pub fn search(&self, query: &str, before: usize) -> Option<usize> {
+        self.entries[..before.min(self.entries.len())]
+            .iter()
+            .rposition(|entry| entry.contains(query))
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&str> {
+        self.entries.get(idx).map(|entry| &entry[..])
+    }
+
+    fn append_to_file(path: &Path, entry: &str) -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = File::options().create(true).append(true).open(path)?;
+        writeln!(file, "{entry}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::History;
+
+    #[test]
+    fn test_browse_and_search() {
+        let mut history = History::default();
+        for entry in ["load fifo.sv", "run 10ns", "run 10ns", " ", "set in 1"] {
+            history.push(entry);
+        }
+        assert_eq!(history.entries(), ["load fifo.sv", "run 10ns", "set in 1"]);
+
+        assert_eq!(history.previous("ru"), Some("set in 1"));
+        assert_eq!(history.previous("ignored"), Some("run 10ns"));
+        assert_eq!(history.previous(""), Some("load fifo.sv"));
+        assert_eq!(history.previous(""), None);
+        assert_eq!(history.next().as_deref(), Some("run 10ns"));
+        assert_eq!(history.next().as_deref(), Some("set in 1"));
+        assert_eq!(history.next().as_deref(), Some("ru"));
+        assert_eq!(history.next(), None);
+
+        assert_eq!(history.search("run", 3), Some(1));
+        assert_eq!(history.search("fifo", 1), Some(0));
+        assert_eq!(history.search("fifo", 0), None);
+        assert_eq!(history.search("reset", 3), None);
+    }
+}
@@ -0,0 +1,185 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use oombak_sim::{ClockSpec, InitSpec, ResetSpec};
+use serde::{Deserialize, Serialize};
+
+use crate::{backend::interpreter, utils::bitvec_str};
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct InitProfiles {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, InitProfile>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct InitProfile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clock: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub period: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reset: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reset_active: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reset_cycles: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inputs: Option<String>,
+    #[serde(default)]
+    pub defaults: BTreeMap<String, String>,
+}
+
+impl InitProfiles {
+    pub fn path_of(design_path: &Path) -> PathBuf {
+        let mut file_name = design_path.file_name().unwrap_or_default().to_os_string();
+        file_name.push(".profiles.toml");
+        design_path.with_file_name(file_name)
+    }
+
+    pub fn load(design_path: &Path) -> Result<Self, String> {
+        let path = Self::path_of(design_path);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        toml::from_str(&content).map_err(|e| format!("cannot parse {}: {e}", path.display()))
+    }
+
+    pub fn save(&self, design_path: &Path) -> Result<(), String> {
+        let path = Self::path_of(design_path);
+        let content = toml::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(&path, content).map_err(|e| format!("cannot write {}: {e}", path.display()))
+    }
+
+    pub fn get(&self, name: Option<&str>) -> Result<(&str, &InitProfile), String> {
+        let name = match name.or(self.auto.as_deref()) {
+            Some(name) => name,
+            None => {
+                return Err(
+                    "no profile name given and no profile is applied automatically".to_string(),
+                )
+            }
+        };
+        match self.profiles.get_key_value(name) {
+            Some((name, profile)) => Ok((name, profile)),
+            None => Err(format!("unknown profile \"{name}\"")),
+        }
+    }
+}
+
+impl InitProfile {
+    pub fn update(&mut self, other: InitProfile) {
+        self.clock = other.clock.or(self.clock.take());
+        self.period = other.period.or(self.period.take());
+        self.reset = other.reset.or(self.reset.take());
+        self.reset_active = other.reset_active.or(self.reset_active.take());
+        self.reset_cycles = other.reset_cycles.or(self.reset_cycles.take());
+        self.inputs = other.inputs.or(self.inputs.take());
+        self.defaults.extend(other.defaults);
+    }
+}
+
+impl TryFrom<&InitProfile> for InitSpec {
+    type Error = String;
+
+    fn try_from(profile: &InitProfile) -> Result<Self, Self::Error> {
+        let input_default = profile
+            .inputs
+            .as_deref()
+            .map(bitvec_str::parse)
+            .transpose()?;
+
+        let mut default_values = vec![];
+        for (signal_name, value) in profile.defaults.iter() {
+            default_values.push((signal_name.clone(), bitvec_str::parse(value)?));
+        }
+
+        let clock = match (&profile.clock, &profile.period) {
+            (Some(signal_name), Some(period)) => {
+                let period = interpreter::parse_duration(period)?;
+                if period < 2 {
+                    return Err(format!("clock period {period} is too short"));
+                }
+                let high_duration = period / 2;
+                let clock_spec = ClockSpec {
+                    phase: period - high_duration,
+                    ..ClockSpec::new(high_duration, period - high_duration)
+                };
+                Some((signal_name.clone(), clock_spec))
+            }
+            (Some(_), None) => return Err("clock is given without a period".to_string()),
+            (None, Some(_)) => return Err("period is given without a clock".to_string()),
+            (None, None) => None,
+        };
+
+        let reset = match &profile.reset {
+            Some(signal_name) => {
+                let active_value = match &profile.reset_active {
+                    Some(value) => bitvec_str::parse(value)?,
+                    None => bitvec_str::parse("1")?,
+                };
+                let inactive_value = !active_value.clone();
+                Some(ResetSpec {
+                    signal_name: signal_name.clone(),
+                    active_value,
+                    inactive_value,
+                    num_of_cycles: profile.reset_cycles.unwrap_or(1),
+                })
+            }
+            None => None,
+        };
+
+        Ok(InitSpec {
+            input_default,
+            default_values,
+            clock,
+            reset,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oombak_sim::InitSpec;
+
+    use super::InitProfile;
+
+    #[test]
+    fn test_init_spec_from_profile() {
+        let profile = InitProfile {
+            clock: Some("clk".to_string()),
+            period: Some("10ns".to_string()),
+            reset: Some("rst_n".to_string()),
+            reset_active: Some("0b0".to_string()),
+            reset_cycles: Some(4),
+            ..Default::default()
+        };
+        let init_spec = InitSpec::try_from(&profile).unwrap();
+
+        let (clock_signal_name, clock_spec) = init_spec.clock.unwrap();
+        assert_eq!(clock_signal_name, "clk");
+        assert_eq!(clock_spec.period(), 10_000);
+        assert_eq!(clock_spec.phase, 5_000);
+
+        let reset_spec = init_spec.reset.unwrap();
+        assert_eq!(reset_spec.num_of_cycles, 4);
+        assert!(reset_spec.active_value.not_any());
+        assert!(reset_spec.inactive_value.all());
+    }
+
+    #[test]
+    fn test_clock_requires_period() {
+        let profile = InitProfile {
+            clock: Some("clk".to_string()),
+            ..Default::default()
+        };
+        assert!(InitSpec::try_from(&profile).is_err());
+    }
+}
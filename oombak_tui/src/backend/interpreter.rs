@@ -1,50 +1,134 @@
-use std::{path::PathBuf, sync::OnceLock};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::PathBuf,
+    sync::OnceLock,
+};
 
 use bitvec::vec::BitVec;
+use oombak_sim::{ClockSpec, Edge, StimulusSpec};
+use ratatui::style::Color;
 
-use crate::utils::bitvec_str;
+use crate::{
+    backend::{decoder, expression, init_profile::InitProfile},
+    styles::theme,
+    utils::{bitvec_str, pattern::Pattern},
+    widgets::OutputKind,
+};
 
 pub enum Command {
     Run(usize),
-    Load(PathBuf),
+    RunCycles(usize),
+    Load(PathBuf, Option<String>),
     Set(String, BitVec<u32>),
     SetPeriodic(String, usize, BitVec<u32>, BitVec<u32>),
+    SetClock(String, ClockSpec),
+    SetStimulus(String, StimulusSpec),
+    SetReferenceClock(String),
+    SetAtEdge(String, BitVec<u32>, Edge, usize),
+    DefineProfile(String, InitProfile, Option<bool>),
+    Init(Option<String>),
+    SaveSession(PathBuf),
+    OpenSession(PathBuf),
+    AddMarker(String, Option<usize>),
+    DeleteMarker(String),
+    GotoMarker(String),
+    Find(FindTarget, Option<String>, bool),
+    Search(String),
+    Derive(String, String),
+    RemoveDerived(String),
+    Expand(String, Option<(usize, usize)>),
+    Collapse(String),
+    AddGroup(String),
+    RemoveGroup(String),
+    Move(Option<String>, bool),
+    Hide(Option<String>),
+    Show(Option<String>),
+    Duplicate(Option<String>),
+    Filter(Option<String>),
+    Probe(ProbeAction, Option<String>),
+    DefineEnumMap(String, Vec<(BitVec<u32>, String)>),
+    /// Adds a decoder with its name, protocol and arguments, or lists the protocols
+    Decode(Option<(String, String, BTreeMap<String, String>)>),
+    RemoveDecoder(String),
+    ShowTransactions(Option<String>),
+    SetColor(Option<Color>, Option<String>),
+    Theme(Option<String>),
+    Log(LogAction),
     Quit,
     Help,
     Noop,
 }
 
+#[derive(Clone)]
+pub enum FindTarget {
+    Change,
+    Edge(Edge),
+    Value(BitVec<u32>),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ProbeAction {
+    Add,
+    Remove,
+    List,
+}
+
+pub enum LogAction {
+    /// Shows only the kinds of output, all of them if empty
+    Filter(Vec<OutputKind>),
+    Search(Option<String>),
+    Group(bool),
+    Save(PathBuf),
+}
+
 struct CommandInfo {
     name: &'static str,
     description: &'static str,
     args: Vec<&'static str>,
+    options: Vec<(&'static str, &'static str)>,
     parser: Parser,
 }
 
-type Parser = Box<dyn Fn(&[&str]) -> Result<Command, String> + Send + Sync>;
+type Options<'a> = HashMap<&'a str, &'a str>;
+
+type Parser = Box<dyn Fn(&[&str], &Options) -> Result<Command, String> + Send + Sync>;
+
+const TIME_UNITS: [(&str, f64); 5] = [
+    ("ps", 1.0),
+    ("ns", 1e3),
+    ("us", 1e6),
+    ("ms", 1e9),
+    ("s", 1e12),
+];
 
-static ALL_COMMAND_INFO: OnceLock<[CommandInfo; 6]> = OnceLock::new();
+const FREQUENCY_UNITS: [(&str, f64); 4] = [("GHz", 1e9), ("MHz", 1e6), ("kHz", 1e3), ("Hz", 1.0)];
+
+static ALL_COMMAND_INFO: OnceLock<[CommandInfo; 42]> = OnceLock::new();
 static HELP: OnceLock<String> = OnceLock::new();
 
-fn all_command_info() -> &'static [CommandInfo; 6] {
+fn all_command_info() -> &'static [CommandInfo; 42] {
     ALL_COMMAND_INFO.get_or_init(|| {
         [
             CommandInfo {
                 name: "run",
-                description: "run the simulation for as long as the duration.",
-                args: vec!["duration"],
+                description: "run the simulation for as long as the duration, or for a number of clock cycles",
+                args: vec!["duration or number of cycles", "[cycles]"],
+                options: vec![],
                 parser: Box::new(parse_run),
             },
             CommandInfo {
                 name: "load",
-                description: "loads the file for simulation",
-                args: vec!["SystemVerilog file path"],
+                description: "loads the top-level SystemVerilog file, or a filelist, for simulation",
+                args: vec!["design file path"],
+                options: vec![("top", "top-level module name")],
                 parser: Box::new(parse_load),
             },
             CommandInfo {
                 name: "set",
                 description: "sets the signal value",
                 args: vec!["signal name", "value"],
+                options: vec![],
                 parser: Box::new(parse_set),
             },
             CommandInfo {
@@ -56,18 +140,288 @@ fn all_command_info() -> &'static [CommandInfo; 6] {
                     "low state value",
                     "high state value",
                 ],
+                options: vec![],
                 parser: Box::new(parse_set_periodic),
             },
+            CommandInfo {
+                name: "set-clock",
+                description: "drives a 1-bit clock on the signal, starting high after the phase",
+                args: vec!["signal name", "frequency or period"],
+                options: vec![
+                    ("duty", "duty cycle %"),
+                    ("phase", "phase"),
+                    ("cycles", "number of cycles"),
+                    ("jitter", "jitter"),
+                ],
+                parser: Box::new(parse_set_clock),
+            },
+            CommandInfo {
+                name: "set-sequence",
+                description:
+                    "drives the signal through a sequence of values, each held for its duration",
+                args: vec!["signal name", "value:duration..."],
+                options: vec![("repeat", "true|false")],
+                parser: Box::new(parse_set_sequence),
+            },
+            CommandInfo {
+                name: "set-ramp",
+                description: "drives the signal with a counter, stepping every step duration",
+                args: vec!["signal name", "start value", "step duration"],
+                options: vec![("increment", "increment"), ("steps", "number of steps")],
+                parser: Box::new(parse_set_ramp),
+            },
+            CommandInfo {
+                name: "set-random",
+                description: "drives the signal with pseudo-random values from a seeded LFSR",
+                args: vec!["signal name", "step duration"],
+                options: vec![("seed", "seed"), ("steps", "number of steps")],
+                parser: Box::new(parse_set_random),
+            },
+            CommandInfo {
+                name: "set-from-file",
+                description:
+                    "drives the signal with values read from a CSV file of value,duration rows",
+                args: vec!["signal name", "CSV file path"],
+                options: vec![("repeat", "true|false")],
+                parser: Box::new(parse_set_from_file),
+            },
+            CommandInfo {
+                name: "clock",
+                description: "designates the reference clock for cycle-based commands",
+                args: vec!["signal name"],
+                options: vec![],
+                parser: Box::new(parse_clock),
+            },
+            CommandInfo {
+                name: "set-at-edge",
+                description: "sets the signal value after the next edge of the reference clock",
+                args: vec!["signal name", "value", "rising|falling", "[+skew]"],
+                options: vec![],
+                parser: Box::new(parse_set_at_edge),
+            },
+            CommandInfo {
+                name: "profile",
+                description: "defines or updates an initialization profile of the loaded design",
+                args: vec!["profile name"],
+                options: vec![
+                    ("clock", "signal name"),
+                    ("period", "clock period"),
+                    ("reset", "signal name"),
+                    ("active", "reset active value"),
+                    ("cycles", "reset cycles"),
+                    ("inputs", "default input value"),
+                    ("auto", "true|false"),
+                ],
+                parser: Box::new(parse_profile),
+            },
+            CommandInfo {
+                name: "profile-default",
+                description: "sets the default value of a signal in an initialization profile",
+                args: vec!["profile name", "signal name", "value"],
+                options: vec![],
+                parser: Box::new(parse_profile_default),
+            },
+            CommandInfo {
+                name: "init",
+                description: "applies an initialization profile (default: the automatically applied one)",
+                args: vec!["[profile name]"],
+                options: vec![],
+                parser: Box::new(parse_init),
+            },
+            CommandInfo {
+                name: "save-session",
+                description: "saves the loaded design, probes, wave layout and history",
+                args: vec!["session file path"],
+                options: vec![],
+                parser: Box::new(parse_save_session),
+            },
+            CommandInfo {
+                name: "open-session",
+                description: "restores a session saved with save-session",
+                args: vec!["session file path"],
+                options: vec![],
+                parser: Box::new(parse_open_session),
+            },
+            CommandInfo {
+                name: "marker",
+                description: "drops (or moves) a marker at the cursor or the given time",
+                args: vec!["marker name", "[time]"],
+                options: vec![],
+                parser: Box::new(parse_marker),
+            },
+            CommandInfo {
+                name: "delete-marker",
+                description: "deletes a marker",
+                args: vec!["marker name"],
+                options: vec![],
+                parser: Box::new(parse_delete_marker),
+            },
+            CommandInfo {
+                name: "goto-marker",
+                description: "moves the cursor to a marker",
+                args: vec!["marker name"],
+                options: vec![],
+                parser: Box::new(parse_goto_marker),
+            },
+            CommandInfo {
+                name: "find",
+                description: "moves the cursor to the next value change, edge or value of a signal (default: the selected one)",
+                args: vec!["change|rising|falling|value", "[value]"],
+                options: vec![("signal", "signal name"), ("backward", "true|false")],
+                parser: Box::new(parse_find),
+            },
+            CommandInfo {
+                name: "search",
+                description: "lists the time intervals in which the expression over probed signals is true",
+                args: vec!["expression..."],
+                options: vec![],
+                parser: Box::new(parse_search),
+            },
+            CommandInfo {
+                name: "derive",
+                description: "defines a signal computed from an expression over probed signals",
+                args: vec!["signal name", "expression..."],
+                options: vec![],
+                parser: Box::new(parse_derive),
+            },
+            CommandInfo {
+                name: "remove-derived",
+                description: "removes a signal defined with derive",
+                args: vec!["signal name"],
+                options: vec![],
+                parser: Box::new(parse_remove_derived),
+            },
+            CommandInfo {
+                name: "expand",
+                description: "shows each bit of a bus, or the given bit range, as a child row",
+                args: vec!["signal name", "[msb:lsb]"],
+                options: vec![],
+                parser: Box::new(parse_expand),
+            },
+            CommandInfo {
+                name: "collapse",
+                description: "hides the child rows of a bus",
+                args: vec!["signal name"],
+                options: vec![],
+                parser: Box::new(parse_collapse),
+            },
+            CommandInfo {
+                name: "group",
+                description: "adds a collapsible group header above the selected row",
+                args: vec!["group name"],
+                options: vec![],
+                parser: Box::new(parse_group),
+            },
+            CommandInfo {
+                name: "ungroup",
+                description: "removes a group header, keeping its rows",
+                args: vec!["group name"],
+                options: vec![],
+                parser: Box::new(parse_ungroup),
+            },
+            CommandInfo {
+                name: "move",
+                description: "moves a row of the signal list (default: the selected one)",
+                args: vec!["up|down", "[signal name]"],
+                options: vec![],
+                parser: Box::new(parse_move),
+            },
+            CommandInfo {
+                name: "hide",
+                description: "removes a signal from view without un-probing it (default: the selected one)",
+                args: vec!["[signal name]"],
+                options: vec![],
+                parser: Box::new(parse_hide),
+            },
+            CommandInfo {
+                name: "show",
+                description: "shows a hidden signal again, or lists the hidden signals",
+                args: vec!["[signal name]"],
+                options: vec![],
+                parser: Box::new(parse_show),
+            },
+            CommandInfo {
+                name: "duplicate",
+                description: "adds a copy of a signal with its own display properties (default: the selected one)",
+                args: vec!["[signal name]"],
+                options: vec![],
+                parser: Box::new(parse_duplicate),
+            },
+            CommandInfo {
+                name: "filter",
+                description: "shows only the rows matching a fuzzy or wildcard (*, ?) pattern, or all rows if omitted",
+                args: vec!["[pattern]"],
+                options: vec![],
+                parser: Box::new(parse_filter),
+            },
+            CommandInfo {
+                name: "probe",
+                description: "adds or removes every signal whose path matches a fuzzy, wildcard (*, ?) or /regex/ pattern, or lists the probed signals",
+                args: vec!["add|remove|list", "[pattern]"],
+                options: vec![],
+                parser: Box::new(parse_probe),
+            },
+            CommandInfo {
+                name: "enum-map",
+                description: "defines names for signal values, shown with the Enum radix",
+                args: vec!["map name", "value=name..."],
+                options: vec![],
+                parser: Box::new(parse_enum_map),
+            },
+            CommandInfo {
+                name: "decode",
+                description: "adds a row of the transactions decoded from the signals of a bus, given as role=signal, prefix=<common prefix> and protocol parameters, or lists the protocols",
+                args: vec!["[decoder name]", "[protocol]", "[role=signal]..."],
+                options: vec![],
+                parser: Box::new(parse_decode),
+            },
+            CommandInfo {
+                name: "remove-decoder",
+                description: "removes a row added with decode",
+                args: vec!["decoder name"],
+                options: vec![],
+                parser: Box::new(parse_remove_decoder),
+            },
+            CommandInfo {
+                name: "transactions",
+                description: "lists the transactions of a decoder (default: the selected one)",
+                args: vec!["[decoder name]"],
+                options: vec![],
+                parser: Box::new(parse_transactions),
+            },
+            CommandInfo {
+                name: "color",
+                description: "sets the waveform color of a signal (default: the selected one), or resets it with none",
+                args: vec!["color", "[signal name]"],
+                options: vec![],
+                parser: Box::new(parse_color),
+            },
+            CommandInfo {
+                name: "theme",
+                description: "switches to a color theme, or lists the themes",
+                args: vec!["[theme name]"],
+                options: vec![],
+                parser: Box::new(parse_theme),
+            },
+            CommandInfo {
+                name: "log",
+                description: "shows only some kinds of terminal output (normal, notification, error, or all), searches it, groups notifications by request (on|off), or saves it to a file",
+                args: vec!["filter|search|group|save", "[argument]..."],
+                options: vec![],
+                parser: Box::new(parse_log),
+            },
             CommandInfo {
                 name: "quit",
                 description: "closes this application",
                 args: vec![],
+                options: vec![],
                 parser: Box::new(parse_quit),
             },
             CommandInfo {
                 name: "help",
                 description: "displays this message",
                 args: vec![],
+                options: vec![],
                 parser: Box::new(parse_help),
             },
         ]
@@ -82,13 +436,33 @@ pub fn interpret(command_string: &str) -> Result<Command, String> {
     let (command, args) = words.split_at(1);
     for command_info in all_command_info() {
         if command_info.name == command[0] {
-            check_usage(command_info, args)?;
-            return (command_info.parser)(args);
+            // Commands without options take "=" literally, e.g. in expressions
+            let (args, options) = if command_info.options.is_empty() {
+                (args.to_vec(), Options::new())
+            } else {
+                split_options(args)
+            };
+            check_usage(command_info, &args, &options)?;
+            return (command_info.parser)(&args, &options);
         }
     }
     Err(format!("unknown command \"{}\"", command[0]))
 }
 
+pub fn command_names() -> impl Iterator<Item = &'static str> {
+    all_command_info()
+        .iter()
+        .map(|command_info| command_info.name)
+}
+
+pub fn option_names(command_name: &str) -> Vec<&'static str> {
+    all_command_info()
+        .iter()
+        .find(|command_info| command_info.name == command_name)
+        .map(|command_info| command_info.options.iter().map(|(name, _)| *name).collect())
+        .unwrap_or_default()
+}
+
 pub fn help() -> &'static str {
     HELP.get_or_init(|| {
         let mut help = "Commands:\n".to_string();
@@ -100,32 +474,42 @@ pub fn help() -> &'static str {
             help += command_info.description;
             help += "\n";
         }
+        help +=
+            "Durations are in time steps, unless suffixed with ps, ns, us, ms or s, which count\n";
+        help += "a time step as 1 ps.\n";
         help
     })
 }
 
-fn parse_run(args: &[&str]) -> Result<Command, String> {
-    if let Ok(duration) = args[0].parse() {
-        Ok(Command::Run(duration))
-    } else {
-        Err(format!("cannot parse {} as u64", args[0]))
+fn parse_run(args: &[&str], _options: &Options) -> Result<Command, String> {
+    match args {
+        [duration] => Ok(Command::Run(parse_duration(duration)?)),
+        [num_of_cycles, "cycles"] => match num_of_cycles.parse() {
+            Ok(num_of_cycles) => Ok(Command::RunCycles(num_of_cycles)),
+            Err(e) => Err(format!(
+                "cannot parse number of cycles {num_of_cycles}: {e}"
+            )),
+        },
+        [_, unit] => Err(format!("expected \"cycles\", got \"{unit}\"")),
+        _ => unreachable!(),
     }
 }
 
-fn parse_load(args: &[&str]) -> Result<Command, String> {
-    Ok(Command::Load(PathBuf::from(args[0])))
+fn parse_load(args: &[&str], options: &Options) -> Result<Command, String> {
+    let top_module = options.get("top").map(|s| s.to_string());
+    Ok(Command::Load(PathBuf::from(args[0]), top_module))
 }
 
-fn parse_set(args: &[&str]) -> Result<Command, String> {
+fn parse_set(args: &[&str], _options: &Options) -> Result<Command, String> {
     match bitvec_str::parse(args[1]) {
         Ok(value) => Ok(Command::Set(args[0].to_string(), value)),
         Err(e) => Err(e),
     }
 }
 
-fn parse_set_periodic(args: &[&str]) -> Result<Command, String> {
+fn parse_set_periodic(args: &[&str], _options: &Options) -> Result<Command, String> {
     match (
-        args[1].parse::<usize>(),
+        parse_duration(args[1]),
         bitvec_str::parse(args[2]),
         bitvec_str::parse(args[3]),
     ) {
@@ -135,39 +519,698 @@ fn parse_set_periodic(args: &[&str]) -> Result<Command, String> {
             low_value,
             high_value,
         )),
-        (Err(e), _, _) => Err(e.to_string()),
+        (Err(e), _, _) => Err(e),
         (_, Err(e), _) => Err(e),
         (_, _, Err(e)) => Err(e),
     }
 }
 
-fn parse_quit(_args: &[&str]) -> Result<Command, String> {
+fn parse_set_clock(args: &[&str], options: &Options) -> Result<Command, String> {
+    let period = parse_frequency_or_period(args[1])?;
+    if period < 2 {
+        return Err(format!("period of {} is too short for a clock", args[1]));
+    }
+
+    let high_duration = match options.get("duty") {
+        Some(duty) => {
+            let duty = duty
+                .trim_end_matches('%')
+                .parse::<f64>()
+                .map_err(|e| format!("cannot parse duty cycle {duty}: {e}"))?;
+            (period as f64 * duty / 100.0).round() as usize
+        }
+        None => period / 2,
+    };
+    if high_duration == 0 || high_duration >= period {
+        return Err("duty cycle leaves no time for either the high or low state".to_string());
+    }
+
+    let mut clock_spec = ClockSpec::new(high_duration, period - high_duration);
+    if let Some(phase) = options.get("phase") {
+        clock_spec.phase = parse_duration(phase)?;
+    }
+    clock_spec.num_of_cycles = parse_count_option(options, "cycles")?;
+    if let Some(jitter) = options.get("jitter") {
+        clock_spec.jitter = parse_duration(jitter)?;
+    }
+
+    Ok(Command::SetClock(args[0].to_string(), clock_spec))
+}
+
+fn parse_set_sequence(args: &[&str], options: &Options) -> Result<Command, String> {
+    let mut steps = vec![];
+    for step in &args[1..] {
+        let (value, duration) = step
+            .split_once(':')
+            .ok_or_else(|| format!("expected <value:duration>, got {step}"))?;
+        steps.push((bitvec_str::parse(value)?, parse_duration(duration)?));
+    }
+    let repeat = parse_bool_option(options, "repeat")?.unwrap_or(false);
+    let stimulus_spec = StimulusSpec::Sequence { steps, repeat };
+    Ok(Command::SetStimulus(args[0].to_string(), stimulus_spec))
+}
+
+fn parse_set_ramp(args: &[&str], options: &Options) -> Result<Command, String> {
+    let increment = match options.get("increment") {
+        Some(increment) => increment
+            .parse::<i64>()
+            .map_err(|e| format!("cannot parse increment {increment}: {e}"))?,
+        None => 1,
+    };
+    let stimulus_spec = StimulusSpec::Ramp {
+        start: bitvec_str::parse(args[1])?,
+        increment,
+        step_duration: parse_duration(args[2])?,
+        num_of_steps: parse_count_option(options, "steps")?,
+    };
+    Ok(Command::SetStimulus(args[0].to_string(), stimulus_spec))
+}
+
+fn parse_set_random(args: &[&str], options: &Options) -> Result<Command, String> {
+    let seed = match options.get("seed") {
+        Some(seed) => seed
+            .parse::<u64>()
+            .map_err(|e| format!("cannot parse seed {seed}: {e}"))?,
+        None => 1,
+    };
+    let stimulus_spec = StimulusSpec::Random {
+        seed,
+        step_duration: parse_duration(args[1])?,
+        num_of_steps: parse_count_option(options, "steps")?,
+    };
+    Ok(Command::SetStimulus(args[0].to_string(), stimulus_spec))
+}
+
+fn parse_set_from_file(args: &[&str], options: &Options) -> Result<Command, String> {
+    let content =
+        fs::read_to_string(args[1]).map_err(|e| format!("cannot read {}: {e}", args[1]))?;
+    let steps = parse_csv_steps(&content)?;
+    let repeat = parse_bool_option(options, "repeat")?.unwrap_or(false);
+    let stimulus_spec = StimulusSpec::Sequence { steps, repeat };
+    Ok(Command::SetStimulus(args[0].to_string(), stimulus_spec))
+}
+
+fn parse_csv_steps(content: &str) -> Result<Vec<(BitVec<u32>, usize)>, String> {
+    let mut steps = vec![];
+    for (line_number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let columns: Vec<&str> = line.split(',').map(str::trim).collect();
+        match columns[..] {
+            [value, duration] => {
+                let step =
+                    bitvec_str::parse(value).and_then(|v| Ok((v, parse_duration(duration)?)));
+                match step {
+                    Ok(step) => steps.push(step),
+                    // Allow a header row
+                    Err(_) if steps.is_empty() && line_number == 0 => (),
+                    Err(e) => return Err(format!("line {}: {e}", line_number + 1)),
+                }
+            }
+            _ => {
+                return Err(format!(
+                    "line {}: expected 2 columns (value,duration)",
+                    line_number + 1
+                ))
+            }
+        }
+    }
+    if steps.is_empty() {
+        return Err("CSV file contains no steps".to_string());
+    }
+    Ok(steps)
+}
+
+fn parse_bool_option(options: &Options, key: &str) -> Result<Option<bool>, String> {
+    options
+        .get(key)
+        .map(|value| {
+            value
+                .parse::<bool>()
+                .map_err(|_| format!("expected true or false for {key}, got {value}"))
+        })
+        .transpose()
+}
+
+fn parse_count_option(options: &Options, key: &str) -> Result<Option<usize>, String> {
+    options
+        .get(key)
+        .map(|value| {
+            value
+                .parse::<usize>()
+                .map_err(|e| format!("cannot parse {key} {value}: {e}"))
+        })
+        .transpose()
+}
+
+fn parse_clock(args: &[&str], _options: &Options) -> Result<Command, String> {
+    Ok(Command::SetReferenceClock(args[0].to_string()))
+}
+
+fn parse_set_at_edge(args: &[&str], _options: &Options) -> Result<Command, String> {
+    let value = bitvec_str::parse(args[1])?;
+    let edge = match args[2] {
+        "rising" => Edge::Rising,
+        "falling" => Edge::Falling,
+        edge => return Err(format!("expected rising or falling, got {edge}")),
+    };
+    let skew = match args.get(3) {
+        Some(skew) => parse_duration(skew.strip_prefix('+').unwrap_or(skew))?,
+        None => 0,
+    };
+    Ok(Command::SetAtEdge(args[0].to_string(), value, edge, skew))
+}
+
+fn parse_profile(args: &[&str], options: &Options) -> Result<Command, String> {
+    let value_option = |key| -> Result<Option<String>, String> {
+        match options.get(key) {
+            Some(value) => {
+                bitvec_str::parse(value)?;
+                Ok(Some(value.to_string()))
+            }
+            None => Ok(None),
+        }
+    };
+    let period = match options.get("period") {
+        Some(period) => {
+            parse_duration(period)?;
+            Some(period.to_string())
+        }
+        None => None,
+    };
+    let profile = InitProfile {
+        clock: options.get("clock").map(|s| s.to_string()),
+        period,
+        reset: options.get("reset").map(|s| s.to_string()),
+        reset_active: value_option("active")?,
+        reset_cycles: parse_count_option(options, "cycles")?,
+        inputs: value_option("inputs")?,
+        defaults: Default::default(),
+    };
+    let auto = parse_bool_option(options, "auto")?;
+    Ok(Command::DefineProfile(args[0].to_string(), profile, auto))
+}
+
+fn parse_profile_default(args: &[&str], _options: &Options) -> Result<Command, String> {
+    bitvec_str::parse(args[2])?;
+    let profile = InitProfile {
+        defaults: [(args[1].to_string(), args[2].to_string())].into(),
+        ..Default::default()
+    };
+    Ok(Command::DefineProfile(args[0].to_string(), profile, None))
+}
+
+fn parse_init(args: &[&str], _options: &Options) -> Result<Command, String> {
+    Ok(Command::Init(args.first().map(|s| s.to_string())))
+}
+
+fn parse_save_session(args: &[&str], _options: &Options) -> Result<Command, String> {
+    Ok(Command::SaveSession(PathBuf::from(args[0])))
+}
+
+fn parse_open_session(args: &[&str], _options: &Options) -> Result<Command, String> {
+    Ok(Command::OpenSession(PathBuf::from(args[0])))
+}
+
+fn parse_marker(args: &[&str], _options: &Options) -> Result<Command, String> {
+    let time = args.get(1).map(|time| parse_duration(time)).transpose()?;
+    Ok(Command::AddMarker(args[0].to_string(), time))
+}
+
+fn parse_delete_marker(args: &[&str], _options: &Options) -> Result<Command, String> {
+    Ok(Command::DeleteMarker(args[0].to_string()))
+}
+
+fn parse_goto_marker(args: &[&str], _options: &Options) -> Result<Command, String> {
+    Ok(Command::GotoMarker(args[0].to_string()))
+}
+
+fn parse_find(args: &[&str], options: &Options) -> Result<Command, String> {
+    let target = match args {
+        ["change"] => FindTarget::Change,
+        ["rising"] => FindTarget::Edge(Edge::Rising),
+        ["falling"] => FindTarget::Edge(Edge::Falling),
+        ["value", value] => FindTarget::Value(bitvec_str::parse(value)?),
+        ["value"] => return Err("expected a value to find".to_string()),
+        [target, _] if *target != "value" => {
+            return Err(format!("unexpected value for \"{target}\""))
+        }
+        [target, ..] => {
+            return Err(format!(
+                "expected change, rising, falling or value, got {target}"
+            ))
+        }
+        _ => unreachable!(),
+    };
+    let signal_name = options.get("signal").map(|s| s.to_string());
+    let is_backward = parse_bool_option(options, "backward")?.unwrap_or(false);
+    Ok(Command::Find(target, signal_name, is_backward))
+}
+
+fn parse_search(args: &[&str], _options: &Options) -> Result<Command, String> {
+    let expression = args.join(" ");
+    expression::parse(&expression)?;
+    Ok(Command::Search(expression))
+}
+
+fn parse_derive(args: &[&str], _options: &Options) -> Result<Command, String> {
+    let expression = args[1..].join(" ");
+    expression::parse(&expression)?;
+    Ok(Command::Derive(args[0].to_string(), expression))
+}
+
+fn parse_remove_derived(args: &[&str], _options: &Options) -> Result<Command, String> {
+    Ok(Command::RemoveDerived(args[0].to_string()))
+}
+
+fn parse_expand(args: &[&str], _options: &Options) -> Result<Command, String> {
+    let bit_range = match args.get(1) {
+        Some(bit_range) => {
+            let (msb, lsb) = bit_range.split_once(':').unwrap_or((bit_range, bit_range));
+            let parse_bit = |bit: &str| {
+                bit.parse::<usize>()
+                    .map_err(|e| format!("cannot parse bit index {bit}: {e}"))
+            };
+            let (msb, lsb) = (parse_bit(msb)?, parse_bit(lsb)?);
+            if lsb > msb {
+                return Err(format!("bit range {bit_range} is reversed"));
+            }
+            Some((msb, lsb))
+        }
+        None => None,
+    };
+    Ok(Command::Expand(args[0].to_string(), bit_range))
+}
+
+fn parse_collapse(args: &[&str], _options: &Options) -> Result<Command, String> {
+    Ok(Command::Collapse(args[0].to_string()))
+}
+
+fn parse_group(args: &[&str], _options: &Options) -> Result<Command, String> {
+    Ok(Command::AddGroup(args[0].to_string()))
+}
+
+fn parse_ungroup(args: &[&str], _options: &Options) -> Result<Command, String> {
+    Ok(Command::RemoveGroup(args[0].to_string()))
+}
+
+fn parse_move(args: &[&str], _options: &Options) -> Result<Command, String> {
+    let is_up = match args[0] {
+        "up" => true,
+        "down" => false,
+        direction => return Err(format!("expected up or down, got {direction}")),
+    };
+    Ok(Command::Move(args.get(1).map(|s| s.to_string()), is_up))
+}
+
+fn parse_hide(args: &[&str], _options: &Options) -> Result<Command, String> {
+    Ok(Command::Hide(args.first().map(|s| s.to_string())))
+}
+
+fn parse_show(args: &[&str], _options: &Options) -> Result<Command, String> {
+    Ok(Command::Show(args.first().map(|s| s.to_string())))
+}
+
+fn parse_duplicate(args: &[&str], _options: &Options) -> Result<Command, String> {
+    Ok(Command::Duplicate(args.first().map(|s| s.to_string())))
+}
+
+fn parse_filter(args: &[&str], _options: &Options) -> Result<Command, String> {
+    Ok(Command::Filter(args.first().map(|s| s.to_string())))
+}
+
+fn parse_probe(args: &[&str], _options: &Options) -> Result<Command, String> {
+    let action = match args[0] {
+        "add" => ProbeAction::Add,
+        "remove" => ProbeAction::Remove,
+        "list" => ProbeAction::List,
+        action => return Err(format!("expected add, remove or list, got {action}")),
+    };
+    let pattern = match args.get(1) {
+        Some(pattern) => {
+            Pattern::new(pattern)?;
+            Some(pattern.to_string())
+        }
+        None if action != ProbeAction::List => {
+            return Err("expected a pattern of signals to probe".to_string())
+        }
+        None => None,
+    };
+    Ok(Command::Probe(action, pattern))
+}
+
+fn parse_log(args: &[&str], _options: &Options) -> Result<Command, String> {
+    let action = match (args[0], &args[1..]) {
+        ("filter", [] | ["all"]) => LogAction::Filter(vec![]),
+        ("filter", kinds) => {
+            let kinds = kinds
+                .iter()
+                .map(|kind| {
+                    OutputKind::from_name(kind).ok_or(format!(
+                        "expected one of {} or all, got {kind}",
+                        OutputKind::NAMES.join(", ")
+                    ))
+                })
+                .collect::<Result<_, String>>()?;
+            LogAction::Filter(kinds)
+        }
+        ("search", []) => LogAction::Search(None),
+        ("search", words) => LogAction::Search(Some(words.join(" "))),
+        ("group", [] | ["on"]) => LogAction::Group(true),
+        ("group", ["off"]) => LogAction::Group(false),
+        ("group", _) => return Err("expected on or off".to_string()),
+        ("save", [path]) => LogAction::Save(PathBuf::from(path)),
+        ("save", _) => return Err("expected a file path".to_string()),
+        (action, _) => {
+            return Err(format!(
+                "expected filter, search, group or save, got {action}"
+            ))
+        }
+    };
+    Ok(Command::Log(action))
+}
+
+fn parse_enum_map(args: &[&str], _options: &Options) -> Result<Command, String> {
+    let entries = args[1..]
+        .iter()
+        .map(|entry| match entry.split_once('=') {
+            Some((value, name)) if !name.is_empty() => {
+                Ok((bitvec_str::parse(value)?, name.to_string()))
+            }
+            _ => Err(format!("expected value=name, got {entry}")),
+        })
+        .collect::<Result<_, String>>()?;
+    Ok(Command::DefineEnumMap(args[0].to_string(), entries))
+}
+
+fn parse_decode(args: &[&str], _options: &Options) -> Result<Command, String> {
+    let (name, protocol, arguments) = match args {
+        [] => return Ok(Command::Decode(None)),
+        [_] => return Err("expected a protocol after the decoder name".to_string()),
+        [name, protocol, arguments @ ..] => (name, protocol, arguments),
+    };
+    if decoder::find(protocol).is_none() {
+        return Err(format!(
+            "unknown protocol \"{protocol}\", see the list given by decode"
+        ));
+    }
+    let arguments = arguments
+        .iter()
+        .map(|argument| match argument.split_once('=') {
+            Some((role, signal_name)) if !role.is_empty() => {
+                Ok((role.to_string(), signal_name.to_string()))
+            }
+            _ => Err(format!("expected role=signal, got {argument}")),
+        })
+        .collect::<Result<_, String>>()?;
+    Ok(Command::Decode(Some((
+        name.to_string(),
+        protocol.to_string(),
+        arguments,
+    ))))
+}
+
+fn parse_remove_decoder(args: &[&str], _options: &Options) -> Result<Command, String> {
+    Ok(Command::RemoveDecoder(args[0].to_string()))
+}
+
+fn parse_transactions(args: &[&str], _options: &Options) -> Result<Command, String> {
+    Ok(Command::ShowTransactions(
+        args.first().map(|s| s.to_string()),
+    ))
+}
+
+fn parse_color(args: &[&str], _options: &Options) -> Result<Command, String> {
+    let color = match args[0] {
+        "none" => None,
+        color => Some(theme::parse_color(color)?),
+    };
+    Ok(Command::SetColor(color, args.get(1).map(|s| s.to_string())))
+}
+
+fn parse_theme(args: &[&str], _options: &Options) -> Result<Command, String> {
+    Ok(Command::Theme(args.first().map(|s| s.to_string())))
+}
+
+fn parse_quit(_args: &[&str], _options: &Options) -> Result<Command, String> {
     Ok(Command::Quit)
 }
 
-fn parse_help(_args: &[&str]) -> Result<Command, String> {
+fn parse_help(_args: &[&str], _options: &Options) -> Result<Command, String> {
     Ok(Command::Help)
 }
 
-fn check_usage(command_info: &CommandInfo, args: &[&str]) -> Result<(), String> {
-    if args.len() != command_info.args.len() {
+pub fn parse_duration(text: &str) -> Result<usize, String> {
+    let (number, scale) = split_unit(text, &TIME_UNITS);
+    let duration =
+        parse_scaled(number, scale).ok_or_else(|| format!("cannot parse {text} as a duration"))?;
+    if duration.fract() != 0.0 {
+        return Err(format!("{text} is not a whole number of time steps"));
+    }
+    Ok(duration as usize)
+}
+
+fn parse_frequency_or_period(text: &str) -> Result<usize, String> {
+    match split_unit(text, &FREQUENCY_UNITS) {
+        (number, Some(scale)) => {
+            let frequency = parse_scaled(number, Some(scale))
+                .filter(|f| *f > 0.0)
+                .ok_or_else(|| format!("cannot parse {text} as a frequency"))?;
+            Ok((1e12 / frequency).round() as usize)
+        }
+        (_, None) => parse_duration(text),
+    }
+}
+
+fn split_unit<'a>(text: &'a str, units: &[(&str, f64)]) -> (&'a str, Option<f64>) {
+    for (unit, scale) in units {
+        if let Some(number) = text.strip_suffix(unit) {
+            return (number, Some(*scale));
+        }
+    }
+    (text, None)
+}
+
+fn parse_scaled(number: &str, scale: Option<f64>) -> Option<f64> {
+    match scale {
+        Some(scale) => number
+            .parse::<f64>()
+            .ok()
+            .filter(|n| n.is_finite() && *n >= 0.0)
+            .map(|n| n * scale),
+        None => number.parse::<usize>().ok().map(|n| n as f64),
+    }
+}
+
+fn split_options<'a>(words: &[&'a str]) -> (Vec<&'a str>, Options<'a>) {
+    let mut args = vec![];
+    let mut options = HashMap::new();
+    for word in words {
+        match word.split_once('=') {
+            Some((key, value)) => {
+                options.insert(key, value);
+            }
+            None => args.push(*word),
+        }
+    }
+    (args, options)
+}
+
+fn check_usage(command_info: &CommandInfo, args: &[&str], options: &Options) -> Result<(), String> {
+    let (min_num_of_args, max_num_of_args) = command_info.arity();
+    if args.len() < min_num_of_args || args.len() > max_num_of_args {
+        let expected = if min_num_of_args == max_num_of_args {
+            format!("{min_num_of_args}")
+        } else if max_num_of_args == usize::MAX {
+            format!("at least {min_num_of_args}")
+        } else {
+            format!("{min_num_of_args} to {max_num_of_args}")
+        };
         return Err(format!(
-            "expected {} arguments (usage: {})",
-            command_info.args.len(),
+            "expected {expected} arguments (usage: {})",
             command_info.usage()
         ));
     }
+    for key in options.keys() {
+        if !command_info.options.iter().any(|(name, _)| name == key) {
+            return Err(format!(
+                "unknown option \"{key}\" (usage: {})",
+                command_info.usage()
+            ));
+        }
+    }
     Ok(())
 }
 
 impl CommandInfo {
+    fn arity(&self) -> (usize, usize) {
+        let min_num_of_args = self.args.iter().filter(|arg| !arg.starts_with('[')).count();
+        let max_num_of_args = if self.args.last().is_some_and(|arg| arg.ends_with("...")) {
+            usize::MAX
+        } else {
+            self.args.len()
+        };
+        (min_num_of_args, max_num_of_args)
+    }
+
     fn usage(&self) -> String {
         let mut usage = self.name.to_string();
         for arg in self.args.iter() {
-            usage += " <";
-            usage += arg;
-            usage += ">";
+            if arg.starts_with('[') {
+                usage += " ";
+                usage += arg;
+            } else {
+                usage += " <";
+                usage += arg;
+                usage += ">";
+            }
+        }
+        for (name, description) in self.options.iter() {
+            usage += &format!(" [{name}=<{description}>]");
         }
         usage
     }
 }
+
+#[cfg(test)]
+mod test {
+    use bitvec::{bitvec, order::Lsb0};
+    use oombak_sim::Edge;
+
+    use super::{
+        interpret, parse_csv_steps, parse_duration, parse_frequency_or_period, Command, LogAction,
+        ProbeAction,
+    };
+    use crate::widgets::OutputKind;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("15"), Ok(15));
+        assert_eq!(parse_duration("15ps"), Ok(15));
+        assert_eq!(parse_duration("2.5ns"), Ok(2500));
+        assert_eq!(parse_duration("1us"), Ok(1_000_000));
+        assert!(parse_duration("0.5ps").is_err());
+        assert!(parse_duration("1.5").is_err());
+        assert!(parse_duration("ns").is_err());
+    }
+
+    #[test]
+    fn test_parse_frequency_or_period() {
+        assert_eq!(parse_frequency_or_period("100MHz"), Ok(10_000));
+        assert_eq!(parse_frequency_or_period("1GHz"), Ok(1_000));
+        assert_eq!(parse_frequency_or_period("10ns"), Ok(10_000));
+        assert_eq!(parse_frequency_or_period("20"), Ok(20));
+        assert!(parse_frequency_or_period("0Hz").is_err());
+    }
+
+    #[test]
+    fn test_parse_csv_steps() {
+        let steps = parse_csv_steps("value,duration\n0x1, 10\n\n# comment\n0b11,2ns\n").unwrap();
+        let durations: Vec<usize> = steps.iter().map(|(_, d)| *d).collect();
+        assert_eq!(durations, vec![10, 2000]);
+        assert!(parse_csv_steps("0x1,10\n0x2\n").is_err());
+        assert!(parse_csv_steps("value,duration\n").is_err());
+    }
+
+    #[test]
+    fn test_interpret_cycle_commands() {
+        for (text, num_of_cycles) in [("run 4 cycles", 4), ("run 0 cycles", 0)] {
+            assert!(
+                matches!(interpret(text), Ok(Command::RunCycles(n)) if n == num_of_cycles),
+                "{text}"
+            );
+        }
+        for (text, skew) in [
+            ("set-at-edge data 0b101 falling", 0),
+            ("set-at-edge data 0b101 falling +2ns", 2000),
+            ("set-at-edge data 0b101 falling 7", 7),
+        ] {
+            assert!(
+                matches!(
+                    interpret(text),
+                    Ok(Command::SetAtEdge(name, value, Edge::Falling, s))
+                        if name == "data" && value == bitvec![u32, Lsb0; 1, 0, 1] && s == skew
+                ),
+                "{text}"
+            );
+        }
+        for text in [
+            "run 4 cycle",
+            "run -1 cycles",
+            "run 4 5",
+            "run 10fs",
+            "run 2.5",
+            "clock",
+            "set-at-edge data 1",
+            "set-at-edge data 1 both",
+            "set-at-edge data 1 rising +2fs",
+            "set-at-edge data 0xg rising",
+        ] {
+            assert!(interpret(text).is_err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_interpret_search() {
+        match interpret("search top.state == 3 && top.valid") {
+            Ok(Command::Search(expression)) => {
+                assert_eq!(expression, "top.state == 3 && top.valid")
+            }
+            _ => panic!("expected a search command"),
+        }
+        assert!(interpret("search top.state ==").is_err());
+        assert!(interpret("set-ramp data 0 10 step=2").is_err());
+    }
+
+    #[test]
+    fn test_interpret_probe() {
+        match interpret("probe add *.fifo*.count") {
+            Ok(Command::Probe(ProbeAction::Add, Some(pattern))) => {
+                assert_eq!(pattern, "*.fifo*.count")
+            }
+            _ => panic!("expected a probe add command"),
+        }
+        assert!(matches!(
+            interpret("probe list"),
+            Ok(Command::Probe(ProbeAction::List, None))
+        ));
+        assert!(interpret("probe remove").is_err());
+        assert!(interpret("probe add /fifo(/").is_err());
+        assert!(interpret("probe clear").is_err());
+    }
+
+    #[test]
+    fn test_interpret_decode() {
+        match interpret("decode uart0 uart line=top.tx baud=115200") {
+            Ok(Command::Decode(Some((name, protocol, arguments)))) => {
+                assert_eq!((&name[..], &protocol[..]), ("uart0", "uart"));
+                assert_eq!(arguments["line"], "top.tx");
+                assert_eq!(arguments["baud"], "115200");
+            }
+            _ => panic!("expected a decode command"),
+        }
+        assert!(matches!(interpret("decode"), Ok(Command::Decode(None))));
+        assert!(interpret("decode bus").is_err());
+        assert!(interpret("decode bus pcie").is_err());
+        assert!(interpret("decode bus apb top.psel").is_err());
+    }
+
+    #[test]
+    fn test_interpret_log() {
+        match interpret("log filter error notification") {
+            Ok(Command::Log(LogAction::Filter(kinds))) => {
+                assert_eq!(kinds, [OutputKind::Error, OutputKind::Notification])
+            }
+            _ => panic!("expected a log filter command"),
+        }
+        match interpret("log search Running  CMake") {
+            Ok(Command::Log(LogAction::Search(Some(text)))) => assert_eq!(text, "Running CMake"),
+            _ => panic!("expected a log search command"),
+        }
+        assert!(interpret("log filter warning").is_err());
+        assert!(interpret("log group maybe").is_err());
+        assert!(interpret("log save").is_err());
+    }
+}
@@ -0,0 +1,253 @@
+use std::{collections::HashMap, fs, sync::Mutex};
+
+use serde::Deserialize;
+
+use crate::{
+    utils::config,
+    widgets::{KeyDesc, KeyId, KeyMaps},
+};
+
+/// An action that can be bound to keys in `keys.toml`.
+pub struct ActionInfo<A> {
+    pub action: A,
+    /// Name of the action in `keys.toml`
+    pub name: &'static str,
+    pub description: &'static str,
+    pub default_keys: &'static [&'static str],
+}
+
+/// Errors met while loading the bindings of any section, reported once the terminal is shown.
+static LOAD_ERRORS: Mutex<Vec<String>> = Mutex::new(vec![]);
+
+pub enum KeyPress<A> {
+    Action(A),
+    /// The key starts a chord and the next keys are awaited
+    Pending,
+    Unbound,
+}
+
+/// Dispatches key presses to actions, and describes the bindings in the key maps shown in the
+/// help bar.
+#[derive(Clone)]
+pub struct KeyBindings<A: 'static> {
+    actions: &'static [ActionInfo<A>],
+    bindings: Vec<(Vec<KeyId>, usize)>,
+    pending_keys: Vec<KeyId>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Keys {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl<A: Copy> KeyBindings<A> {
+    pub fn with_defaults(actions: &'static [ActionInfo<A>]) -> Self {
+        Self::new(actions, HashMap::new()).expect("logic error: default key bindings are invalid")
+    }
+
+    /// Binds the actions to their default keys, except those bound in the `section` table of
+    /// `keys.toml`.
+    pub fn load(actions: &'static [ActionInfo<A>], section: &str) -> Result<Self, String> {
+        let path = match config::config_dir().map(|dir| dir.join("keys.toml")) {
+            Some(path) if path.is_file() => path,
+            _ => return Ok(Self::with_defaults(actions)),
+        };
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("cannot read {}: {e}", path.to_string_lossy()))?;
+        let mut sections: HashMap<String, HashMap<String, Keys>> = toml::from_str(&content)
+            .map_err(|e| format!("invalid {}: {e}", path.to_string_lossy()))?;
+        let keys = sections.remove(section).unwrap_or_default();
+        Self::new(actions, keys)
+            .map_err(|e| format!("invalid {}: [{section}] {e}", path.to_string_lossy()))
+    }
+
+    /// Loads the bindings of `section`, or, if `keys.toml` is invalid, binds the default keys
+    /// and keeps the error for `take_load_errors`.
+    pub fn load_or_defaults(actions: &'static [ActionInfo<A>], section: &str) -> Self {
+        Self::load(actions, section).unwrap_or_else(|message| {
+            let mut load_errors = LOAD_ERRORS.lock().unwrap();
+            if !load_errors.contains(&message) {
+                load_errors.push(message);
+            }
+            Self::with_defaults(actions)
+        })
+    }
+
+    fn new(
+        actions: &'static [ActionInfo<A>],
+        mut keys: HashMap<String, Keys>,
+    ) -> Result<Self, String> {
+        if let Some(name) = keys
+            .keys()
+            .find(|name| !actions.iter().any(|a| a.name == *name))
+        {
+            return Err(format!("unknown action \"{name}\""));
+        }
+
+        let mut bindings = vec![];
+        for (idx, action_info) in actions.iter().enumerate() {
+            let chords = match keys.remove(action_info.name) {
+                Some(Keys::One(chord)) => vec![chord],
+                Some(Keys::Many(chords)) => chords,
+                None => continue,
+            };
+            for chord in chords {
+                bindings.push((KeyId::parse_chord(&chord)?, idx));
+            }
+        }
+        // Default keys give way to the configured ones
+        let num_of_configured_bindings = bindings.len();
+        for (idx, action_info) in actions.iter().enumerate() {
+            if bindings[..num_of_configured_bindings]
+                .iter()
+                .any(|(_, i)| *i == idx)
+            {
+                continue;
+            }
+            for chord in action_info.default_keys {
+                let chord = KeyId::parse_chord(chord)?;
+                let is_taken = bindings[..num_of_configured_bindings]
+                    .iter()
+                    .any(|(keys, _)| keys.starts_with(&chord) || chord.starts_with(keys));
+                if !is_taken {
+                    bindings.push((chord, idx));
+                }
+            }
+        }
+
+        for (i, (keys, idx)) in bindings.iter().enumerate() {
+            let conflict = bindings[i + 1..].iter().find(|(other_keys, _)| {
+                other_keys.starts_with(keys) || keys.starts_with(other_keys)
+            });
+            if let Some((other_keys, other_idx)) = conflict {
+                let chord = KeyId::from_chord(keys).unwrap();
+                let other_chord = KeyId::from_chord(other_keys).unwrap();
+                return Err(format!(
+                    "\"{chord}\" of {} conflicts with \"{other_chord}\" of {}",
+                    actions[*idx].name, actions[*other_idx].name
+                ));
+            }
+        }
+
+        Ok(Self {
+            actions,
+            bindings,
+            pending_keys: vec![],
+        })
+    }
+
+    pub fn press(&mut self, key_id: KeyId) -> KeyPress<A> {
+        self.pending_keys.push(key_id.clone());
+        let mut is_chord_started = false;
+        for (keys, idx) in self.bindings.iter() {
+            if *keys == self.pending_keys {
+                self.pending_keys.clear();
+                return KeyPress::Action(self.actions[*idx].action);
+            }
+            is_chord_started |= keys.starts_with(&self.pending_keys);
+        }
+        if is_chord_started {
+            return KeyPress::Pending;
+        }
+
+        // A key that breaks a chord starts over
+        let is_chord_broken = self.pending_keys.len() > 1;
+        self.pending_keys.clear();
+        if is_chord_broken {
+            self.press(key_id)
+        } else {
+            KeyPress::Unbound
+        }
+    }
+
+    pub fn key_maps(&self) -> KeyMaps {
+        let mut key_maps = KeyMaps::from(HashMap::new());
+        for (keys, idx) in self.bindings.iter() {
+            if let Some(key_id) = KeyId::from_chord(keys) {
+                key_maps.insert(key_id, KeyDesc::from(self.actions[*idx].description));
+            }
+        }
+        key_maps
+    }
+}
+
+pub fn take_load_errors() -> Vec<String> {
+    std::mem::take(&mut LOAD_ERRORS.lock().unwrap())
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use crate::widgets::KeyId;
+
+    use super::{ActionInfo, KeyBindings, KeyPress, Keys};
+
+    const ACTIONS: [ActionInfo<usize>; 3] = [
+        ActionInfo {
+            action: 0,
+            name: "scroll-up",
+            description: "scroll up",
+            default_keys: &["k", "up"],
+        },
+        ActionInfo {
+            action: 1,
+            name: "scroll-down",
+            description: "scroll down",
+            default_keys: &["j"],
+        },
+        ActionInfo {
+            action: 2,
+            name: "go-to-start",
+            description: "go to start",
+            default_keys: &["ctrl-a"],
+        },
+    ];
+
+    fn press(key_bindings: &mut KeyBindings<usize>, key: &str) -> Option<usize> {
+        match key_bindings.press(key.parse().unwrap()) {
+            KeyPress::Action(action) => Some(action),
+            KeyPress::Pending => None,
+            KeyPress::Unbound => Some(usize::MAX),
+        }
+    }
+
+    #[test]
+    fn test_press() {
+        let keys = HashMap::from([
+            ("scroll-down".to_string(), Keys::One("k".to_string())),
+            (
+                "go-to-start".to_string(),
+                Keys::Many(vec!["g g".to_string(), "<Home>".to_string()]),
+            ),
+        ]);
+        let mut key_bindings = KeyBindings::new(&ACTIONS, keys).unwrap();
+
+        assert_eq!(press(&mut key_bindings, "k"), Some(1));
+        assert_eq!(press(&mut key_bindings, "<up>"), Some(0));
+        assert_eq!(press(&mut key_bindings, "j"), Some(usize::MAX));
+        assert_eq!(press(&mut key_bindings, "ctrl-a"), Some(usize::MAX));
+        assert_eq!(press(&mut key_bindings, "home"), Some(2));
+        assert_eq!(press(&mut key_bindings, "g"), None);
+        assert_eq!(press(&mut key_bindings, "g"), Some(2));
+        assert_eq!(press(&mut key_bindings, "g"), None);
+        assert_eq!(press(&mut key_bindings, "k"), Some(1));
+
+        let key_maps = key_bindings.key_maps();
+        let chord = KeyId::from_chord(&KeyId::parse_chord("g g").unwrap()).unwrap();
+        assert_eq!(chord.to_string(), "g g");
+        assert!(key_maps.into_iter().any(|(key_id, _)| *key_id == chord));
+
+        let keys = HashMap::from([("go-to-start".to_string(), Keys::One("k k".to_string()))]);
+        assert!(KeyBindings::new(&ACTIONS, keys).is_ok());
+        let keys = HashMap::from([
+            ("scroll-down".to_string(), Keys::One("u".to_string())),
+            ("go-to-start".to_string(), Keys::One("u u".to_string())),
+        ]);
+        assert!(KeyBindings::new(&ACTIONS, keys).is_err());
+        let keys = HashMap::from([("jump".to_string(), Keys::One("u".to_string()))]);
+        assert!(KeyBindings::new(&ACTIONS, keys).is_err());
+    }
+}
@@ -1 +1,8 @@
+pub mod completion;
+pub mod decoder;
+pub mod expression;
+pub mod history;
+pub mod init_profile;
 pub mod interpreter;
+pub mod key_bindings;
+pub mod session;
@@ -0,0 +1,299 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use oombak_sim::ProbePointsModification;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::models::{DecoderSpec, DerivedSignal, Marker, PlotType, SimulationSpec, WaveSpec},
+    plugin,
+    styles::theme,
+    utils::bitvec_str::{self, EnumMap},
+};
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct Session {
+    pub design_path: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_module: Option<String>,
+    #[serde(default)]
+    pub probed_points: Vec<String>,
+    #[serde(default)]
+    pub zoom: u8,
+    #[serde(default)]
+    pub cursor: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference_marker: Option<String>,
+    #[serde(default)]
+    pub history: Vec<String>,
+    #[serde(default)]
+    pub markers: Vec<Marker>,
+    #[serde(default)]
+    pub derived_signals: Vec<DerivedSignal>,
+    /// Value-to-name maps, keyed by map name and then by value.
+    #[serde(default)]
+    pub enum_maps: BTreeMap<String, BTreeMap<String, String>>,
+    #[serde(default)]
+    pub decoders: Vec<DecoderSpec>,
+    #[serde(default)]
+    pub waves: Vec<WaveLayout>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WaveLayout {
+    pub signal_name: String,
+    pub height: u16,
+    pub plot_type: PlotType,
+    pub radix: bitvec_str::Radix,
+    pub signed: bool,
+    #[serde(default)]
+    pub fraction_bits: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enum_map: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub analog_min: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub analog_max: Option<f64>,
+    #[serde(default)]
+    pub interpolate: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Name of the plugin formatter used with the Custom radix
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub formatter: Option<String>,
+    #[serde(default)]
+    pub hidden: bool,
+    /// Marks a group header, whose name is stored in `signal_name`.
+    #[serde(default)]
+    pub group: bool,
+    #[serde(default)]
+    pub collapsed: bool,
+}
+
+impl WaveLayout {
+    fn new(wave_spec: &WaveSpec, hidden: bool) -> Self {
+        let collapsed = wave_spec
+            .group
+            .as_ref()
+            .is_some_and(|group| !group.collapsed_rows.is_empty());
+        Self {
+            signal_name: wave_spec.wave.signal_name.clone(),
+            height: wave_spec.height,
+            plot_type: wave_spec.plot_type,
+            radix: wave_spec.radix,
+            signed: wave_spec.signed,
+            fraction_bits: wave_spec.fraction_bits,
+            enum_map: wave_spec.enum_map.as_ref().map(|m| m.name.clone()),
+            analog_min: wave_spec.analog_min,
+            analog_max: wave_spec.analog_max,
+            interpolate: wave_spec.interpolate,
+            color: wave_spec.color.map(|color| color.to_string()),
+            formatter: wave_spec
+                .formatter
+                .as_ref()
+                .map(|formatter| formatter.name().to_string()),
+            hidden,
+            group: wave_spec.group.is_some(),
+            collapsed,
+        }
+    }
+}
+
+impl Session {
+    pub fn new(
+        design_path: &Path,
+        top_module: &str,
+        probed_points: &[String],
+        simulation_spec: &SimulationSpec,
+        cursor: usize,
+        history: &[String],
+    ) -> Self {
+        let mut waves = vec![];
+        for wave_spec in simulation_spec.wave_specs.iter() {
+            waves.push(WaveLayout::new(wave_spec, false));
+            if let Some(group) = &wave_spec.group {
+                waves.extend(
+                    group
+                        .collapsed_rows
+                        .iter()
+                        .map(|ws| WaveLayout::new(ws, false)),
+                );
+            }
+        }
+        waves.extend(
+            simulation_spec
+                .hidden_wave_specs
+                .iter()
+                .map(|ws| WaveLayout::new(ws, true)),
+        );
+        Self {
+            design_path: design_path.to_path_buf(),
+            top_module: Some(top_module.to_string()),
+            probed_points: probed_points.to_vec(),
+            zoom: simulation_spec.zoom,
+            cursor,
+            markers: simulation_spec.markers.clone(),
+            reference_marker: simulation_spec.reference_marker.clone(),
+            history: history.to_vec(),
+            derived_signals: simulation_spec.derived_signals.clone(),
+            enum_maps: simulation_spec
+                .enum_maps
+                .iter()
+                .map(|m| (m.name.clone(), Self::enum_map_entries(m)))
+                .collect(),
+            decoders: simulation_spec.decoders.clone(),
+            waves,
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        toml::from_str(&content)
+            .map_err(|e| format!("{} is not an oombak session file: {e}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let content = toml::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, content).map_err(|e| format!("cannot write {}: {e}", path.display()))
+    }
+
+    pub fn probe_points_modification(&self, probed_points: &[String]) -> ProbePointsModification {
+        let current: HashSet<&String> = probed_points.iter().collect();
+        let wanted: HashSet<&String> = self.probed_points.iter().collect();
+        ProbePointsModification {
+            to_add: wanted.difference(&current).map(|s| s.to_string()).collect(),
+            to_remove: current.difference(&wanted).map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn enum_map_entries(enum_map: &EnumMap) -> BTreeMap<String, String> {
+        enum_map
+            .entries()
+            .iter()
+            .map(|(value, name)| {
+                let option = bitvec_str::Option {
+                    radix: bitvec_str::Radix::Hexadecimal,
+                    width: value.last_one().map_or(1, |idx| idx + 1),
+                    ..Default::default()
+                };
+                (
+                    format!("0x{}", bitvec_str::from(value, &option)),
+                    name.clone(),
+                )
+            })
+            .collect()
+    }
+
+    pub fn apply_to(&self, simulation_spec: &mut SimulationSpec) {
+        for (name, entries) in self.enum_maps.iter() {
+            let entries = entries
+                .iter()
+                .filter_map(|(value, name)| Some((bitvec_str::parse(value).ok()?, name.clone())))
+                .collect();
+            simulation_spec.define_enum_map(EnumMap::new(name, entries));
+        }
+        simulation_spec.derived_signals = self.derived_signals.clone();
+        simulation_spec.update_derived_waves();
+        simulation_spec.decoders = self.decoders.clone();
+        simulation_spec.update_decoded_waves();
+        let mut wave_specs = vec![];
+        let mut hidden_wave_specs = vec![];
+        for layout in self.waves.iter() {
+            if layout.group {
+                wave_specs.push(WaveSpec::group(&layout.signal_name));
+            } else if let Some(wave_spec) = simulation_spec.take_wave_spec(&layout.signal_name) {
+                let wave_spec = WaveSpec {
+                    height: layout.height,
+                    plot_type: layout.plot_type,
+                    radix: layout.radix,
+                    signed: layout.signed,
+                    fraction_bits: layout.fraction_bits,
+                    // The map of a decoder row is not among the defined ones, but made with it
+                    enum_map: layout.enum_map.as_deref().and_then(|name| {
+                        simulation_spec
+                            .get_enum_map(name)
+                            .or(wave_spec.enum_map.as_ref())
+                            .cloned()
+                    }),
+                    analog_min: layout.analog_min,
+                    analog_max: layout.analog_max,
+                    interpolate: layout.interpolate,
+                    color: layout
+                        .color
+                        .as_deref()
+                        .and_then(|color| theme::parse_color(color).ok()),
+                    // Unless the plugin registering it is no longer loaded
+                    formatter: layout.formatter.as_deref().and_then(plugin::find_formatter),
+                    ..wave_spec
+                };
+                if layout.hidden {
+                    hidden_wave_specs.push(wave_spec);
+                } else {
+                    wave_specs.push(wave_spec);
+                }
+            }
+        }
+        wave_specs.append(&mut simulation_spec.wave_specs);
+        simulation_spec.wave_specs = wave_specs;
+        simulation_spec.hidden_wave_specs = hidden_wave_specs;
+        for layout in self.waves.iter().filter(|layout| layout.collapsed) {
+            simulation_spec.toggle_group(&layout.signal_name);
+        }
+        simulation_spec.zoom = self.zoom;
+        simulation_spec.markers = self.markers.clone();
+        simulation_spec.reference_marker = self.reference_marker.clone();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use super::Session;
+
+    #[test]
+    fn test_session_round_trip() {
+        let content = r#"
+            design_path = "sv/adder.sv"
+            probed_points = ["adder.a", "adder.c"]
+            zoom = 3
+            cursor = 12
+            reference_marker = "start"
+            history = ["run 10"]
+
+            [[derived_signals]]
+            name = "sum"
+            expression = "adder.a + adder.b"
+
+            [[markers]]
+            name = "start"
+            time = 4
+
+            [[waves]]
+            signal_name = "adder.c"
+            height = 2
+            plot_type = "analog"
+            radix = "hexadecimal"
+            signed = false
+        "#;
+        let session: Session = toml::from_str(content).unwrap();
+        assert_eq!(session.design_path, Path::new("sv/adder.sv"));
+        assert_eq!(session.waves[0].height, 2);
+
+        let modification =
+            session.probe_points_modification(&["adder.a".to_string(), "adder.b".to_string()]);
+        assert_eq!(modification.to_add, vec!["adder.c".to_string()]);
+        assert_eq!(modification.to_remove, vec!["adder.b".to_string()]);
+
+        let saved: Session = toml::from_str(&toml::to_string_pretty(&session).unwrap()).unwrap();
+        assert_eq!(saved.cursor, 12);
+        assert_eq!(saved.history, session.history);
+        assert_eq!(saved.markers[0].time, 4);
+        assert_eq!(saved.reference_marker.as_deref(), Some("start"));
+        assert_eq!(saved.derived_signals[0].expression, "adder.a + adder.b");
+    }
+}
@@ -0,0 +1,156 @@
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Usage: oombak_tui [OPTIONS] [SESSION FILE]
+
+Options:
+  -l, --load <FILE>       load a top-level SystemVerilog file, or a filelist
+  -f, --filelist <FILE>   load the sources listed in a filelist
+  -t, --top <MODULE>      top-level module of the loaded design (default: the file name)
+  -s, --session <FILE>    open a session saved with save-session
+  -x, --script <FILE>     run the commands in the file
+      --theme <NAME>      color theme
+      --log <FILE>        append the terminal output to the file
+      --plugin <FILE>     load a plugin library (can be given more than once)
+  -c, --config <FILE>     configuration file (default: ~/.config/oombak/config.toml)
+  -h, --help              print this help
+  -V, --version           print the version";
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Args {
+    pub design_path: Option<PathBuf>,
+    pub top_module: Option<String>,
+    pub session_path: Option<PathBuf>,
+    pub script_path: Option<PathBuf>,
+    pub theme: Option<String>,
+    pub log_path: Option<PathBuf>,
+    pub plugin_paths: Vec<PathBuf>,
+    pub config_path: Option<PathBuf>,
+    pub is_help: bool,
+    pub is_version: bool,
+}
+
+impl Args {
+    /// Parses the arguments, without the program name. Options take their value either as the
+    /// next argument or after `=`, e.g. `--top=fifo`.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Self::default();
+        let mut filelist_path = None;
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (option, inline_value) = match arg.split_once('=') {
+                Some((option, value)) if option.starts_with("--") => {
+                    (option.to_string(), Some(value.to_string()))
+                }
+                _ => (arg.clone(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or(format!("{option} expects a value"))
+            };
+            match &option[..] {
+                "-l" | "--load" => set_once(&mut parsed.design_path, &option, value()?)?,
+                "-f" | "--filelist" => set_once(&mut filelist_path, &option, value()?)?,
+                "-t" | "--top" => set_once(&mut parsed.top_module, &option, value()?)?,
+                "-s" | "--session" => set_once(&mut parsed.session_path, &option, value()?)?,
+                "-x" | "--script" => set_once(&mut parsed.script_path, &option, value()?)?,
+                "--theme" => set_once(&mut parsed.theme, &option, value()?)?,
+                "--log" => set_once(&mut parsed.log_path, &option, value()?)?,
+                "--plugin" => parsed.plugin_paths.push(value()?.into()),
+                "-c" | "--config" => set_once(&mut parsed.config_path, &option, value()?)?,
+                "-h" | "--help" => parsed.is_help = true,
+                "-V" | "--version" => parsed.is_version = true,
+                _ if option.starts_with('-') && option != "-" => {
+                    return Err(format!("unknown option {option}"))
+                }
+                _ => set_once(&mut parsed.session_path, "session file", arg)?,
+            }
+        }
+        parsed.check_filelist(filelist_path)?;
+        parsed.check_conflicts()?;
+        Ok(parsed)
+    }
+
+    fn check_filelist(&mut self, filelist_path: Option<PathBuf>) -> Result<(), String> {
+        let Some(filelist_path) = filelist_path else {
+            return Ok(());
+        };
+        if self.design_path.is_some() {
+            return Err("--load and --filelist cannot be used together".to_string());
+        }
+        // The builder reads .f and .vf files as filelists
+        if !filelist_path
+            .extension()
+            .is_some_and(|ext| ext == "f" || ext == "vf")
+        {
+            return Err(format!(
+                "{} is not a filelist (.f or .vf)",
+                filelist_path.display()
+            ));
+        }
+        self.design_path = Some(filelist_path);
+        Ok(())
+    }
+
+    fn check_conflicts(&self) -> Result<(), String> {
+        if self.session_path.is_some() && self.design_path.is_some() {
+            return Err("a session loads its own design, which cannot be given too".to_string());
+        }
+        if self.top_module.is_some() && self.design_path.is_none() {
+            return Err("--top needs a design to --load".to_string());
+        }
+        Ok(())
+    }
+}
+
+fn set_once<T>(field: &mut Option<T>, name: &str, value: impl Into<T>) -> Result<(), String> {
+    if field.is_some() {
+        return Err(format!("{name} is given more than once"));
+    }
+    *field = Some(value.into());
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::Args;
+
+    fn parse(args: &str) -> Result<Args, String> {
+        Args::parse(args.split_whitespace().map(str::to_string))
+    }
+
+    #[test]
+    fn test_parse() {
+        let args =
+            parse("-f rtl/fifo.f --top=fifo_top --script init.oombak --log out.log").unwrap();
+        assert_eq!(args.design_path, Some(PathBuf::from("rtl/fifo.f")));
+        assert_eq!(args.top_module.as_deref(), Some("fifo_top"));
+        assert_eq!(args.script_path, Some(PathBuf::from("init.oombak")));
+        assert_eq!(args.log_path, Some(PathBuf::from("out.log")));
+        assert_eq!(args.session_path, None);
+
+        let args = parse("--plugin libgray.so --plugin=libbus.so fifo.session").unwrap();
+        assert_eq!(
+            args.plugin_paths,
+            [PathBuf::from("libgray.so"), PathBuf::from("libbus.so")]
+        );
+
+        let args = parse("--theme light fifo.session").unwrap();
+        assert_eq!(args.session_path, Some(PathBuf::from("fifo.session")));
+        assert_eq!(args.theme.as_deref(), Some("light"));
+        assert!(parse("-h").unwrap().is_help);
+
+        assert!(parse("--load").is_err());
+        assert!(parse("--jobs 4").is_err());
+        assert!(parse("-l fifo.sv -f fifo.f").is_err());
+        assert!(parse("-f fifo.sv").is_err());
+        assert!(parse("-f fifo.txt").is_err());
+        assert!(parse("-l fifo.sv fifo.session").is_err());
+        assert!(parse("--top fifo").is_err());
+        assert!(parse("a.session b.session").is_err());
+    }
+}
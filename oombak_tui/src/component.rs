@@ -1,6 +1,6 @@
 use std::sync::{Arc, RwLock};
 
-use crossterm::event::{Event, KeyEvent};
+use crossterm::event::{Event, KeyEvent, MouseEvent};
 use ratatui::{
     layout::{Constraint, Layout, Rect},
     widgets::Block,
@@ -16,6 +16,10 @@ pub trait Component: Send + Sync {
 
     fn handle_resize_event(&mut self, columns: u16, rows: u16) -> HandleResult;
 
+    fn handle_mouse_event(&mut self, _mouse_event: &MouseEvent) -> HandleResult {
+        HandleResult::NotHandled
+    }
+
     fn handle_focus_gained(&mut self) -> HandleResult;
 
     fn get_focused_child(&self) -> Option<Arc<RwLock<dyn Component>>>;
@@ -43,6 +47,7 @@ pub trait Component: Send + Sync {
             HandleResult::NotHandled => match event {
                 Event::Key(key_event) => self.handle_key_event(key_event),
                 Event::Resize(columns, rows) => self.handle_resize_event(*columns, *rows),
+                Event::Mouse(mouse_event) => self.handle_mouse_event(mouse_event),
                 _ => HandleResult::NotHandled,
             },
         }
@@ -1,14 +1,29 @@
-use std::{collections::HashMap, sync::mpsc::Sender};
+use std::{
+    collections::VecDeque,
+    fs::{self, File},
+    path::{Path, PathBuf},
+    sync::mpsc::Sender,
+};
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use ratatui::{style::Stylize, text::Line};
+use oombak_sim::{request::ProbePointsModification, InstanceNode};
+use ratatui::{layout::Rect, style::Stylize, text::Line};
 
 use crate::{
-    backend::interpreter,
+    backend::{
+        completion::{self, Completion},
+        history::History,
+        init_profile::{InitProfile, InitProfiles},
+        interpreter::{self, LogAction, ProbeAction},
+        key_bindings::{ActionInfo, KeyBindings, KeyPress},
+    },
     component::{Component, HandleResult},
-    styles::terminal::{ERROR_OUTPUT_STYLE, NORMAL_OUTPUT_STYLE, NOTIFICATION_OUTPUT_STYLE},
+    styles::terminal::{
+        COMPLETION_HINT_STYLE, ERROR_OUTPUT_STYLE, NORMAL_OUTPUT_STYLE, NOTIFICATION_OUTPUT_STYLE,
+    },
     threads::{simulator_request_dispatcher, RendererMessage},
-    widgets::{CommandLine, KeyDesc, KeyId, KeyMaps, Terminal, TerminalOutput, TerminalState},
+    utils::pattern::Pattern,
+    widgets::{CommandLine, KeyId, KeyMaps, Terminal, TerminalOutput, TerminalState},
 };
 
 use super::TokioSender;
@@ -19,7 +34,26 @@ pub struct CommandInterpreter {
     terminal_state: TerminalState,
     line_state: LineState,
     mode: Mode,
+    key_bindings: KeyBindings<Action>,
     key_mappings: KeyMaps,
+    loaded_design_path: Option<PathBuf>,
+    signal_paths: Vec<String>,
+    probed_points: Vec<String>,
+    history: History,
+    history_search: Option<HistorySearch>,
+    /// Completion candidates shown above the command line
+    completion_hint: Option<String>,
+    view_commands: Vec<interpreter::Command>,
+    script_lines: VecDeque<String>,
+    /// Request whose result the next line of the script waits for
+    awaited_request_id: Option<usize>,
+}
+
+struct HistorySearch {
+    query: String,
+    match_idx: Option<usize>,
+    /// Line restored if the search is cancelled
+    edited_line: String,
 }
 
 #[derive(PartialEq)]
@@ -34,19 +68,116 @@ pub enum Mode {
     Window,
 }
 
+#[derive(Clone, Copy)]
+enum Action {
+    Close,
+    Execute,
+    Complete,
+    PreviousCommand,
+    NextCommand,
+    SearchHistory,
+    ScrollOutputUp,
+    ScrollOutputDown,
+    ScrollOutputPageUp,
+    ScrollOutputPageDown,
+}
+
+const ACTIONS: [ActionInfo<Action>; 10] = [
+    ActionInfo {
+        action: Action::Close,
+        name: "close",
+        description: "close window",
+        default_keys: &["esc", "ctrl-d"],
+    },
+    ActionInfo {
+        action: Action::Execute,
+        name: "execute",
+        description: "execute command",
+        default_keys: &["enter"],
+    },
+    ActionInfo {
+        action: Action::Complete,
+        name: "complete",
+        description: "complete",
+        default_keys: &["tab"],
+    },
+    ActionInfo {
+        action: Action::PreviousCommand,
+        name: "previous-command",
+        description: "previous command",
+        default_keys: &["up", "ctrl-p"],
+    },
+    ActionInfo {
+        action: Action::NextCommand,
+        name: "next-command",
+        description: "next command",
+        default_keys: &["down", "ctrl-n"],
+    },
+    ActionInfo {
+        action: Action::SearchHistory,
+        name: "search-history",
+        description: "search history",
+        default_keys: &["ctrl-r"],
+    },
+    ActionInfo {
+        action: Action::ScrollOutputUp,
+        name: "scroll-output-up",
+        description: "scroll output up",
+        default_keys: &["shift-up"],
+    },
+    ActionInfo {
+        action: Action::ScrollOutputDown,
+        name: "scroll-output-down",
+        description: "scroll output down",
+        default_keys: &["shift-down"],
+    },
+    ActionInfo {
+        action: Action::ScrollOutputPageUp,
+        name: "scroll-output-page-up",
+        description: "scroll output page up",
+        default_keys: &["pageup"],
+    },
+    ActionInfo {
+        action: Action::ScrollOutputPageDown,
+        name: "scroll-output-page-down",
+        description: "scroll output page down",
+        default_keys: &["pagedown"],
+    },
+];
+
+const MAX_NUM_OF_LISTED_CANDIDATES: usize = 50;
+
+impl HistorySearch {
+    fn prompt(query: &str, is_failed: bool) -> String {
+        let failed = if is_failed { "failed " } else { "" };
+        format!(" ({failed}reverse-i-search)`{query}': ")
+    }
+}
+
 impl CommandInterpreter {
     pub fn new(
         message_tx: Sender<RendererMessage>,
         request_tx: TokioSender<oombak_sim::Message>,
     ) -> Self {
-        let key_mappings = Self::create_key_mappings();
+        let key_bindings = KeyBindings::load_or_defaults(&ACTIONS, "terminal");
+        let key_mappings = key_bindings.key_maps();
         Self {
             message_tx,
             request_tx,
             terminal_state: TerminalState::default(),
             line_state: LineState::NotActive,
             mode: Mode::Line,
+            key_bindings,
             key_mappings,
+            loaded_design_path: None,
+            signal_paths: vec![],
+            probed_points: vec![],
+            history: History::load(),
+            history_search: None,
+            completion_hint: None,
+            view_commands: vec![],
+            script_lines: VecDeque::new(),
+            awaited_request_id: None,
         }
     }
 
@@ -63,19 +194,61 @@ impl CommandInterpreter {
         self.mode = Mode::Window;
     }
 
-    fn create_key_mappings() -> KeyMaps {
-        HashMap::from([
-            (KeyId::from(KeyCode::Esc), KeyDesc::from("close window")),
-            (
-                KeyId::from((KeyCode::Char('d'), KeyModifiers::CONTROL)),
-                KeyDesc::from("close window"),
-            ),
-            (
-                KeyId::from(KeyCode::Enter),
-                KeyDesc::from("execute command"),
-            ),
-        ])
-        .into()
+    pub fn command_history(&self) -> &[String] {
+        self.history.entries()
+    }
+
+    pub fn set_command_history(&mut self, command_history: Vec<String>) {
+        self.history.set_entries(command_history);
+    }
+
+    pub fn take_view_commands(&mut self) -> Vec<interpreter::Command> {
+        std::mem::take(&mut self.view_commands)
+    }
+
+    pub fn append_output(&mut self, output: TerminalOutput) {
+        self.terminal_state.append_output_history(output);
+        self.notify_render();
+    }
+
+    pub fn set_log_file(&mut self, log_file: File) {
+        self.terminal_state.set_log_file(log_file);
+    }
+
+    pub fn load(&mut self, design_path: &Path, top_module: Option<String>) {
+        self.send_request(oombak_sim::Request::load(
+            design_path.to_path_buf(),
+            top_module,
+        ));
+        self.append_output(TerminalOutput::Normal(format!(
+            "loading {}",
+            design_path.display()
+        )));
+    }
+
+    /// Executes the commands in the file line by line, each once the simulator is done with the
+    /// requests of the previous ones. Empty lines and lines starting with `#` are skipped, and the
+    /// script stops at the first error.
+    pub fn run_script(&mut self, path: &Path) {
+        let script = match fs::read_to_string(path) {
+            Ok(script) => script,
+            Err(e) => {
+                let message = format!("cannot read {}: {e}", path.display());
+                self.append_output(TerminalOutput::Error(message));
+                return;
+            }
+        };
+        self.script_lines = script
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect();
+        self.append_output(TerminalOutput::Normal(format!(
+            "running script {}",
+            path.display()
+        )));
+        self.continue_script();
     }
 }
 
@@ -118,9 +291,16 @@ impl CommandInterpreter {
                     rect,
                     self.terminal_state.command_line_state_mut(),
                 );
+                if let (Some(hint), Some(y)) = (&self.completion_hint, rect.y.checked_sub(1)) {
+                    let hint_rect = Rect { y, ..rect };
+                    f.render_widget(
+                        Line::from(&hint[..]).style(COMPLETION_HINT_STYLE),
+                        hint_rect,
+                    );
+                }
             }
             LineState::NotActive => {
-                let line = match self.terminal_state.output_history().last() {
+                let line = match self.terminal_state.last_output() {
                     Some(TerminalOutput::Normal(res)) => {
                         Line::from(&res[..]).style(NORMAL_OUTPUT_STYLE)
                     }
@@ -142,10 +322,7 @@ impl CommandInterpreter {
     }
 
     fn handle_key_event_line_mode(&mut self, key_event: &KeyEvent) -> HandleResult {
-        let mut handle_result = self.handle_key_event_window_mode(key_event);
-        if key_event.code == KeyCode::Enter {
-            handle_result = HandleResult::ReleaseFocus;
-        }
+        let handle_result = self.handle_key_event_window_mode(key_event);
         if handle_result == HandleResult::ReleaseFocus {
             self.line_state = LineState::NotActive;
         }
@@ -153,60 +330,215 @@ impl CommandInterpreter {
     }
 
     fn handle_key_event_window_mode(&mut self, key_event: &KeyEvent) -> HandleResult {
-        let command_line_state = self.terminal_state.command_line_state_mut();
-        match (key_event.code, key_event.modifiers) {
-            (KeyCode::Esc, _) => {
-                command_line_state.clear();
-                return HandleResult::ReleaseFocus;
+        if self.history_search.is_some() {
+            if let Some(handle_result) = self.handle_key_event_history_search(key_event) {
+                return handle_result;
             }
-            (KeyCode::Char('d'), modifier) if modifier.contains(KeyModifiers::CONTROL) => {
-                command_line_state.clear();
+        }
+        let key_press = self.key_bindings.press(KeyId::from(key_event));
+        if !matches!(key_press, KeyPress::Action(Action::Complete)) {
+            self.completion_hint = None;
+        }
+        match key_press {
+            KeyPress::Action(action) => return self.perform(action),
+            KeyPress::Pending => (),
+            KeyPress::Unbound if matches!(key_event.code, KeyCode::F(_)) => {
+                return HandleResult::NotHandled
+            }
+            KeyPress::Unbound => self.edit_command_line(key_event),
+        };
+        self.notify_render();
+        HandleResult::Handled
+    }
+
+    fn perform(&mut self, action: Action) -> HandleResult {
+        match action {
+            Action::Close => {
+                self.terminal_state.command_line_state_mut().clear();
                 return HandleResult::ReleaseFocus;
             }
-            (KeyCode::Enter, _) => {
-                self.execute_command();
+            Action::Execute => {
+                let command_text = self.terminal_state.command_line_state().text().to_string();
+                self.history.push(&command_text);
+                self.execute_command(&command_text);
                 self.terminal_state.command_line_state_mut().clear();
+                if self.mode == Mode::Line {
+                    return HandleResult::ReleaseFocus;
+                }
+            }
+            Action::Complete => self.complete(),
+            Action::PreviousCommand => self.show_previous_command(),
+            Action::NextCommand => self.show_next_command(),
+            Action::SearchHistory => self.start_history_search(),
+            Action::ScrollOutputUp => self.terminal_state.scroll_up(1),
+            Action::ScrollOutputDown => self.terminal_state.scroll_down(1),
+            Action::ScrollOutputPageUp => self.terminal_state.scroll_page_up(),
+            Action::ScrollOutputPageDown => self.terminal_state.scroll_page_down(),
+        }
+        self.notify_render();
+        HandleResult::Handled
+    }
+
+    fn edit_command_line(&mut self, key_event: &KeyEvent) {
+        let command_line_state = self.terminal_state.command_line_state_mut();
+        let is_ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
+        let is_alt = key_event.modifiers.contains(KeyModifiers::ALT);
+        match key_event.code {
+            KeyCode::Char('a') if is_ctrl => command_line_state.move_cursor_to_start(),
+            KeyCode::Char('e') if is_ctrl => command_line_state.move_cursor_to_end(),
+            KeyCode::Char('w') if is_ctrl => command_line_state.delete_word_before_cursor(),
+            KeyCode::Char('u') if is_ctrl => command_line_state.delete_before_cursor(),
+            KeyCode::Char('k') if is_ctrl => command_line_state.delete_after_cursor(),
+            KeyCode::Char('b') if is_alt => command_line_state.move_cursor_word_left(),
+            KeyCode::Char('f') if is_alt => command_line_state.move_cursor_word_right(),
+            KeyCode::Char(c) if (key_event.modifiers - KeyModifiers::SHIFT).is_empty() => {
+                command_line_state.put(c)
+            }
+            KeyCode::Backspace if is_alt => command_line_state.delete_word_before_cursor(),
+            KeyCode::Backspace => command_line_state.backspace(),
+            KeyCode::Delete => command_line_state.delete(),
+            KeyCode::Home => command_line_state.move_cursor_to_start(),
+            KeyCode::End => command_line_state.move_cursor_to_end(),
+            KeyCode::Right if is_ctrl => command_line_state.move_cursor_word_right(),
+            KeyCode::Left if is_ctrl => command_line_state.move_cursor_word_left(),
+            KeyCode::Right => command_line_state.move_cursor_right(),
+            KeyCode::Left => command_line_state.move_cursor_left(),
+            _ => (),
+        }
+    }
+
+    fn show_previous_command(&mut self) {
+        let command_line_state = self.terminal_state.command_line_state_mut();
+        if let Some(command) = self.history.previous(command_line_state.text()) {
+            command_line_state.set_text(command);
+        }
+    }
+
+    fn show_next_command(&mut self) {
+        if let Some(command) = self.history.next() {
+            self.terminal_state
+                .command_line_state_mut()
+                .set_text(&command);
+        }
+    }
+
+    fn complete(&mut self) {
+        let command_line_state = self.terminal_state.command_line_state_mut();
+        match completion::complete(command_line_state.text_before_cursor(), &self.signal_paths) {
+            Completion::Insert(text) => text.chars().for_each(|c| command_line_state.put(c)),
+            Completion::Candidates(candidates) if self.mode == Mode::Line => {
+                self.completion_hint = Some(candidates.join("  "));
             }
-            (KeyCode::Char(c), modifier) if modifier.is_empty() => {
-                command_line_state.put(c);
+            Completion::Candidates(candidates) => {
+                let mut output =
+                    candidates[..candidates.len().min(MAX_NUM_OF_LISTED_CANDIDATES)].join("\n");
+                if candidates.len() > MAX_NUM_OF_LISTED_CANDIDATES {
+                    let num_of_unlisted = candidates.len() - MAX_NUM_OF_LISTED_CANDIDATES;
+                    output += &format!("\n... and {num_of_unlisted} more");
+                }
+                self.terminal_state
+                    .append_output_history(TerminalOutput::Notification(output));
             }
-            (KeyCode::Backspace, _) => {
-                command_line_state.backspace();
+            Completion::None => (),
+        }
+    }
+
+    fn start_history_search(&mut self) {
+        let command_line_state = self.terminal_state.command_line_state_mut();
+        self.history_search = Some(HistorySearch {
+            query: String::new(),
+            match_idx: None,
+            edited_line: command_line_state.text().to_string(),
+        });
+        command_line_state.set_prompt(Some(HistorySearch::prompt("", false)));
+    }
+
+    /// Returns `None` for keys that end the search and are then handled as usual.
+    fn handle_key_event_history_search(&mut self, key_event: &KeyEvent) -> Option<HandleResult> {
+        let search = self.history_search.as_mut()?;
+        let is_ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
+        let num_of_entries = self.history.entries().len();
+        match key_event.code {
+            KeyCode::Char('r') if is_ctrl => {
+                let before = search.match_idx.unwrap_or(num_of_entries);
+                self.search_history(before);
             }
-            (KeyCode::Right, _) => {
-                command_line_state.move_cursor_right();
+            KeyCode::Esc => self.cancel_history_search(),
+            KeyCode::Char('g') if is_ctrl => self.cancel_history_search(),
+            KeyCode::Char(c) if (key_event.modifiers - KeyModifiers::SHIFT).is_empty() => {
+                search.query.push(c);
+                // The current match still counts while the query grows
+                let before = search.match_idx.map_or(num_of_entries, |idx| idx + 1);
+                self.search_history(before);
             }
-            (KeyCode::Left, _) => {
-                command_line_state.move_cursor_left();
+            KeyCode::Backspace => {
+                search.query.pop();
+                self.search_history(num_of_entries);
             }
-            (KeyCode::F(_), _) => {
-                return HandleResult::NotHandled;
+            _ => {
+                self.end_history_search();
+                return None;
             }
-            _ => (),
-        };
+        }
         self.notify_render();
-        HandleResult::Handled
+        Some(HandleResult::Handled)
+    }
+
+    fn search_history(&mut self, before: usize) {
+        let Some(search) = self.history_search.as_mut() else {
+            return;
+        };
+        let command_line_state = self.terminal_state.command_line_state_mut();
+        let found = self.history.search(&search.query, before);
+        if let Some(idx) = found {
+            search.match_idx = Some(idx);
+            let entry = self.history.get(idx).unwrap();
+            let match_start = entry.find(&search.query[..]).unwrap_or(0);
+            command_line_state.set_text_with_cursor(entry, entry[..match_start].chars().count());
+        }
+        let prompt = HistorySearch::prompt(&search.query, found.is_none());
+        command_line_state.set_prompt(Some(prompt));
+    }
+
+    fn cancel_history_search(&mut self) {
+        if let Some(search) = self.history_search.take() {
+            let command_line_state = self.terminal_state.command_line_state_mut();
+            command_line_state.set_text(&search.edited_line);
+            command_line_state.set_prompt(None);
+        }
+    }
+
+    fn end_history_search(&mut self) {
+        self.history_search = None;
+        self.terminal_state
+            .command_line_state_mut()
+            .set_prompt(None);
     }
 
-    fn execute_command(&mut self) {
-        let command_text = self.terminal_state.command_line_state().text();
+    fn execute_command(&mut self, command_text: &str) {
         match interpreter::interpret(command_text) {
             Ok(command) => match command {
                 interpreter::Command::Run(duration) => {
                     let request = oombak_sim::Request::run(duration);
-                    self.request_tx.blocking_send(request).unwrap();
+                    self.send_request(request);
                     self.terminal_state
                         .append_output_history(TerminalOutput::Normal(command_text.to_string()));
                 }
-                interpreter::Command::Load(sv_path) => {
-                    let request = oombak_sim::Request::load(sv_path);
-                    self.request_tx.blocking_send(request).unwrap();
+                interpreter::Command::RunCycles(num_of_cycles) => {
+                    let request = oombak_sim::Request::run_cycles(num_of_cycles);
+                    self.send_request(request);
+                    self.terminal_state
+                        .append_output_history(TerminalOutput::Normal(command_text.to_string()));
+                }
+                interpreter::Command::Load(design_path, top_module) => {
+                    let request = oombak_sim::Request::load(design_path, top_module);
+                    self.send_request(request);
                     self.terminal_state
                         .append_output_history(TerminalOutput::Normal(command_text.to_string()));
                 }
                 interpreter::Command::Set(signal_name, value) => {
                     let request = oombak_sim::Request::set_signal(signal_name, value);
-                    self.request_tx.blocking_send(request).unwrap();
+                    self.send_request(request);
                     self.terminal_state
                         .append_output_history(TerminalOutput::Normal(command_text.to_string()));
                 }
@@ -217,7 +549,87 @@ impl CommandInterpreter {
                         low_value,
                         high_value,
                     );
-                    self.request_tx.blocking_send(request).unwrap();
+                    self.send_request(request);
+                    self.terminal_state
+                        .append_output_history(TerminalOutput::Normal(command_text.to_string()));
+                }
+                interpreter::Command::SetClock(signal_name, clock_spec) => {
+                    let request = oombak_sim::Request::set_clock(signal_name, clock_spec);
+                    self.send_request(request);
+                    self.terminal_state
+                        .append_output_history(TerminalOutput::Normal(command_text.to_string()));
+                }
+                interpreter::Command::SetStimulus(signal_name, stimulus_spec) => {
+                    let request = oombak_sim::Request::set_stimulus(signal_name, stimulus_spec);
+                    self.send_request(request);
+                    self.terminal_state
+                        .append_output_history(TerminalOutput::Normal(command_text.to_string()));
+                }
+                interpreter::Command::SetReferenceClock(signal_name) => {
+                    let request = oombak_sim::Request::set_reference_clock(signal_name);
+                    self.send_request(request);
+                    self.terminal_state
+                        .append_output_history(TerminalOutput::Normal(command_text.to_string()));
+                }
+                interpreter::Command::SetAtEdge(signal_name, value, edge, skew) => {
+                    let request = oombak_sim::Request::set_at_edge(signal_name, value, edge, skew);
+                    self.send_request(request);
+                    self.terminal_state
+                        .append_output_history(TerminalOutput::Normal(command_text.to_string()));
+                }
+                interpreter::Command::DefineProfile(name, profile, auto) => {
+                    let output = match self.define_profile(&name, profile, auto) {
+                        Ok(()) => TerminalOutput::Normal(command_text.to_string()),
+                        Err(message) => TerminalOutput::Error(message),
+                    };
+                    self.terminal_state.append_output_history(output);
+                }
+                interpreter::Command::Init(name) => {
+                    let output = match self.apply_profile(name.as_deref()) {
+                        Ok(name) => TerminalOutput::Normal(format!("applying profile {name}")),
+                        Err(message) => TerminalOutput::Error(message),
+                    };
+                    self.terminal_state.append_output_history(output);
+                }
+                interpreter::Command::Log(action) => {
+                    let output = match self.log(action) {
+                        Ok(()) => TerminalOutput::Normal(command_text.to_string()),
+                        Err(message) => TerminalOutput::Error(message),
+                    };
+                    self.terminal_state.append_output_history(output);
+                }
+                interpreter::Command::Probe(action, pattern) => {
+                    let output = match self.probe(action, pattern.as_deref()) {
+                        Ok(output) => TerminalOutput::Normal(output),
+                        Err(message) => TerminalOutput::Error(message),
+                    };
+                    self.terminal_state.append_output_history(output);
+                }
+                command @ (interpreter::Command::SaveSession(_)
+                | interpreter::Command::OpenSession(_)
+                | interpreter::Command::AddMarker(_, _)
+                | interpreter::Command::DeleteMarker(_)
+                | interpreter::Command::GotoMarker(_)
+                | interpreter::Command::Find(_, _, _)
+                | interpreter::Command::Search(_)
+                | interpreter::Command::Derive(_, _)
+                | interpreter::Command::RemoveDerived(_)
+                | interpreter::Command::Expand(_, _)
+                | interpreter::Command::Collapse(_)
+                | interpreter::Command::AddGroup(_)
+                | interpreter::Command::RemoveGroup(_)
+                | interpreter::Command::Move(_, _)
+                | interpreter::Command::Hide(_)
+                | interpreter::Command::Show(_)
+                | interpreter::Command::Duplicate(_)
+                | interpreter::Command::Filter(_)
+                | interpreter::Command::DefineEnumMap(_, _)
+                | interpreter::Command::Decode(_)
+                | interpreter::Command::RemoveDecoder(_)
+                | interpreter::Command::ShowTransactions(_)
+                | interpreter::Command::SetColor(_, _)
+                | interpreter::Command::Theme(_)) => {
+                    self.view_commands.push(command);
                     self.terminal_state
                         .append_output_history(TerminalOutput::Normal(command_text.to_string()));
                 }
@@ -236,6 +648,160 @@ impl CommandInterpreter {
         }
     }
 
+    fn loaded_design_path(&self) -> Result<&PathBuf, String> {
+        self.loaded_design_path
+            .as_ref()
+            .ok_or_else(|| "no design loaded".to_string())
+    }
+
+    fn define_profile(
+        &self,
+        name: &str,
+        profile: InitProfile,
+        auto: Option<bool>,
+    ) -> Result<(), String> {
+        let design_path = self.loaded_design_path()?;
+        let mut profiles = InitProfiles::load(design_path)?;
+        profiles
+            .profiles
+            .entry(name.to_string())
+            .or_default()
+            .update(profile);
+        match auto {
+            Some(true) => profiles.auto = Some(name.to_string()),
+            Some(false) if profiles.auto.as_deref() == Some(name) => profiles.auto = None,
+            _ => (),
+        }
+        profiles.save(design_path)
+    }
+
+    fn apply_profile(&mut self, name: Option<&str>) -> Result<String, String> {
+        let design_path = self.loaded_design_path()?;
+        let profiles = InitProfiles::load(design_path)?;
+        let (name, profile) = profiles.get(name)?;
+        let init_spec = oombak_sim::InitSpec::try_from(profile)?;
+        let request = oombak_sim::Request::initialize(init_spec);
+        self.send_request(request);
+        Ok(name.to_string())
+    }
+
+    fn apply_auto_profile(&mut self) {
+        let has_auto_profile = self
+            .loaded_design_path()
+            .and_then(|path| InitProfiles::load(path))
+            .is_ok_and(|profiles| profiles.auto.is_some());
+        if has_auto_profile {
+            let output = match self.apply_profile(None) {
+                Ok(name) => TerminalOutput::Normal(format!("applying profile {name}")),
+                Err(message) => TerminalOutput::Error(message),
+            };
+            self.terminal_state.append_output_history(output);
+        }
+    }
+
+    /// Expands the pattern against the signals of the loaded design and requests adding or
+    /// removing all of the matches at once.
+    fn probe(&mut self, action: ProbeAction, pattern: Option<&str>) -> Result<String, String> {
+        self.loaded_design_path()?;
+        let pattern = pattern.map(Pattern::new).transpose()?;
+        let is_matched = |path: &&String| pattern.as_ref().is_none_or(|p| p.matches(path));
+        let is_probed = |path: &&String| self.probed_points.contains(path);
+        let modification = match action {
+            ProbeAction::List => {
+                let probed_points: Vec<&str> = self
+                    .probed_points
+                    .iter()
+                    .filter(is_matched)
+                    .map(|path| &path[..])
+                    .collect();
+                if probed_points.is_empty() {
+                    return Ok("no probed signals".to_string());
+                }
+                return Ok(probed_points.join("\n"));
+            }
+            ProbeAction::Add => ProbePointsModification {
+                to_add: self
+                    .signal_paths
+                    .iter()
+                    .filter(is_matched)
+                    .filter(|path| !is_probed(path))
+                    .cloned()
+                    .collect(),
+                to_remove: vec![],
+            },
+            ProbeAction::Remove => ProbePointsModification {
+                to_add: vec![],
+                to_remove: self
+                    .probed_points
+                    .iter()
+                    .filter(is_matched)
+                    .cloned()
+                    .collect(),
+            },
+        };
+        let number_of_signals = modification.to_add.len() + modification.to_remove.len();
+        if number_of_signals == 0 {
+            return Err("no signal to modify matches the pattern".to_string());
+        }
+        let request = oombak_sim::Request::modify_probe_points(modification);
+        self.send_request(request);
+        let verb = if action == ProbeAction::Add {
+            "adding"
+        } else {
+            "removing"
+        };
+        Ok(format!("{verb} {number_of_signals} probe points"))
+    }
+
+    fn send_request(&mut self, request: oombak_sim::Message) {
+        if let oombak_sim::Message::Request(request) = &request {
+            self.awaited_request_id = Some(request.id);
+        }
+        self.request_tx.blocking_send(request).unwrap();
+    }
+
+    fn log(&mut self, action: LogAction) -> Result<(), String> {
+        match action {
+            LogAction::Filter(kinds) => self.terminal_state.set_shown_kinds(kinds),
+            LogAction::Search(text) => self.terminal_state.set_search(text),
+            LogAction::Group(is_grouped) => self.terminal_state.set_grouped(is_grouped),
+            LogAction::Save(path) => self.terminal_state.save_log(&path)?,
+        }
+        Ok(())
+    }
+
+    fn continue_script(&mut self) {
+        while self.awaited_request_id.is_none() {
+            let Some(line) = self.script_lines.pop_front() else {
+                return;
+            };
+            self.execute_command(&line);
+            if let Some(TerminalOutput::Error(_)) = self.terminal_state.last_output() {
+                self.abort_script();
+            }
+        }
+    }
+
+    fn abort_script(&mut self) {
+        if !self.script_lines.is_empty() {
+            self.script_lines.clear();
+            self.terminal_state
+                .append_output_history(TerminalOutput::Error("script aborted".to_string()));
+        }
+    }
+
+    fn collect_signal_paths(node: &InstanceNode, parent_path: &str, paths: &mut Vec<String>) {
+        let path = if parent_path.is_empty() {
+            node.name.clone()
+        } else {
+            format!("{parent_path}.{}", node.name)
+        };
+        paths.extend(node.signals.iter().map(|s| format!("{path}.{}", s.name)));
+        for child in node.children.iter() {
+            Self::collect_signal_paths(child, &path, paths);
+        }
+    }
+
     fn notify_render(&self) {
         self.message_tx.send(RendererMessage::Render).unwrap();
     }
@@ -248,6 +814,14 @@ impl CommandInterpreter {
 impl simulator_request_dispatcher::Listener for CommandInterpreter {
     fn on_receive_reponse(&mut self, response: &oombak_sim::Response) {
         let id = response.id;
+        let is_awaited = self.awaited_request_id == Some(id)
+            && !matches!(
+                response.payload,
+                oombak_sim::response::Payload::Notification(_)
+            );
+        if is_awaited {
+            self.awaited_request_id = None;
+        }
         let result = match &response.payload {
             oombak_sim::response::Payload::Result(_) => {
                 TerminalOutput::Normal(format!("[ID: {id:x}] Finished"))
@@ -259,7 +833,20 @@ impl simulator_request_dispatcher::Listener for CommandInterpreter {
                 TerminalOutput::Error(format!("[ID: {id:x}] Error: {e}"))
             }
         };
-        self.terminal_state.append_output_history(result);
+        self.terminal_state.append_request_output(id, result);
+        if let Some(oombak_sim::response::Results::LoadedDut(loaded_dut)) = response.result() {
+            self.loaded_design_path = Some(loaded_dut.path.clone());
+            self.probed_points = loaded_dut.probed_points.clone();
+            self.signal_paths.clear();
+            Self::collect_signal_paths(&loaded_dut.root_node, "", &mut self.signal_paths);
+            self.apply_auto_profile();
+        }
+        if is_awaited {
+            match &response.payload {
+                oombak_sim::response::Payload::Error(_) => self.abort_script(),
+                _ => self.continue_script(),
+            }
+        }
         self.notify_render();
     }
 }
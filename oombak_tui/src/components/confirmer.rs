@@ -1,9 +1,6 @@
-use std::{
-    collections::HashMap,
-    sync::{mpsc::Sender, Arc, RwLock},
-};
+use std::sync::{mpsc::Sender, Arc, RwLock};
 
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::KeyEvent;
 use ratatui::{
     layout::Rect,
     widgets::{Block, BorderType, Clear, Paragraph, Wrap},
@@ -11,14 +8,44 @@ use ratatui::{
 };
 
 use crate::{
+    backend::key_bindings::{ActionInfo, KeyBindings, KeyPress},
     component::{Component, HandleResult},
     threads::RendererMessage,
     utils::layout::get_popup_area_centered,
-    widgets::{ConfirmationBox, ConfirmationState, KeyDesc, KeyId, KeyMaps},
+    widgets::{ConfirmationBox, ConfirmationState, KeyId, KeyMaps},
 };
 
+#[derive(Clone, Copy)]
+enum Action {
+    HighlightConfirm,
+    HighlightDismiss,
+    Select,
+}
+
+const ACTIONS: [ActionInfo<Action>; 3] = [
+    ActionInfo {
+        action: Action::HighlightConfirm,
+        name: "move-left",
+        description: "move selection",
+        default_keys: &["left", "h"],
+    },
+    ActionInfo {
+        action: Action::HighlightDismiss,
+        name: "move-right",
+        description: "move selection",
+        default_keys: &["right", "l"],
+    },
+    ActionInfo {
+        action: Action::Select,
+        name: "select",
+        description: "confirm selection",
+        default_keys: &["enter"],
+    },
+];
+
 pub struct Confirmer {
     message_tx: Sender<RendererMessage>,
+    key_bindings: KeyBindings<Action>,
     key_maps: KeyMaps,
     text: String,
     highlighted_state: ConfirmationState,
@@ -27,9 +54,11 @@ pub struct Confirmer {
 
 impl Confirmer {
     pub fn new(message_tx: Sender<RendererMessage>) -> Self {
-        let key_maps = Self::create_key_maps();
+        let key_bindings = KeyBindings::load_or_defaults(&ACTIONS, "confirm");
+        let key_maps = key_bindings.key_maps();
         Self {
             message_tx,
+            key_bindings,
             key_maps,
             text: "".to_string(),
             highlighted_state: ConfirmationState::confirm(),
@@ -37,25 +66,6 @@ impl Confirmer {
         }
     }
 
-    pub fn create_key_maps() -> KeyMaps {
-        KeyMaps::from(HashMap::from([
-            (
-                KeyId::from(KeyCode::Char('h')),
-                KeyDesc::from("move selection"),
-            ),
-            (KeyId::from(KeyCode::Left), KeyDesc::from("move selection")),
-            (
-                KeyId::from(KeyCode::Char('l')),
-                KeyDesc::from("move selection"),
-            ),
-            (KeyId::from(KeyCode::Right), KeyDesc::from("move selection")),
-            (
-                KeyId::from(KeyCode::Enter),
-                KeyDesc::from("confirm selection"),
-            ),
-        ]))
-    }
-
     pub fn set_text(&mut self, text: &str) {
         self.text = text.to_string();
     }
@@ -94,19 +104,15 @@ impl Component for Confirmer {
     }
 
     fn handle_key_event(&mut self, key_event: &KeyEvent) -> HandleResult {
-        match key_event.code {
-            KeyCode::Left | KeyCode::Char('h') => {
-                self.highlighted_state.set_confirm();
-            }
-            KeyCode::Right | KeyCode::Char('l') => {
-                self.highlighted_state.set_dismiss();
-            }
-            KeyCode::Enter => {
+        match self.key_bindings.press(KeyId::from(key_event)) {
+            KeyPress::Action(Action::HighlightConfirm) => self.highlighted_state.set_confirm(),
+            KeyPress::Action(Action::HighlightDismiss) => self.highlighted_state.set_dismiss(),
+            KeyPress::Action(Action::Select) => {
                 self.notify_render();
                 self.selected_state = self.highlighted_state;
                 return HandleResult::ReleaseFocus;
             }
-            _ => (),
+            KeyPress::Pending | KeyPress::Unbound => (),
         }
         self.notify_render();
         HandleResult::Handled
@@ -1,15 +1,14 @@
 use std::{
-    collections::HashMap,
     env, fs,
     io::BufRead,
     path::{Path, PathBuf},
     sync::mpsc::Sender,
 };
 
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use file_type::FileType;
 use ratatui::{
-    layout::{Constraint, Layout, Rect},
+    layout::{Constraint, Layout, Position, Rect},
     style::{Modifier, Style, Styled, Stylize},
     text::{Line, Span, Text},
     widgets::{Block, BorderType, List, ListItem, ListState},
@@ -17,6 +16,7 @@ use ratatui::{
 };
 
 use crate::{
+    backend::key_bindings::{ActionInfo, KeyBindings, KeyPress},
     component::{Component, HandleResult},
     styles::{
         file_explorer::{
@@ -26,11 +26,47 @@ use crate::{
         global::SELECTED_ITEM_STYLE,
     },
     threads::RendererMessage,
-    widgets::{KeyDesc, KeyId, KeyMaps},
+    utils,
+    widgets::{KeyId, KeyMaps},
 };
 
 use super::TokioSender;
 
+#[derive(Clone, Copy)]
+enum Action {
+    Close,
+    Open,
+    ScrollUp,
+    ScrollDown,
+}
+
+const ACTIONS: [ActionInfo<Action>; 4] = [
+    ActionInfo {
+        action: Action::Close,
+        name: "close",
+        description: "close window",
+        default_keys: &["q"],
+    },
+    ActionInfo {
+        action: Action::Open,
+        name: "open",
+        description: "open",
+        default_keys: &["enter"],
+    },
+    ActionInfo {
+        action: Action::ScrollUp,
+        name: "scroll-up",
+        description: "scroll up",
+        default_keys: &["up", "k"],
+    },
+    ActionInfo {
+        action: Action::ScrollDown,
+        name: "scroll-down",
+        description: "scroll down",
+        default_keys: &["down", "j"],
+    },
+];
+
 pub struct FileExplorer {
     message_tx: Sender<RendererMessage>,
     request_tx: TokioSender<oombak_sim::Message>,
@@ -38,7 +74,10 @@ pub struct FileExplorer {
     entries: Vec<PathBuf>,
     selected_idx: Option<usize>,
     list_state: ListState,
+    list_area: Rect,
+    key_bindings: KeyBindings<Action>,
     key_mappings: KeyMaps,
+    session_to_open: Option<PathBuf>,
 }
 
 impl FileExplorer {
@@ -48,7 +87,8 @@ impl FileExplorer {
     ) -> Self {
         let path = env::current_dir().unwrap();
         let entries = Self::get_sorted_entries(&path);
-        let key_mappings = Self::create_key_mappings();
+        let key_bindings = KeyBindings::load_or_defaults(&ACTIONS, "file-explorer");
+        let key_mappings = key_bindings.key_maps();
         Self {
             message_tx,
             request_tx,
@@ -56,20 +96,26 @@ impl FileExplorer {
             entries,
             selected_idx: None,
             list_state: ListState::default(),
+            list_area: Rect::default(),
+            key_bindings,
             key_mappings,
+            session_to_open: None,
         }
     }
 
-    fn create_key_mappings() -> KeyMaps {
-        HashMap::from([
-            (KeyId::from('q'), KeyDesc::from("close window")),
-            (KeyId::from(KeyCode::Enter), KeyDesc::from("open")),
-            (KeyId::from(KeyCode::Up), KeyDesc::from("scroll up")),
-            (KeyId::from('k'), KeyDesc::from("scroll up")),
-            (KeyId::from(KeyCode::Down), KeyDesc::from("scroll down")),
-            (KeyId::from('j'), KeyDesc::from("scroll down")),
-        ])
-        .into()
+    fn perform(&mut self, action: Action) -> HandleResult {
+        match action {
+            Action::Close => return HandleResult::ReleaseFocus,
+            Action::Open if self.handle_enter_key_press() => {
+                self.reset_path();
+                return HandleResult::ReleaseFocus;
+            }
+            Action::Open => (),
+            Action::ScrollUp => self.handle_up_key_press(),
+            Action::ScrollDown => self.handle_down_key_press(),
+        }
+        self.notify_render();
+        HandleResult::Handled
     }
 }
 
@@ -96,32 +142,55 @@ impl Component for FileExplorer {
 
         f.render_widget(list_block, list_area);
         f.render_stateful_widget(list, list_inner_area, &mut self.list_state);
+        self.list_area = list_inner_area;
         self.render_file_details(f, file_detail_area);
         self.render_file_preview(f, file_preview_area);
     }
 
     fn handle_key_event(&mut self, key_event: &KeyEvent) -> HandleResult {
-        match key_event.code {
-            KeyCode::Up | KeyCode::Char('k') => self.handle_up_key_press(),
-            KeyCode::Down | KeyCode::Char('j') => self.handle_down_key_press(),
-            KeyCode::Enter => {
-                if self.handle_enter_key_press() {
-                    self.reset_path();
-                    return HandleResult::ReleaseFocus;
-                }
+        match self.key_bindings.press(KeyId::from(key_event)) {
+            KeyPress::Action(action) => self.perform(action),
+            KeyPress::Unbound if matches!(key_event.code, KeyCode::F(_)) => {
+                HandleResult::NotHandled
             }
-            KeyCode::Char('q') => return HandleResult::ReleaseFocus,
-            KeyCode::F(_) => return HandleResult::NotHandled,
-            _ => (),
+            KeyPress::Pending | KeyPress::Unbound => HandleResult::Handled,
         }
-        self.notify_render();
-        HandleResult::Handled
     }
 
     fn handle_resize_event(&mut self, _columns: u16, _rows: u16) -> HandleResult {
         HandleResult::NotHandled
     }
 
+    fn handle_mouse_event(&mut self, mouse_event: &MouseEvent) -> HandleResult {
+        match mouse_event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let position = Position::new(mouse_event.column, mouse_event.row);
+                let item_heights = vec![1; self.entries.len() + 1];
+                let idx = utils::layout::get_list_item_at(
+                    self.list_area,
+                    self.list_state.offset(),
+                    item_heights,
+                    position,
+                );
+                match idx {
+                    Some(idx) if self.selected_idx == Some(idx) => {
+                        return self.perform(Action::Open)
+                    }
+                    Some(idx) => {
+                        self.list_state.select(Some(idx));
+                        self.selected_idx = Some(idx);
+                    }
+                    None => return HandleResult::NotHandled,
+                }
+            }
+            MouseEventKind::ScrollUp => self.handle_up_key_press(),
+            MouseEventKind::ScrollDown => self.handle_down_key_press(),
+            _ => return HandleResult::NotHandled,
+        }
+        self.notify_render();
+        HandleResult::Handled
+    }
+
     fn handle_focus_gained(&mut self) -> HandleResult {
         HandleResult::Handled
     }
@@ -173,12 +242,20 @@ impl FileExplorer {
         }
     }
 
-    fn load_file(&self, idx: usize) {
+    pub fn take_session_to_open(&mut self) -> Option<PathBuf> {
+        self.session_to_open.take()
+    }
+
+    fn load_file(&mut self, idx: usize) {
         let mut file_path = self.path.clone();
         let file = &self.entries[idx];
         file_path.push(file);
+        if file_path.extension().is_some_and(|ext| ext == "toml") {
+            self.session_to_open = Some(file_path);
+            return;
+        }
         self.request_tx
-            .blocking_send(oombak_sim::Request::load(file_path))
+            .blocking_send(oombak_sim::Request::load(file_path, None))
             .unwrap();
     }
 
@@ -1,12 +1,12 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 use std::sync::mpsc::Sender;
 use std::sync::{Arc, RwLock, RwLockReadGuard};
 
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, MouseButton, MouseEvent, MouseEventKind};
 use oombak_sim::request::ProbePointsModification;
 use oombak_sim::response::LoadedDut;
 use oombak_sim::{InstanceNode, Signal};
-use ratatui::layout::Rect;
+use ratatui::layout::{Position, Rect};
 use ratatui::style::Stylize;
 use ratatui::text::{Span, Text};
 use ratatui::widgets::{Block, BorderType};
@@ -17,16 +17,74 @@ use ratatui::{
     widgets::{List, ListItem, ListState, Paragraph},
 };
 
+use crate::backend::key_bindings::{ActionInfo, KeyBindings, KeyPress};
 use crate::styles::global::SELECTED_ITEM_STYLE;
 use crate::styles::instance_hier_viewer::{INSTANCE_ITEM_STYLE, SIGNAL_ITEM_STYLE};
-use crate::widgets::{KeyDesc, KeyId, KeyMaps};
+use crate::widgets::{CommandLine, CommandLineState, KeyId, KeyMaps};
 use crate::{
     component::{Component, HandleResult},
     threads::RendererMessage,
+    utils::{self, pattern::Pattern},
 };
 
 use super::{Confirmer, TokioSender};
 
+#[derive(Clone, Copy)]
+enum Action {
+    Close,
+    ToggleProbe,
+    ScrollUp,
+    ScrollDown,
+    Filter,
+    MarkAllForAdd,
+    MarkAllForRemove,
+}
+
+const ACTIONS: [ActionInfo<Action>; 7] = [
+    ActionInfo {
+        action: Action::Close,
+        name: "close",
+        description: "confirm / dismiss changes",
+        default_keys: &["q"],
+    },
+    ActionInfo {
+        action: Action::ToggleProbe,
+        name: "toggle-probe",
+        description: "add / remove signal from probing",
+        default_keys: &["enter"],
+    },
+    ActionInfo {
+        action: Action::ScrollUp,
+        name: "scroll-up",
+        description: "scroll up",
+        default_keys: &["up", "k"],
+    },
+    ActionInfo {
+        action: Action::ScrollDown,
+        name: "scroll-down",
+        description: "scroll down",
+        default_keys: &["down", "j"],
+    },
+    ActionInfo {
+        action: Action::Filter,
+        name: "filter",
+        description: "filter by name or path",
+        default_keys: &["/"],
+    },
+    ActionInfo {
+        action: Action::MarkAllForAdd,
+        name: "mark-all-for-add",
+        description: "mark all shown signals for add",
+        default_keys: &["+"],
+    },
+    ActionInfo {
+        action: Action::MarkAllForRemove,
+        name: "mark-all-for-remove",
+        description: "mark all shown signals for remove",
+        default_keys: &["-"],
+    },
+];
+
 pub struct InstanceHierViewer {
     message_tx: Sender<RendererMessage>,
     request_tx: TokioSender<oombak_sim::Message>,
@@ -36,9 +94,13 @@ pub struct InstanceHierViewer {
     probed_points: HashSet<String>,
     items_in_list: Vec<HierItem>,
     list_state: ListState,
+    list_area: Rect,
     selected_item_idx: Option<usize>,
     signals_marked_to_add: HashSet<String>,
     signals_marked_to_remove: HashSet<String>,
+    filter_state: CommandLineState,
+    is_editing_filter: bool,
+    key_bindings: KeyBindings<Action>,
     key_mappings: KeyMaps,
 }
 
@@ -80,7 +142,8 @@ impl InstanceHierViewer {
         message_tx: Sender<RendererMessage>,
         request_tx: TokioSender<oombak_sim::Message>,
     ) -> Self {
-        let key_mappings = Self::create_key_mappings();
+        let key_bindings = KeyBindings::load_or_defaults(&ACTIONS, "probe-editor");
+        let key_mappings = key_bindings.key_maps();
         let confirmer = Arc::new(RwLock::new(Confirmer::new(message_tx.clone())));
         Self {
             message_tx,
@@ -90,10 +153,14 @@ impl InstanceHierViewer {
             root_node: None,
             items_in_list: vec![],
             list_state: ListState::default(),
+            list_area: Rect::default(),
             selected_item_idx: None,
             probed_points: HashSet::default(),
             signals_marked_to_add: HashSet::default(),
             signals_marked_to_remove: HashSet::default(),
+            filter_state: CommandLineState::default(),
+            is_editing_filter: false,
+            key_bindings,
             key_mappings,
         }
     }
@@ -109,26 +176,44 @@ impl InstanceHierViewer {
         self.list_state.select_first();
     }
 
-    fn create_key_mappings() -> KeyMaps {
-        HashMap::from([
-            (KeyId::from('q'), KeyDesc::from("confirm / dismiss changes")),
-            (
-                KeyId::from(KeyCode::Enter),
-                KeyDesc::from("add / remove signal from probing"),
-            ),
-            (KeyId::from(KeyCode::Up), KeyDesc::from("scroll up")),
-            (KeyId::from('k'), KeyDesc::from("scroll up")),
-            (KeyId::from(KeyCode::Down), KeyDesc::from("scroll down")),
-            (KeyId::from('j'), KeyDesc::from("scroll down")),
-        ])
-        .into()
+    fn perform(&mut self, action: Action) -> HandleResult {
+        match action {
+            Action::Close => {
+                if self.root_node.is_some() && !self.signals_marked_to_add.is_empty()
+                    || !self.signals_marked_to_remove.is_empty()
+                {
+                    self.focused_child = Some(Child::Confirmer);
+                    let mut text =
+                        String::from("Would you like to apply the following changes?\n\n");
+                    for s in &self.signals_marked_to_add {
+                        text += &format!("(+) {s}\n");
+                    }
+                    for s in &self.signals_marked_to_remove {
+                        text += &format!("(-) {s}\n");
+                    }
+                    self.confirmer.write().unwrap().set_text(&text);
+                } else {
+                    self.notify_render();
+                    return HandleResult::ReleaseFocus;
+                }
+            }
+            Action::ToggleProbe => self.perform_action_on_selected(),
+            Action::ScrollDown => self.scroll_down(),
+            Action::ScrollUp => self.scroll_up(),
+            Action::Filter => self.is_editing_filter = true,
+            Action::MarkAllForAdd => self.mark_all_shown(true),
+            Action::MarkAllForRemove => self.mark_all_shown(false),
+        }
+        self.notify_render();
+        HandleResult::Handled
     }
 }
 
 impl Component for InstanceHierViewer {
     fn render(&mut self, f: &mut ratatui::Frame, rect: ratatui::prelude::Rect) {
         if let Some(node) = &self.root_node {
-            let (list_items, items_in_list) = Self::get_flattened_hierarchy(node);
+            let (list_items, items_in_list) =
+                Self::get_flattened_hierarchy(node, self.filter_pattern().as_ref());
             self.items_in_list = items_in_list;
             let list = List::new(list_items).highlight_style(SELECTED_ITEM_STYLE);
             let block = Block::bordered().border_type(BorderType::Rounded);
@@ -140,10 +225,18 @@ impl Component for InstanceHierViewer {
                 Layout::vertical(vec![Constraint::Length(9), Constraint::Min(0)])
                     .split(main_areas[1])[0];
             let list_area = main_areas[0];
-            let inner_list_area = block.inner(list_area);
+            let mut inner_list_area = block.inner(list_area);
 
             f.render_widget(block, list_area);
+            if self.is_editing_filter || !self.filter_state.text().is_empty() {
+                let areas = Layout::vertical(vec![Constraint::Length(1), Constraint::Min(0)])
+                    .split(inner_list_area);
+                let filter_line = CommandLine::default().no_header();
+                f.render_stateful_widget(filter_line, areas[0], &mut self.filter_state);
+                inner_list_area = areas[1];
+            }
             f.render_stateful_widget(list, inner_list_area, &mut self.list_state);
+            self.list_area = inner_list_area;
             self.render_item_detail(f, item_detail_area);
         } else {
             let rect = Layout::vertical(vec![
@@ -165,37 +258,54 @@ impl Component for InstanceHierViewer {
         &mut self,
         key_event: &crossterm::event::KeyEvent,
     ) -> crate::component::HandleResult {
-        match key_event.code {
-            KeyCode::Char('q') => {
-                if self.root_node.is_some() && !self.signals_marked_to_add.is_empty()
-                    || !self.signals_marked_to_remove.is_empty()
-                {
-                    self.focused_child = Some(Child::Confirmer);
-                    let mut text =
-                        String::from("Would you like to apply the following changes?\n\n");
-                    for s in &self.signals_marked_to_add {
-                        text += &format!("(+) {s}\n");
-                    }
-                    for s in &self.signals_marked_to_remove {
-                        text += &format!("(-) {s}\n");
-                    }
-                    self.confirmer.write().unwrap().set_text(&text);
-                } else {
-                    self.notify_render();
-                    return HandleResult::ReleaseFocus;
-                }
+        if self.is_editing_filter {
+            self.edit_filter(key_event.code);
+            self.notify_render();
+            return HandleResult::Handled;
+        }
+        match self.key_bindings.press(KeyId::from(key_event)) {
+            KeyPress::Action(action) => self.perform(action),
+            KeyPress::Unbound if matches!(key_event.code, KeyCode::F(_)) => {
+                HandleResult::NotHandled
             }
-            KeyCode::Enter => self.perform_action_on_selected(),
-            KeyCode::Down | KeyCode::Char('j') => self.scroll_down(),
-            KeyCode::Up | KeyCode::Char('k') => self.scroll_up(),
-            KeyCode::F(_) => return HandleResult::NotHandled,
-            _ => (),
+            KeyPress::Pending | KeyPress::Unbound => HandleResult::Handled,
         }
+    }
+
+    fn handle_resize_event(&mut self, _columns: u16, _rows: u16) -> HandleResult {
         self.notify_render();
         HandleResult::Handled
     }
 
-    fn handle_resize_event(&mut self, _columns: u16, _rows: u16) -> HandleResult {
+    fn handle_mouse_event(&mut self, mouse_event: &MouseEvent) -> HandleResult {
+        if self.focused_child.is_some() {
+            return HandleResult::NotHandled;
+        }
+        match mouse_event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let position = Position::new(mouse_event.column, mouse_event.row);
+                let item_heights = vec![1; self.items_in_list.len()];
+                let idx = utils::layout::get_list_item_at(
+                    self.list_area,
+                    self.list_state.offset(),
+                    item_heights,
+                    position,
+                );
+                match idx {
+                    Some(idx) if self.selected_item_idx == Some(idx) => {
+                        self.perform_action_on_selected()
+                    }
+                    Some(idx) => {
+                        self.list_state.select(Some(idx));
+                        self.selected_item_idx = Some(idx);
+                    }
+                    None => return HandleResult::NotHandled,
+                }
+            }
+            MouseEventKind::ScrollUp => self.scroll_up(),
+            MouseEventKind::ScrollDown => self.scroll_down(),
+            _ => return HandleResult::NotHandled,
+        }
         self.notify_render();
         HandleResult::Handled
     }
@@ -235,12 +345,66 @@ impl InstanceHierViewer {
         self.confirmer.write().unwrap().render(f, rect);
     }
 
+    fn filter_pattern(&self) -> Option<Pattern> {
+        // An incomplete regular expression does not filter the list while it is being typed
+        Some(self.filter_state.text())
+            .filter(|text| !text.is_empty())
+            .and_then(|text| Pattern::new(text).ok())
+    }
+
+    fn edit_filter(&mut self, key_code: KeyCode) {
+        match key_code {
+            KeyCode::Enter => self.is_editing_filter = false,
+            KeyCode::Esc => {
+                self.is_editing_filter = false;
+                self.filter_state.clear();
+            }
+            KeyCode::Char(c) => self.filter_state.put(c),
+            KeyCode::Backspace => self.filter_state.backspace(),
+            KeyCode::Right => self.filter_state.move_cursor_right(),
+            KeyCode::Left => self.filter_state.move_cursor_left(),
+            _ => return,
+        }
+        self.selected_item_idx = Some(0);
+        self.list_state.select_first();
+    }
+
+    /// Marks every signal in the list, i.e. every match while filtering, for adding or removal.
+    fn mark_all_shown(&mut self, is_add: bool) {
+        for item in self.items_in_list.iter() {
+            if let HierItem::Signal(leaf) = item {
+                let mut leaf = leaf.write().unwrap();
+                if !matches!(leaf.marker, Marker::NotMarked) || leaf.is_added == is_add {
+                    continue;
+                }
+                if is_add {
+                    self.signals_marked_to_add.insert(leaf.path.clone());
+                    leaf.marker = Marker::MarkedForAdd;
+                } else {
+                    self.signals_marked_to_remove.insert(leaf.path.clone());
+                    leaf.marker = Marker::MarkedForRemove;
+                }
+            }
+        }
+    }
+
     fn get_flattened_hierarchy(
         node: &Arc<RwLock<InstanceHierNode>>,
+        filter: Option<&Pattern>,
     ) -> (Vec<ListItem<'static>>, Vec<HierItem>) {
         let mut list_items = vec![];
         let mut items_in_list = vec![];
-        Self::traverse_hier_node(node, &mut list_items, &mut items_in_list, 0);
+        match filter {
+            Some(filter) => Self::traverse_filtered_hier_node(
+                node,
+                &mut list_items,
+                &mut items_in_list,
+                0,
+                filter,
+                false,
+            ),
+            None => Self::traverse_hier_node(node, &mut list_items, &mut items_in_list, 0),
+        }
         (list_items, items_in_list)
     }
 
@@ -250,7 +414,8 @@ impl InstanceHierViewer {
         items_in_list: &mut Vec<HierItem>,
         depth: usize,
     ) {
-        list_items.push(Self::new_instance_list_item(node, depth));
+        let is_expanded = node.read().unwrap().is_expanded;
+        list_items.push(Self::new_instance_list_item(node, depth, is_expanded));
         items_in_list.push(HierItem::Instance(node.clone()));
         let node = node.read().unwrap();
         if node.is_expanded {
@@ -264,13 +429,49 @@ impl InstanceHierViewer {
         }
     }
 
+    /// Lists the instances whose path matches the filter with all of their signals, and the
+    /// signals whose path matches along with the instances containing them.
+    fn traverse_filtered_hier_node(
+        node: &Arc<RwLock<InstanceHierNode>>,
+        list_items: &mut Vec<ListItem>,
+        items_in_list: &mut Vec<HierItem>,
+        depth: usize,
+        filter: &Pattern,
+        is_parent_matched: bool,
+    ) {
+        if !node.read().unwrap().has_match(filter, is_parent_matched) {
+            return;
+        }
+        list_items.push(Self::new_instance_list_item(node, depth, true));
+        items_in_list.push(HierItem::Instance(node.clone()));
+        let node = node.read().unwrap();
+        let is_matched = is_parent_matched || filter.matches(&node.path);
+        for leaf in node.leafs.iter() {
+            if is_matched || filter.matches(&leaf.read().unwrap().path) {
+                list_items.push(Self::new_signal_list_item(leaf, depth + 1));
+                items_in_list.push(HierItem::Signal(leaf.clone()));
+            }
+        }
+        for node in node.children.iter() {
+            Self::traverse_filtered_hier_node(
+                node,
+                list_items,
+                items_in_list,
+                depth + 1,
+                filter,
+                is_matched,
+            );
+        }
+    }
+
     fn new_instance_list_item<'a>(
         node: &Arc<RwLock<InstanceHierNode>>,
         depth: usize,
+        is_expanded: bool,
     ) -> ListItem<'a> {
         let node = node.read().unwrap();
         let indentation = " ".repeat(depth * 2);
-        let expand_or_collapse_symbol = if node.is_expanded { "[-]" } else { "[+]" };
+        let expand_or_collapse_symbol = if is_expanded { "[-]" } else { "[+]" };
         let line = Line::raw(format!(
             "{}{} {} ({})",
             indentation, expand_or_collapse_symbol, node.path, node.module_name
@@ -337,11 +538,9 @@ impl InstanceHierViewer {
     }
 
     fn get_selected_item(&self) -> Option<&HierItem> {
-        if let Some(idx) = self.selected_item_idx {
-            Some(&self.items_in_list[idx])
-        } else {
-            None
-        }
+        // The list is empty when nothing matches the filter
+        self.selected_item_idx
+            .and_then(|idx| self.items_in_list.get(idx))
     }
 
     fn request_modify_probe_points(&self) {
@@ -365,7 +564,8 @@ impl InstanceHierViewer {
         if let Some(idx) = self.selected_item_idx {
             self.list_state.select_next();
             let new_idx = usize::saturating_add(idx, 1);
-            self.selected_item_idx = Some(usize::min(self.items_in_list.len() - 1, new_idx));
+            let last_idx = self.items_in_list.len().saturating_sub(1);
+            self.selected_item_idx = Some(usize::min(last_idx, new_idx));
         }
     }
 
@@ -498,6 +698,19 @@ impl InstanceHierNode {
         }
     }
 
+    fn has_match(&self, filter: &Pattern, is_parent_matched: bool) -> bool {
+        is_parent_matched
+            || filter.matches(&self.path)
+            || self
+                .leafs
+                .iter()
+                .any(|leaf| filter.matches(&leaf.read().unwrap().path))
+            || self
+                .children
+                .iter()
+                .any(|node| node.read().unwrap().has_match(filter, false))
+    }
+
     fn instance_name(&self) -> String {
         self.path.split(".").last().unwrap().to_string()
     }
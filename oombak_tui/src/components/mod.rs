@@ -5,12 +5,15 @@ mod instance_hier_viewer;
 mod key_maps_viewer;
 pub mod models;
 mod periodic_signal_setter;
+mod request_status_viewer;
 mod root;
+mod search_viewer;
 mod selector;
 mod signal_display_properties_setter;
 mod signal_properties_editor;
 mod signal_value_setter;
 mod signals_viewer;
+mod transaction_viewer;
 mod wave_viewer;
 
 pub use command_interpreter::CommandInterpreter;
@@ -18,8 +21,11 @@ pub use confirmer::Confirmer;
 pub use file_explorer::FileExplorer;
 pub use instance_hier_viewer::InstanceHierViewer;
 pub use key_maps_viewer::KeyMapsViewer;
+pub use request_status_viewer::RequestStatusViewer;
 pub use root::Root;
+pub use search_viewer::SearchViewer;
 pub use signals_viewer::SignalsViewer;
+pub use transaction_viewer::TransactionViewer;
 pub use wave_viewer::WaveViewer;
 
 use tokio::sync::mpsc::Sender as TokioSender;
@@ -1,11 +1,38 @@
-use crate::utils::bitvec_str;
+use std::{collections::BTreeMap, sync::Arc};
+
+use bitvec::vec::BitVec;
+use oombak_sim::response::{CompactWaveValue, Wave};
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    backend::{
+        decoder::{self, Transaction},
+        expression,
+    },
+    plugin::ValueFormatter,
+    utils::{
+        bitvec_str::{self, EnumMap},
+        pattern::Pattern,
+    },
+};
 
 #[derive(Default, Clone)]
 pub struct SimulationSpec {
     pub wave_specs: Vec<WaveSpec>,
     pub total_time: usize,
+    /// Left at 0 by simulators not reporting it, read through `time_step_ps()`
     pub time_step_ps: usize,
     pub zoom: u8,
+    pub markers: Vec<Marker>,
+    pub reference_marker: Option<String>,
+    pub derived_signals: Vec<DerivedSignal>,
+    /// Rows removed from view without un-probing their signals.
+    pub hidden_wave_specs: Vec<WaveSpec>,
+    /// Pattern narrowing down the rows shown in the signal list, compiled once by `set_filter`.
+    pub filter: Option<(String, Arc<Pattern>)>,
+    pub enum_maps: Vec<Arc<EnumMap>>,
+    pub decoders: Vec<DecoderSpec>,
 }
 
 #[derive(Clone)]
@@ -15,9 +42,56 @@ pub struct WaveSpec {
     pub plot_type: PlotType,
     pub radix: bitvec_str::Radix,
     pub signed: bool,
+    pub fraction_bits: usize,
+    pub enum_map: Option<Arc<EnumMap>>,
+    /// Formatter of values displayed with the Custom radix, registered by a plugin
+    pub formatter: Option<Arc<dyn ValueFormatter>>,
+    /// Bounds of an analog plot. An unset bound follows the values in view.
+    pub analog_min: Option<f64>,
+    pub analog_max: Option<f64>,
+    /// Draws analog plots as straight lines between value changes instead of steps.
+    pub interpolate: bool,
+    /// Color of the waveform, in place of the theme's
+    pub color: Option<Color>,
+    /// Set on group header rows, which gather the rows below them up to the next header.
+    pub group: Option<Group>,
+}
+
+#[derive(Clone, Default)]
+pub struct Group {
+    /// Rows of a collapsed group, kept out of the list until the group is expanded.
+    pub collapsed_rows: Vec<WaveSpec>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Marker {
+    pub name: String,
+    pub time: usize,
 }
 
-#[derive(Copy, Clone)]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DerivedSignal {
+    pub name: String,
+    pub expression: String,
+    /// The bus this signal is a bit or bit range of, shown as a child row of the bus.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+}
+
+/// A protocol decoder, shown as a row of the transactions decoded from the signals bound in its
+/// arguments.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DecoderSpec {
+    pub name: String,
+    pub protocol: String,
+    #[serde(default)]
+    pub arguments: BTreeMap<String, String>,
+    #[serde(skip)]
+    pub transactions: Vec<Transaction>,
+}
+
+#[derive(Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum PlotType {
     Digital,
     Analog,
@@ -30,17 +104,18 @@ impl SimulationSpec {
             total_time: simulation_result.current_time,
             time_step_ps: 1,
             zoom: 2,
+            markers: vec![],
+            reference_marker: None,
+            derived_signals: vec![],
+            hidden_wave_specs: vec![],
+            filter: None,
+            enum_maps: vec![],
+            decoders: vec![],
         };
         spec.wave_specs = simulation_result
             .waves
             .iter()
-            .map(|w| WaveSpec {
-                wave: w.clone(),
-                height: 1,
-                plot_type: PlotType::Digital,
-                radix: bitvec_str::Radix::Binary,
-                signed: true,
-            })
+            .map(|w| WaveSpec::new(w.clone()))
             .collect();
         spec
     }
@@ -51,6 +126,9 @@ impl SimulationSpec {
         self.total_time = sim_spec.total_time;
         self.time_step_ps = sim_spec.time_step_ps;
         self.zoom = sim_spec.zoom;
+        self.hidden_wave_specs.clear();
+        self.update_derived_waves();
+        self.update_decoded_waves();
     }
 
     pub fn reset(&mut self) {
@@ -58,35 +136,868 @@ impl SimulationSpec {
         self.total_time = 0;
         self.time_step_ps = 1;
         self.zoom = 0;
+        self.markers.clear();
+        self.reference_marker = None;
+        self.derived_signals.clear();
+        self.hidden_wave_specs.clear();
+        self.decoders.clear();
     }
 
     pub fn update_with(&mut self, simulation_result: &oombak_sim::response::SimulationResult) {
         self.time_step_ps = simulation_result.time_step_ps;
         self.total_time = simulation_result.current_time;
-        self.wave_specs = simulation_result
-            .waves
-            .iter()
-            .zip(self.wave_specs.iter_mut())
-            .map(|(w, s)| WaveSpec {
-                wave: w.clone(),
-                ..s.clone()
-            })
-            .collect();
+        let is_kept = |signal_name: &str| {
+            self.derived_signals.iter().any(|d| d.name == signal_name)
+                || self.decoders.iter().any(|d| d.name == signal_name)
+        };
+        update_wave_specs(&mut self.wave_specs, is_kept, simulation_result);
+        for wave_spec in self.wave_specs.iter_mut() {
+            if let Some(group) = wave_spec.group.as_mut() {
+                update_wave_specs(&mut group.collapsed_rows, is_kept, simulation_result);
+            }
+        }
+        update_wave_specs(&mut self.hidden_wave_specs, is_kept, simulation_result);
+        // Signals probed since the last result
+        for wave in simulation_result.waves.iter() {
+            if self.get_wave_spec(&wave.signal_name).is_none() {
+                self.wave_specs.push(WaveSpec::new(wave.clone()));
+            }
+        }
+        self.update_derived_waves();
+        self.update_decoded_waves();
     }
 
     pub fn is_empty(&self) -> bool {
-        self.wave_specs.is_empty()
+        self.all_wave_specs().next().is_none()
     }
 
-    pub fn get_wave_spec(&self, signal_name: &str) -> Option<&WaveSpec> {
+    /// Duration of a simulation time step, 1 ps when the simulator does not report it.
+    pub fn time_step_ps(&self) -> usize {
+        self.time_step_ps.max(1)
+    }
+
+    /// Returns the rows of the list whose name matches the filter.
+    pub fn shown_wave_specs(&self) -> Vec<&WaveSpec> {
         self.wave_specs
             .iter()
+            .filter(|ws| {
+                self.filter
+                    .as_ref()
+                    .is_none_or(|(_, pattern)| pattern.matches(&ws.wave.signal_name))
+            })
+            .collect()
+    }
+
+    pub fn filter(&self) -> Option<&str> {
+        self.filter.as_ref().map(|(text, _)| &text[..])
+    }
+
+    pub fn set_filter(&mut self, filter: Option<&str>) -> Result<(), String> {
+        self.filter = match filter {
+            Some(text) => Some((text.to_string(), Arc::new(Pattern::new(text)?))),
+            None => None,
+        };
+        Ok(())
+    }
+
+    /// Looks up a signal, including those in collapsed groups and hidden ones.
+    pub fn get_wave_spec(&self, signal_name: &str) -> Option<&WaveSpec> {
+        self.all_wave_specs()
             .find(|ws| ws.wave.signal_name == signal_name)
     }
 
     pub fn get_wave_spec_mut(&mut self, signal_name: &str) -> Option<&mut WaveSpec> {
+        self.all_wave_specs_mut()
+            .find(|ws| ws.wave.signal_name == signal_name)
+    }
+
+    /// Removes a signal from wherever it is placed and returns it.
+    pub fn take_wave_spec(&mut self, signal_name: &str) -> Option<WaveSpec> {
+        let has_name = |ws: &WaveSpec| ws.group.is_none() && ws.wave.signal_name == signal_name;
+        if let Some(idx) = self.wave_specs.iter().position(has_name) {
+            return Some(self.wave_specs.remove(idx));
+        }
+        if let Some(idx) = self.hidden_wave_specs.iter().position(has_name) {
+            return Some(self.hidden_wave_specs.remove(idx));
+        }
         self.wave_specs
             .iter_mut()
-            .find(|ws| ws.wave.signal_name == signal_name)
+            .filter_map(|ws| ws.group.as_mut())
+            .find_map(|group| {
+                let idx = group.collapsed_rows.iter().position(has_name)?;
+                Some(group.collapsed_rows.remove(idx))
+            })
+    }
+
+    fn all_wave_specs(&self) -> impl Iterator<Item = &WaveSpec> {
+        self.wave_specs
+            .iter()
+            .flat_map(|ws| match &ws.group {
+                Some(group) => group.collapsed_rows.iter().collect(),
+                None => vec![ws],
+            })
+            .chain(self.hidden_wave_specs.iter())
+    }
+
+    fn all_wave_specs_mut(&mut self) -> impl Iterator<Item = &mut WaveSpec> {
+        self.wave_specs
+            .iter_mut()
+            .flat_map(|ws| match ws.group {
+                Some(ref mut group) => group.collapsed_rows.iter_mut().collect(),
+                None => vec![ws],
+            })
+            .chain(self.hidden_wave_specs.iter_mut())
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.wave_specs
+            .iter()
+            .position(|ws| ws.wave.signal_name == name)
+    }
+
+    /// Inserts a group header above the row named `before`, or at the end of the list.
+    pub fn add_group(&mut self, name: &str, before: Option<&str>) -> Result<(), String> {
+        if self.position(name).is_some() || self.get_wave_spec(name).is_some() {
+            return Err(format!("{name} is already in the signal list"));
+        }
+        let idx = before
+            .and_then(|before| self.position(before))
+            .unwrap_or(self.wave_specs.len());
+        self.wave_specs.insert(idx, WaveSpec::group(name));
+        Ok(())
+    }
+
+    /// Removes a group header, leaving its rows in place.
+    pub fn remove_group(&mut self, name: &str) -> bool {
+        if !self.is_group(name) {
+            return false;
+        }
+        if self.is_collapsed(name) {
+            self.toggle_group(name);
+        }
+        self.wave_specs
+            .retain(|ws| ws.group.is_none() || ws.wave.signal_name != name);
+        true
+    }
+
+    /// Collapses or expands a group, returning whether `name` is a group.
+    pub fn toggle_group(&mut self, name: &str) -> bool {
+        let idx = match self.position(name) {
+            Some(idx) if self.wave_specs[idx].group.is_some() => idx,
+            _ => return false,
+        };
+        let collapsed_rows = &mut self.wave_specs[idx].group.as_mut().unwrap().collapsed_rows;
+        if collapsed_rows.is_empty() {
+            let end = self.wave_specs[idx + 1..]
+                .iter()
+                .position(|ws| ws.group.is_some())
+                .map_or(self.wave_specs.len(), |i| idx + 1 + i);
+            let rows: Vec<WaveSpec> = self.wave_specs.drain(idx + 1..end).collect();
+            self.wave_specs[idx].group.as_mut().unwrap().collapsed_rows = rows;
+        } else {
+            let rows = std::mem::take(collapsed_rows);
+            self.wave_specs.splice(idx + 1..idx + 1, rows);
+        }
+        true
+    }
+
+    pub fn is_group(&self, name: &str) -> bool {
+        self.wave_specs
+            .iter()
+            .any(|ws| ws.group.is_some() && ws.wave.signal_name == name)
+    }
+
+    pub fn is_collapsed(&self, name: &str) -> bool {
+        self.wave_specs.iter().any(|ws| {
+            ws.wave.signal_name == name
+                && ws
+                    .group
+                    .as_ref()
+                    .is_some_and(|group| !group.collapsed_rows.is_empty())
+        })
+    }
+
+    pub fn move_up(&mut self, name: &str) -> bool {
+        match self.position(name) {
+            Some(idx) if idx > 0 => {
+                self.wave_specs.swap(idx - 1, idx);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn move_down(&mut self, name: &str) -> bool {
+        match self.position(name) {
+            Some(idx) if idx + 1 < self.wave_specs.len() => {
+                self.wave_specs.swap(idx, idx + 1);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Removes a signal from the list while keeping it probed.
+    pub fn hide(&mut self, signal_name: &str) -> bool {
+        if self.is_hidden(signal_name) {
+            return false;
+        }
+        match self.take_wave_spec(signal_name) {
+            Some(wave_spec) => {
+                self.hidden_wave_specs.push(wave_spec);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Puts a hidden signal back at the end of the list.
+    pub fn show(&mut self, signal_name: &str) -> bool {
+        match self
+            .hidden_wave_specs
+            .iter()
+            .position(|ws| ws.wave.signal_name == signal_name)
+        {
+            Some(idx) => {
+                let wave_spec = self.hidden_wave_specs.remove(idx);
+                self.wave_specs.push(wave_spec);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn is_hidden(&self, signal_name: &str) -> bool {
+        self.hidden_wave_specs
+            .iter()
+            .any(|ws| ws.wave.signal_name == signal_name)
+    }
+
+    /// Adds a copy of a row below it, named `<signal name>#2`, `#3`, and so on, whose display
+    /// properties can be set independently. Returns the name of the copy.
+    pub fn duplicate(&mut self, signal_name: &str) -> Result<String, String> {
+        let wave_spec = match self.position(signal_name) {
+            Some(idx) if self.wave_specs[idx].group.is_none() => self.wave_specs[idx].clone(),
+            Some(_) => return Err(format!("{signal_name} is a group")),
+            None => return Err(format!("unknown signal \"{signal_name}\"")),
+        };
+        let expression = match self.derived_signals.iter().find(|d| d.name == signal_name) {
+            Some(derived_signal) => derived_signal.expression.clone(),
+            None => signal_name.to_string(),
+        };
+        let original_name = signal_name
+            .split_once('#')
+            .map_or(signal_name, |(name, _)| name);
+        let name = (2..)
+            .map(|i| format!("{original_name}#{i}"))
+            .find(|name| self.get_wave_spec(name).is_none())
+            .unwrap();
+        self.define_derived_signal(DerivedSignal {
+            name: name.clone(),
+            expression,
+            parent: None,
+        })?;
+
+        let wave_spec = WaveSpec {
+            wave: self.take_wave_spec(&name).unwrap().wave,
+            ..wave_spec
+        };
+        let idx = self.position(signal_name).unwrap();
+        self.wave_specs.insert(idx + 1, wave_spec);
+        Ok(name)
+    }
+
+    /// Defines (or redefines) a signal whose wave is computed from an expression over other waves.
+    pub fn add_derived_signal(&mut self, name: &str, expression: &str) -> Result<(), String> {
+        self.define_derived_signal(DerivedSignal {
+            name: name.to_string(),
+            expression: expression.to_string(),
+            parent: None,
+        })
+    }
+
+    pub fn remove_derived_signal(&mut self, name: &str) -> bool {
+        if !self.is_derived(name) {
+            return false;
+        }
+        self.collapse_bus(name);
+        self.derived_signals.retain(|d| d.name != name);
+        self.take_wave_spec(name);
+        true
+    }
+
+    /// Shows each bit of a bus as a child row, most significant bit first.
+    pub fn expand_bus(&mut self, signal_name: &str) -> Result<(), String> {
+        let width = match self.get_wave_spec(signal_name) {
+            Some(wave_spec) => wave_spec.wave.width,
+            None => return Err(format!("unknown signal \"{signal_name}\"")),
+        };
+        if width < 2 {
+            return Err(format!("{signal_name} is not a bus"));
+        }
+        for bit in (0..width).rev() {
+            self.add_bus_member(signal_name, bit, bit)?;
+        }
+        Ok(())
+    }
+
+    /// Shows the bit range `msb` to `lsb` of a bus as a child row.
+    pub fn add_bus_group(
+        &mut self,
+        signal_name: &str,
+        msb: usize,
+        lsb: usize,
+    ) -> Result<(), String> {
+        let width = match self.get_wave_spec(signal_name) {
+            Some(wave_spec) => wave_spec.wave.width,
+            None => return Err(format!("unknown signal \"{signal_name}\"")),
+        };
+        if msb >= width {
+            return Err(format!("bit {msb} is out of range for {signal_name}"));
+        }
+        self.add_bus_member(signal_name, msb, lsb)
+    }
+
+    /// Removes the child rows of a bus, returning whether it had any.
+    pub fn collapse_bus(&mut self, signal_name: &str) -> bool {
+        let members: Vec<String> = self
+            .bus_members(signal_name)
+            .map(|d| d.name.clone())
+            .collect();
+        for member in members.iter() {
+            self.remove_derived_signal(member);
+        }
+        !members.is_empty()
+    }
+
+    pub fn is_expanded(&self, signal_name: &str) -> bool {
+        self.bus_members(signal_name).next().is_some()
+    }
+
+    pub fn parent_of(&self, signal_name: &str) -> Option<&str> {
+        self.derived_signals
+            .iter()
+            .find(|d| d.name == signal_name)
+            .and_then(|d| d.parent.as_deref())
+    }
+
+    fn bus_members<'a>(&'a self, signal_name: &'a str) -> impl Iterator<Item = &'a DerivedSignal> {
+        self.derived_signals
+            .iter()
+            .filter(move |d| d.parent.as_deref() == Some(signal_name))
+    }
+
+    fn add_bus_member(&mut self, signal_name: &str, msb: usize, lsb: usize) -> Result<(), String> {
+        if self.position(signal_name).is_none() {
+            return Err(format!("{signal_name} is not shown in the signal list"));
+        }
+        let name = if msb == lsb {
+            format!("{signal_name}[{msb}]")
+        } else {
+            format!("{signal_name}[{msb}:{lsb}]")
+        };
+        self.define_derived_signal(DerivedSignal {
+            name: name.clone(),
+            expression: format!("{signal_name}[{msb}:{lsb}]"),
+            parent: Some(signal_name.to_string()),
+        })?;
+
+        // Place the new row after the bus and its existing child rows
+        let wave_spec = self.take_wave_spec(&name).unwrap();
+        let parent_idx = self.position(signal_name).unwrap();
+        let insert_idx = self.wave_specs[parent_idx + 1..]
+            .iter()
+            .position(|ws| self.parent_of(&ws.wave.signal_name) != Some(signal_name))
+            .map_or(self.wave_specs.len(), |i| parent_idx + 1 + i);
+        self.wave_specs.insert(insert_idx, wave_spec);
+        Ok(())
+    }
+
+    fn define_derived_signal(&mut self, derived_signal: DerivedSignal) -> Result<(), String> {
+        let name = &derived_signal.name;
+        if self.is_group(name) {
+            return Err(format!("{name} is a group"));
+        }
+        if self.get_wave_spec(name).is_some() && !self.is_derived(name) {
+            return Err(format!("{name} is already a probed signal"));
+        }
+        let wave = self.evaluate_derived_wave(name, &derived_signal.expression)?;
+        self.derived_signals.retain(|d| d.name != *name);
+        self.derived_signals.push(derived_signal);
+        self.set_derived_wave(wave);
+        Ok(())
+    }
+
+    pub fn is_derived(&self, signal_name: &str) -> bool {
+        self.derived_signals.iter().any(|d| d.name == signal_name)
+    }
+
+    /// Recomputes derived waves. A derived signal whose operands are no longer probed is hidden
+    /// until they are probed again.
+    pub fn update_derived_waves(&mut self) {
+        for derived_signal in self.derived_signals.clone() {
+            match self.evaluate_derived_wave(&derived_signal.name, &derived_signal.expression) {
+                Ok(wave) => self.set_derived_wave(wave),
+                Err(_) => {
+                    self.take_wave_spec(&derived_signal.name);
+                }
+            }
+        }
+    }
+
+    fn evaluate_derived_wave(&self, name: &str, expression: &str) -> Result<Wave, String> {
+        let intervals = expression::parse(expression)?.evaluate_intervals(self)?;
+        let width = intervals.first().map_or(1, |(_, _, value)| value.len());
+        let mut wave = Wave::new(name.to_string(), width);
+        for (start_time, end_time, mut value) in intervals {
+            value.resize(width, false);
+            wave.append(CompactWaveValue::new(value, end_time - start_time + 1));
+        }
+        Ok(wave)
+    }
+
+    fn set_derived_wave(&mut self, wave: Wave) {
+        match self.get_wave_spec_mut(&wave.signal_name) {
+            Some(wave_spec) => wave_spec.wave = wave,
+            None => self.wave_specs.push(WaveSpec::new(wave)),
+        }
+    }
+
+    /// Decodes the signals bound in `arguments` into a row of transactions, placed above the first
+    /// of them, or redefines the decoder of that name. Returns the number of transactions.
+    pub fn add_decoder(
+        &mut self,
+        name: &str,
+        protocol: &str,
+        arguments: BTreeMap<String, String>,
+    ) -> Result<usize, String> {
+        if self.is_group(name) {
+            return Err(format!("{name} is a group"));
+        }
+        if self.get_wave_spec(name).is_some() && !self.is_decoder(name) {
+            return Err(format!("{name} is already in the signal list"));
+        }
+        let decoded = decoder::decode(protocol, &arguments, self)?;
+        let decoder_spec = DecoderSpec {
+            name: name.to_string(),
+            protocol: protocol.to_string(),
+            arguments,
+            transactions: decoded.transactions,
+        };
+        let num_of_transactions = decoder_spec.transactions.len();
+        let is_new = !self.is_decoder(name);
+        self.set_decoded_wave(&decoder_spec);
+        self.decoders.retain(|d| d.name != name);
+        self.decoders.push(decoder_spec);
+
+        if is_new {
+            let wave_spec = self.take_wave_spec(name).unwrap();
+            let idx = decoded
+                .signal_names
+                .iter()
+                .find_map(|signal_name| self.position(signal_name))
+                .unwrap_or(self.wave_specs.len());
+            self.wave_specs.insert(idx, wave_spec);
+        }
+        Ok(num_of_transactions)
+    }
+
+    pub fn remove_decoder(&mut self, name: &str) -> bool {
+        if !self.is_decoder(name) {
+            return false;
+        }
+        self.take_wave_spec(name);
+        self.decoders.retain(|d| d.name != name);
+        true
+    }
+
+    pub fn is_decoder(&self, signal_name: &str) -> bool {
+        self.decoders.iter().any(|d| d.name == signal_name)
+    }
+
+    pub fn get_decoder(&self, name: &str) -> Option<&DecoderSpec> {
+        self.decoders.iter().find(|d| d.name == name)
+    }
+
+    /// Decodes the transactions again. A decoder whose signals are no longer probed keeps its row,
+    /// empty until they are probed again.
+    pub fn update_decoded_waves(&mut self) {
+        for idx in 0..self.decoders.len() {
+            let decoder_spec = &self.decoders[idx];
+            let decoded = decoder::decode(&decoder_spec.protocol, &decoder_spec.arguments, self);
+            self.decoders[idx].transactions =
+                decoded.map_or(vec![], |decoded| decoded.transactions);
+            let decoder_spec = self.decoders[idx].clone();
+            self.set_decoded_wave(&decoder_spec);
+        }
+    }
+
+    /// Sets the row of a decoder to a wave numbering its transactions from 1, with 0 between
+    /// them, and an enum map naming each number with the label of the transaction.
+    fn set_decoded_wave(&mut self, decoder_spec: &DecoderSpec) {
+        let transactions = &decoder_spec.transactions;
+        let width = (usize::BITS - transactions.len().leading_zeros()).max(1) as usize;
+        let value_of =
+            |n: usize| -> BitVec<u32> { (0..width).map(|i| (n >> i) & 1 == 1).collect() };
+        let mut wave = Wave::new(decoder_spec.name.clone(), width);
+        let mut entries = vec![(value_of(0), String::new())];
+        let mut time = 0;
+        for (idx, transaction) in transactions.iter().enumerate() {
+            // Overlapping transactions are cut short by the next one
+            let start_time = transaction.start_time.max(time);
+            if transaction.end_time < start_time {
+                continue;
+            }
+            if start_time > time {
+                wave.append(CompactWaveValue::new(value_of(0), start_time - time));
+            }
+            let end_time = match transactions.get(idx + 1) {
+                Some(next) if next.start_time > start_time => {
+                    transaction.end_time.min(next.start_time - 1)
+                }
+                _ => transaction.end_time,
+            };
+            wave.append(CompactWaveValue::new(
+                value_of(idx + 1),
+                end_time - start_time + 1,
+            ));
+            entries.push((value_of(idx + 1), transaction.label.clone()));
+            time = end_time + 1;
+        }
+        if self.total_time > time {
+            wave.append(CompactWaveValue::new(value_of(0), self.total_time - time));
+        }
+
+        let enum_map = Arc::new(EnumMap::new(&decoder_spec.name, entries));
+        match self.get_wave_spec_mut(&decoder_spec.name) {
+            Some(wave_spec) => {
+                wave_spec.wave = wave;
+                wave_spec.enum_map = Some(enum_map);
+            }
+            None => self.wave_specs.push(WaveSpec {
+                radix: bitvec_str::Radix::Enum,
+                enum_map: Some(enum_map),
+                ..WaveSpec::new(wave)
+            }),
+        }
+    }
+
+    /// Defines (or redefines) a value-to-name map, updating the signals displayed with it.
+    pub fn define_enum_map(&mut self, enum_map: EnumMap) {
+        let enum_map = Arc::new(enum_map);
+        self.enum_maps.retain(|m| m.name != enum_map.name);
+        self.enum_maps.push(enum_map.clone());
+        for wave_spec in self.all_wave_specs_mut() {
+            if wave_spec
+                .enum_map
+                .as_ref()
+                .is_some_and(|m| m.name == enum_map.name)
+            {
+                wave_spec.enum_map = Some(enum_map.clone());
+            }
+        }
+    }
+
+    pub fn get_enum_map(&self, name: &str) -> Option<&Arc<EnumMap>> {
+        self.enum_maps.iter().find(|m| m.name == name)
+    }
+
+    /// Adds or moves a marker, making it the reference for delta-time measurements. Unnamed
+    /// markers are named `M1`, `M2`, and so on.
+    pub fn add_marker(&mut self, name: Option<&str>, time: usize) -> String {
+        let name = match name {
+            Some(name) => name.to_string(),
+            None => (1..)
+                .map(|i| format!("M{i}"))
+                .find(|name| self.get_marker(name).is_none())
+                .unwrap(),
+        };
+        self.markers.retain(|m| m.name != name);
+        self.markers.push(Marker {
+            name: name.clone(),
+            time,
+        });
+        self.markers.sort_by_key(|m| m.time);
+        self.reference_marker = Some(name.clone());
+        name
+    }
+
+    pub fn remove_marker(&mut self, name: &str) -> bool {
+        let number_of_markers = self.markers.len();
+        self.markers.retain(|m| m.name != name);
+        if self.reference_marker.as_deref() == Some(name) {
+            self.reference_marker = self.markers.last().map(|m| m.name.clone());
+        }
+        self.markers.len() != number_of_markers
+    }
+
+    pub fn get_marker(&self, name: &str) -> Option<&Marker> {
+        self.markers.iter().find(|m| m.name == name)
+    }
+
+    pub fn reference_marker(&self) -> Option<&Marker> {
+        self.reference_marker
+            .as_deref()
+            .and_then(|name| self.get_marker(name))
+    }
+
+    /// Returns the first marker after `time`, wrapping around to the earliest marker.
+    pub fn next_marker(&self, time: usize) -> Option<&Marker> {
+        self.markers
+            .iter()
+            .find(|m| m.time > time)
+            .or(self.markers.first())
+    }
+}
+
+impl WaveSpec {
+    pub fn new(wave: Wave) -> Self {
+        Self {
+            wave,
+            height: 1,
+            plot_type: PlotType::Digital,
+            radix: bitvec_str::Radix::Binary,
+            signed: true,
+            fraction_bits: 0,
+            enum_map: None,
+            formatter: None,
+            analog_min: None,
+            analog_max: None,
+            interpolate: false,
+            color: None,
+            group: None,
+        }
+    }
+
+    pub fn group(name: &str) -> Self {
+        Self {
+            height: 0,
+            group: Some(Group::default()),
+            ..Self::new(Wave::new(name.to_string(), 0))
+        }
+    }
+}
+
+/// Updates the waves of probed signals, dropping those no longer probed. Rows for which `is_kept`
+/// holds are not probed signals, and are left as they are.
+fn update_wave_specs(
+    wave_specs: &mut Vec<WaveSpec>,
+    is_kept: impl Fn(&str) -> bool,
+    simulation_result: &oombak_sim::response::SimulationResult,
+) {
+    wave_specs.retain_mut(|ws| {
+        let signal_name = &ws.wave.signal_name;
+        if ws.group.is_some() || is_kept(signal_name) {
+            return true;
+        }
+        match simulation_result
+            .waves
+            .iter()
+            .find(|w| w.signal_name == *signal_name)
+        {
+            Some(wave) => {
+                ws.wave = wave.clone();
+                true
+            }
+            None => false,
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use bitvec::{bitvec, order::Lsb0};
+    use oombak_sim::response::{CompactWaveValue, SimulationResult, Wave};
+
+    use super::{SimulationSpec, WaveSpec};
+    use crate::widgets;
+
+    fn wave(signal_name: &str, width: usize) -> Wave {
+        let mut wave = Wave::new(signal_name.to_string(), width);
+        let mut value = bitvec![u32, Lsb0; 1, 0, 1];
+        value.resize(width, false);
+        wave.append(CompactWaveValue::new(value, 4));
+        wave
+    }
+
+    fn signal_names(simulation_spec: &SimulationSpec) -> Vec<&str> {
+        simulation_spec
+            .wave_specs
+            .iter()
+            .map(|ws| &ws.wave.signal_name[..])
+            .collect()
+    }
+
+    #[test]
+    fn test_expand_bus() {
+        let mut simulation_spec = SimulationSpec {
+            wave_specs: vec![
+                WaveSpec::new(wave("data", 3)),
+                WaveSpec::new(wave("valid", 1)),
+            ],
+            ..Default::default()
+        };
+        assert!(simulation_spec.expand_bus("valid").is_err());
+
+        simulation_spec.expand_bus("data").unwrap();
+        simulation_spec.add_bus_group("data", 1, 0).unwrap();
+        assert_eq!(
+            signal_names(&simulation_spec),
+            vec![
+                "data",
+                "data[2]",
+                "data[1]",
+                "data[0]",
+                "data[1:0]",
+                "valid"
+            ]
+        );
+        let bit = simulation_spec.get_wave_spec("data[2]").unwrap();
+        assert_eq!(bit.wave.at(0).unwrap(), &bitvec![u32, Lsb0; 1]);
+        assert_eq!(simulation_spec.parent_of("data[1:0]"), Some("data"));
+
+        assert!(simulation_spec.collapse_bus("data"));
+        assert_eq!(signal_names(&simulation_spec), vec!["data", "valid"]);
+        assert!(!simulation_spec.is_expanded("data"));
+    }
+
+    #[test]
+    fn test_add_decoder() {
+        // A rising clock edge at 2, with valid high throughout
+        let mut clk = Wave::new("clk".to_string(), 1);
+        for value in [
+            bitvec![u32, Lsb0; 0],
+            bitvec![u32, Lsb0; 1],
+            bitvec![u32, Lsb0; 0],
+        ] {
+            clk.append(CompactWaveValue::new(value, 2));
+        }
+        let valid = wave("valid", 1);
+        let mut simulation_spec = SimulationSpec {
+            wave_specs: vec![WaveSpec::new(clk), WaveSpec::new(valid)],
+            total_time: 6,
+            ..Default::default()
+        };
+        let arguments = [("clk", "clk"), ("valid", "valid")]
+            .iter()
+            .map(|(role, signal_name)| (role.to_string(), signal_name.to_string()))
+            .collect();
+        assert_eq!(simulation_spec.add_decoder("s", "stream", arguments), Ok(1));
+        assert_eq!(signal_names(&simulation_spec), vec!["s", "clk", "valid"]);
+        assert!(simulation_spec
+            .add_decoder("valid", "stream", Default::default())
+            .is_err());
+
+        // The transaction spans the clock cycle, numbered 1 and named by its label
+        let wave_spec = simulation_spec.get_wave_spec("s").unwrap();
+        let value = wave_spec.wave.at(1).unwrap();
+        assert_eq!(
+            wave_spec.enum_map.as_ref().unwrap().name_of(value),
+            Some("transfer")
+        );
+        assert_eq!(wave_spec.wave.at(5).unwrap(), &bitvec![u32, Lsb0; 0]);
+
+        // The row stays in place, empty, while a signal of the decoder is not probed
+        let valid = simulation_spec.take_wave_spec("valid").unwrap();
+        simulation_spec.update_decoded_waves();
+        assert_eq!(signal_names(&simulation_spec), vec!["s", "clk"]);
+        assert!(simulation_spec
+            .get_decoder("s")
+            .unwrap()
+            .transactions
+            .is_empty());
+        simulation_spec.wave_specs.push(valid);
+        simulation_spec.update_decoded_waves();
+        assert_eq!(signal_names(&simulation_spec), vec!["s", "clk", "valid"]);
+        assert_eq!(
+            simulation_spec.get_decoder("s").unwrap().transactions.len(),
+            1
+        );
+
+        assert!(simulation_spec.remove_decoder("s"));
+        assert!(!simulation_spec.remove_decoder("s"));
+        assert_eq!(signal_names(&simulation_spec), vec!["clk", "valid"]);
+    }
+
+    #[test]
+    fn test_arrange_rows() {
+        let mut simulation_spec = SimulationSpec {
+            wave_specs: vec![
+                WaveSpec::new(wave("clk", 1)),
+                WaveSpec::new(wave("data", 3)),
+                WaveSpec::new(wave("valid", 1)),
+            ],
+            ..Default::default()
+        };
+        simulation_spec.add_group("bus", Some("data")).unwrap();
+        assert!(simulation_spec.add_group("clk", None).is_err());
+        assert!(!simulation_spec.move_up("clk"));
+        assert!(simulation_spec.move_down("clk"));
+        assert_eq!(
+            signal_names(&simulation_spec),
+            vec!["bus", "clk", "data", "valid"]
+        );
+
+        assert!(simulation_spec.toggle_group("bus"));
+        assert_eq!(signal_names(&simulation_spec), vec!["bus"]);
+        assert!(simulation_spec.get_wave_spec("data").is_some());
+        assert!(simulation_spec.toggle_group("bus"));
+
+        assert!(simulation_spec.hide("clk"));
+        assert_eq!(signal_names(&simulation_spec), vec!["bus", "data", "valid"]);
+        assert!(simulation_spec.show("clk"));
+        assert!(!simulation_spec.show("clk"));
+
+        assert_eq!(simulation_spec.duplicate("data"), Ok("data#2".to_string()));
+        assert_eq!(
+            simulation_spec.duplicate("data#2"),
+            Ok("data#3".to_string())
+        );
+        assert_eq!(
+            signal_names(&simulation_spec),
+            vec!["bus", "data", "data#2", "data#3", "valid", "clk"]
+        );
+        assert!(simulation_spec.remove_group("bus"));
+        assert_eq!(signal_names(&simulation_spec)[0], "data");
+    }
+
+    #[test]
+    fn test_update_with_changed_probes() {
+        let mut simulation_spec = SimulationSpec::new(&SimulationResult {
+            waves: vec![wave("a", 3), wave("b", 3)],
+            time_step_ps: 1,
+            current_time: 4,
+        });
+        simulation_spec.add_derived_signal("sum", "a + b").unwrap();
+        simulation_spec.hide("b");
+
+        // b stays probed, a is removed and c added
+        simulation_spec.update_with(&SimulationResult {
+            waves: vec![wave("b", 3), wave("c", 3)],
+            time_step_ps: 1,
+            current_time: 4,
+        });
+        assert!(simulation_spec.is_derived("sum"));
+        assert!(simulation_spec.is_hidden("b"));
+        assert_eq!(signal_names(&simulation_spec), vec!["c"]);
+
+        // sum comes back once a is probed again
+        simulation_spec.update_with(&SimulationResult {
+            waves: vec![wave("a", 3), wave("b", 3), wave("c", 3)],
+            time_step_ps: 1,
+            current_time: 4,
+        });
+        assert_eq!(signal_names(&simulation_spec), vec!["c", "a", "sum"]);
+    }
+
+    #[test]
+    fn test_update_with_unreported_time_step() {
+        let mut simulation_spec = SimulationSpec::new(&SimulationResult::default());
+        simulation_spec.update_with(&SimulationResult {
+            current_time: 10,
+            ..Default::default()
+        });
+        let end_time = simulation_spec.total_time * simulation_spec.time_step_ps();
+        assert_eq!(widgets::format_time(end_time as f64), "10.00 ps");
     }
 }
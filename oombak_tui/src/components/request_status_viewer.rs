@@ -0,0 +1,390 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::Sender,
+        Arc, RwLock,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{KeyEvent, MouseEvent, MouseEventKind};
+use oombak_sim::response::{Notifications, Payload};
+use ratatui::{
+    layout::{Constraint, Rect},
+    text::Line,
+    widgets::{Cell, Row, Table, TableState},
+    Frame,
+};
+
+use crate::{
+    backend::key_bindings::{ActionInfo, KeyBindings, KeyPress},
+    component::{Component, HandleResult},
+    styles::{
+        global::SELECTED_ITEM_STYLE,
+        request_status_viewer::{
+            DISPATCHED_STYLE, ERROR_STYLE, FINISHED_STYLE, HEADER_STYLE, IN_PROGRESS_STYLE,
+        },
+    },
+    threads::{simulator_request_dispatcher, RendererMessage},
+    widgets::{KeyId, KeyMaps},
+};
+
+/// Requests kept after they are done, the oldest being dropped first
+const MAX_NUM_OF_DONE_REQUESTS: usize = 100;
+const PROGRESS_BAR_WIDTH: usize = 15;
+/// Period of redraws while requests are pending, to keep the elapsed times current
+const TICK_PERIOD: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy)]
+enum Action {
+    Close,
+    PreviousRequest,
+    NextRequest,
+    ClearDone,
+}
+
+const ACTIONS: [ActionInfo<Action>; 4] = [
+    ActionInfo {
+        action: Action::Close,
+        name: "close",
+        description: "close window",
+        default_keys: &["esc", "q", "ctrl-d"],
+    },
+    ActionInfo {
+        action: Action::PreviousRequest,
+        name: "previous-request",
+        description: "previous request",
+        default_keys: &["up"],
+    },
+    ActionInfo {
+        action: Action::NextRequest,
+        name: "next-request",
+        description: "next request",
+        default_keys: &["down"],
+    },
+    ActionInfo {
+        action: Action::ClearDone,
+        name: "clear-done",
+        description: "clear done",
+        default_keys: &["c"],
+    },
+];
+
+/// Lists the requests sent to the simulator, with their state and progress.
+pub struct RequestStatusViewer {
+    message_tx: Sender<RendererMessage>,
+    requests: Vec<RequestStatus>,
+    num_of_pending_requests: Arc<AtomicUsize>,
+    table_state: TableState,
+    key_bindings: KeyBindings<Action>,
+    key_mappings: KeyMaps,
+}
+
+struct RequestStatus {
+    id: usize,
+    summary: String,
+    state: RequestState,
+    dispatched_at: Instant,
+    done_at: Option<Instant>,
+    /// Fraction of the work completed, if reported
+    progress: Option<f32>,
+    message: String,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum RequestState {
+    Dispatched,
+    InProgress,
+    Finished,
+    Error,
+}
+
+impl RequestStatusViewer {
+    pub fn new(message_tx: Sender<RendererMessage>) -> Self {
+        let key_bindings = KeyBindings::load_or_defaults(&ACTIONS, "requests");
+        let key_mappings = key_bindings.key_maps();
+        Self {
+            message_tx,
+            requests: vec![],
+            num_of_pending_requests: Arc::new(AtomicUsize::new(0)),
+            table_state: TableState::default(),
+            key_bindings,
+            key_mappings,
+        }
+    }
+
+    fn add_request(&mut self, id: usize, summary: String) {
+        self.requests.push(RequestStatus {
+            id,
+            summary,
+            state: RequestState::Dispatched,
+            dispatched_at: Instant::now(),
+            done_at: None,
+            progress: None,
+            message: String::new(),
+        });
+        let num_of_done_requests = self.requests.iter().filter(|r| r.is_done()).count();
+        if num_of_done_requests > MAX_NUM_OF_DONE_REQUESTS {
+            let idx = self.requests.iter().position(|r| r.is_done()).unwrap();
+            self.requests.remove(idx);
+        }
+        if self.num_of_pending_requests.fetch_add(1, Ordering::Relaxed) == 0 {
+            self.spawn_ticker();
+        }
+    }
+
+    fn update_request(&mut self, response: &oombak_sim::Response) {
+        let Some(request) = self.requests.iter_mut().find(|r| r.id == response.id) else {
+            return;
+        };
+        if request.is_done() {
+            return;
+        }
+        match &response.payload {
+            Payload::Notification(Notifications::Progress(percentage, message)) => {
+                request.state = RequestState::InProgress;
+                request.progress = Some(percentage.value());
+                request.message = message.clone();
+            }
+            Payload::Notification(Notifications::Generic(message)) => {
+                request.state = RequestState::InProgress;
+                request.message = message.clone();
+            }
+            Payload::Result(_) => {
+                request.state = RequestState::Finished;
+                request.message.clear();
+            }
+            Payload::Error(e) => {
+                request.state = RequestState::Error;
+                request.message = e.to_string();
+            }
+        }
+        if request.is_done() {
+            request.done_at = Some(Instant::now());
+            self.num_of_pending_requests.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    fn clear_done_requests(&mut self) {
+        self.requests.retain(|r| !r.is_done());
+        self.table_state.select(None);
+    }
+
+    /// Redraws periodically until no request is pending.
+    fn spawn_ticker(&self) {
+        let message_tx = self.message_tx.clone();
+        let num_of_pending_requests = self.num_of_pending_requests.clone();
+        thread::spawn(move || {
+            while num_of_pending_requests.load(Ordering::Relaxed) > 0 {
+                thread::sleep(TICK_PERIOD);
+                if message_tx.send(RendererMessage::Render).is_err() {
+                    return;
+                }
+            }
+        });
+    }
+
+    fn select_next(&mut self) {
+        let idx = self.table_state.selected().map_or(0, |idx| idx + 1);
+        if idx < self.requests.len() {
+            self.table_state.select(Some(idx));
+        }
+    }
+
+    fn select_previous(&mut self) {
+        let idx = self
+            .table_state
+            .selected()
+            .map_or(0, |idx| idx.saturating_sub(1));
+        self.table_state.select(Some(idx));
+    }
+
+    fn perform(&mut self, action: Action) -> HandleResult {
+        match action {
+            Action::Close => return HandleResult::ReleaseFocus,
+            Action::PreviousRequest => self.select_previous(),
+            Action::NextRequest => self.select_next(),
+            Action::ClearDone => self.clear_done_requests(),
+        }
+        self.notify_render();
+        HandleResult::Handled
+    }
+
+    fn notify_render(&self) {
+        self.message_tx.send(RendererMessage::Render).unwrap();
+    }
+}
+
+impl RequestStatus {
+    fn is_done(&self) -> bool {
+        matches!(self.state, RequestState::Finished | RequestState::Error)
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.done_at.unwrap_or_else(Instant::now) - self.dispatched_at
+    }
+
+    fn row(&self) -> Row<'static> {
+        let (state, style) = match self.state {
+            RequestState::Dispatched => ("dispatched", DISPATCHED_STYLE),
+            RequestState::InProgress => ("in progress", IN_PROGRESS_STYLE),
+            RequestState::Finished => ("finished", FINISHED_STYLE),
+            RequestState::Error => ("error", ERROR_STYLE),
+        };
+        let progress = match (self.state, self.progress) {
+            (RequestState::Finished, Some(_)) => progress_bar(1.0),
+            (_, Some(progress)) => progress_bar(progress),
+            _ => String::new(),
+        };
+        Row::new(vec![
+            Cell::from(format!("{:x}", self.id)),
+            Cell::from(self.summary.clone()),
+            Cell::from(state).style(style),
+            Cell::from(format_elapsed(self.elapsed())),
+            Cell::from(progress),
+            Cell::from(self.message.clone()),
+        ])
+    }
+}
+
+impl Component for RequestStatusViewer {
+    fn render(&mut self, f: &mut Frame, rect: Rect) {
+        if self.requests.is_empty() {
+            f.render_widget(Line::from(" No request sent yet"), rect);
+            return;
+        }
+        let header = Row::new(["ID", "Request", "State", "Elapsed", "Progress", "Status"])
+            .style(HEADER_STYLE);
+        let widths = [
+            Constraint::Length(8),
+            Constraint::Fill(1),
+            Constraint::Length(11),
+            Constraint::Length(8),
+            Constraint::Length(PROGRESS_BAR_WIDTH as u16 + 5),
+            Constraint::Fill(1),
+        ];
+        let table = Table::new(self.requests.iter().map(RequestStatus::row), widths)
+            .header(header)
+            .row_highlight_style(SELECTED_ITEM_STYLE);
+        f.render_stateful_widget(table, rect, &mut self.table_state);
+    }
+
+    fn handle_key_event(&mut self, key_event: &KeyEvent) -> HandleResult {
+        match self.key_bindings.press(KeyId::from(key_event)) {
+            KeyPress::Action(action) => self.perform(action),
+            KeyPress::Pending => HandleResult::Handled,
+            KeyPress::Unbound => HandleResult::NotHandled,
+        }
+    }
+
+    fn handle_resize_event(&mut self, _columns: u16, _rows: u16) -> HandleResult {
+        HandleResult::NotHandled
+    }
+
+    fn handle_mouse_event(&mut self, mouse_event: &MouseEvent) -> HandleResult {
+        match mouse_event.kind {
+            MouseEventKind::ScrollUp => self.select_previous(),
+            MouseEventKind::ScrollDown => self.select_next(),
+            _ => return HandleResult::NotHandled,
+        }
+        self.notify_render();
+        HandleResult::Handled
+    }
+
+    fn handle_focus_gained(&mut self) -> HandleResult {
+        HandleResult::Handled
+    }
+
+    fn get_focused_child(&self) -> Option<Arc<RwLock<dyn Component>>> {
+        None
+    }
+
+    fn get_key_mappings(&self) -> KeyMaps {
+        self.key_mappings.clone()
+    }
+}
+
+impl simulator_request_dispatcher::Listener for RequestStatusViewer {
+    fn on_receive_reponse(&mut self, response: &oombak_sim::Response) {
+        self.update_request(response);
+    }
+
+    fn on_request_dispatched(&mut self, request: &oombak_sim::Request) {
+        self.add_request(request.id, request.payload.to_string());
+    }
+}
+
+fn progress_bar(progress: f32) -> String {
+    let progress = progress.clamp(0.0, 1.0);
+    let num_of_filled = (progress * PROGRESS_BAR_WIDTH as f32).round() as usize;
+    format!(
+        "{}{} {:>3}%",
+        "█".repeat(num_of_filled),
+        "░".repeat(PROGRESS_BAR_WIDTH - num_of_filled),
+        (progress * 100.0).round() as usize
+    )
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs < 60 {
+        format!("{:.1}s", elapsed.as_secs_f32())
+    } else {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{sync::mpsc, time::Duration};
+
+    use oombak_sim::response::{Payload, Percentage};
+
+    use super::{format_elapsed, progress_bar, RequestState, RequestStatusViewer};
+
+    fn respond(viewer: &mut RequestStatusViewer, id: usize, payload: Payload) {
+        viewer.update_request(&oombak_sim::Response { id, payload });
+    }
+
+    #[test]
+    fn test_request_states() {
+        let (message_tx, _message_rx) = mpsc::channel();
+        let mut viewer = RequestStatusViewer::new(message_tx);
+        viewer.add_request(1, "Load(fifo.sv)".to_string());
+        viewer.add_request(2, "Run(10)".to_string());
+        assert_eq!(viewer.requests[0].state, RequestState::Dispatched);
+
+        let mut percentage = Percentage::new(4);
+        percentage.increment();
+        respond(
+            &mut viewer,
+            1,
+            Payload::progress("Building".to_string(), percentage),
+        );
+        assert_eq!(viewer.requests[0].state, RequestState::InProgress);
+        assert_eq!(viewer.requests[0].progress, Some(0.25));
+
+        respond(&mut viewer, 2, Payload::empty());
+        respond(
+            &mut viewer,
+            2,
+            Payload::generic_notification("late".to_string()),
+        );
+        assert_eq!(viewer.requests[1].state, RequestState::Finished);
+        respond(&mut viewer, 1, Payload::Error("build failed".into()));
+        assert_eq!(viewer.requests[0].state, RequestState::Error);
+        assert_eq!(viewer.requests[0].message, "build failed");
+
+        viewer.add_request(3, "Run(10)".to_string());
+        viewer.clear_done_requests();
+        assert_eq!(viewer.requests.len(), 1);
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(progress_bar(0.5), "████████░░░░░░░  50%");
+        assert_eq!(format_elapsed(Duration::from_millis(2340)), "2.3s");
+        assert_eq!(format_elapsed(Duration::from_secs(125)), "2m05s");
+    }
+}
@@ -1,16 +1,28 @@
-use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::Sender;
 use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
+use crate::backend::{
+    decoder,
+    interpreter::{self, FindTarget},
+    key_bindings::{self, ActionInfo, KeyBindings, KeyPress},
+    session::Session,
+};
 use crate::component::{Component, HandleResult};
-use crate::styles::root::{TITLE_STYLE, VERSION_STYLE};
+use crate::styles::{
+    root::{FILTER_STYLE, TITLE_STYLE, VERSION_STYLE},
+    theme,
+};
 use crate::threads::{simulator_request_dispatcher, RendererMessage};
-use crate::utils;
-use crate::widgets::{KeyDesc, KeyId, KeyMaps};
+use crate::utils::{self, bitvec_str::EnumMap};
+use crate::widgets::{KeyDesc, KeyId, KeyMaps, TerminalOutput};
 
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{Event, KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 
+use oombak_sim::Edge;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Position, Rect};
+use ratatui::style::Color;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, BorderType, Borders, Clear};
 use ratatui::Frame;
@@ -18,8 +30,8 @@ use ratatui::Frame;
 use super::models::SimulationSpec;
 use super::signal_properties_editor::SignalPropertiesEditor;
 use super::{
-    CommandInterpreter, FileExplorer, InstanceHierViewer, KeyMapsViewer, SignalsViewer,
-    TokioSender, WaveViewer,
+    CommandInterpreter, FileExplorer, InstanceHierViewer, KeyMapsViewer, RequestStatusViewer,
+    SearchViewer, SignalsViewer, TokioSender, TransactionViewer, WaveViewer,
 };
 
 pub struct Root {
@@ -31,11 +43,246 @@ pub struct Root {
     instance_hier_viewer: Arc<RwLock<InstanceHierViewer>>,
     command_interpreter: Arc<RwLock<CommandInterpreter>>,
     file_explorer: Arc<RwLock<FileExplorer>>,
+    search_viewer: Arc<RwLock<SearchViewer>>,
+    request_status_viewer: Arc<RwLock<RequestStatusViewer>>,
+    transaction_viewer: Arc<RwLock<TransactionViewer>>,
     signal_properties_editor: Arc<RwLock<SignalPropertiesEditor>>,
     focused_child: Option<Child>,
     simulation_spec: Arc<RwLock<SimulationSpec>>,
+    key_bindings: KeyBindings<Action>,
     key_mappings: KeyMaps,
     show_key_maps: bool,
+    loaded_design_path: Option<PathBuf>,
+    loaded_top_module: String,
+    probed_points: Vec<String>,
+    pending_session: Option<PendingSession>,
+    last_find: Option<(FindTarget, Option<String>)>,
+    /// IDs of the dispatched requests loading a design, as opposed to changing its probes
+    design_load_ids: HashSet<usize>,
+}
+
+#[derive(Clone, Copy)]
+enum Action {
+    Quit,
+    LoadSimFile,
+    OpenTerminal,
+    OpenProbeEditor,
+    OpenCommandLine,
+    SearchWaves,
+    ShowRequests,
+    ScrollUp,
+    ScrollDown,
+    ScrollRight,
+    ScrollLeft,
+    ZoomIn,
+    ZoomOut,
+    DropMarker,
+    DeleteReferenceMarker,
+    JumpToNextMarker,
+    JumpToNextChange,
+    JumpToPreviousChange,
+    JumpToNextRisingEdge,
+    JumpToPreviousRisingEdge,
+    JumpToNextFallingEdge,
+    JumpToPreviousFallingEdge,
+    ToggleBus,
+    MoveRowUp,
+    MoveRowDown,
+    HideSignal,
+    DuplicateSignal,
+    RepeatFind,
+    RepeatFindBackward,
+    OpenSignalProperties,
+}
+
+const ACTIONS: [ActionInfo<Action>; 30] = [
+    ActionInfo {
+        action: Action::Quit,
+        name: "quit",
+        description: "quit",
+        default_keys: &["q"],
+    },
+    ActionInfo {
+        action: Action::LoadSimFile,
+        name: "load-sim-file",
+        description: "load sim file",
+        default_keys: &["o"],
+    },
+    ActionInfo {
+        action: Action::OpenTerminal,
+        name: "open-terminal",
+        description: "open terminal",
+        default_keys: &["t"],
+    },
+    ActionInfo {
+        action: Action::OpenProbeEditor,
+        name: "open-probe-editor",
+        description: "open probe editor",
+        default_keys: &["s"],
+    },
+    ActionInfo {
+        action: Action::OpenCommandLine,
+        name: "open-command-line",
+        description: "open command line",
+        default_keys: &[":"],
+    },
+    ActionInfo {
+        action: Action::SearchWaves,
+        name: "search-waves",
+        description: "search waves",
+        default_keys: &["/"],
+    },
+    ActionInfo {
+        action: Action::ShowRequests,
+        name: "show-requests",
+        description: "show simulator requests",
+        default_keys: &["p"],
+    },
+    ActionInfo {
+        action: Action::ScrollUp,
+        name: "scroll-up",
+        description: "scroll up",
+        default_keys: &["up", "k"],
+    },
+    ActionInfo {
+        action: Action::ScrollDown,
+        name: "scroll-down",
+        description: "scroll down",
+        default_keys: &["down", "j"],
+    },
+    ActionInfo {
+        action: Action::ScrollRight,
+        name: "scroll-right",
+        description: "scroll right",
+        default_keys: &["l", "right"],
+    },
+    ActionInfo {
+        action: Action::ScrollLeft,
+        name: "scroll-left",
+        description: "scroll left",
+        default_keys: &["h", "left"],
+    },
+    ActionInfo {
+        action: Action::ZoomIn,
+        name: "zoom-in",
+        description: "zoom in",
+        default_keys: &["z", "+"],
+    },
+    ActionInfo {
+        action: Action::ZoomOut,
+        name: "zoom-out",
+        description: "zoom out",
+        default_keys: &["x", "-"],
+    },
+    ActionInfo {
+        action: Action::DropMarker,
+        name: "drop-marker",
+        description: "drop marker at cursor",
+        default_keys: &["m"],
+    },
+    ActionInfo {
+        action: Action::DeleteReferenceMarker,
+        name: "delete-reference-marker",
+        description: "delete reference marker",
+        default_keys: &["M"],
+    },
+    ActionInfo {
+        action: Action::JumpToNextMarker,
+        name: "jump-to-next-marker",
+        description: "jump to next marker",
+        default_keys: &["'"],
+    },
+    ActionInfo {
+        action: Action::JumpToNextChange,
+        name: "jump-to-next-change",
+        description: "jump to next value change",
+        default_keys: &["]"],
+    },
+    ActionInfo {
+        action: Action::JumpToPreviousChange,
+        name: "jump-to-previous-change",
+        description: "jump to previous value change",
+        default_keys: &["["],
+    },
+    ActionInfo {
+        action: Action::JumpToNextRisingEdge,
+        name: "jump-to-next-rising-edge",
+        description: "jump to next rising edge",
+        default_keys: &["r"],
+    },
+    ActionInfo {
+        action: Action::JumpToPreviousRisingEdge,
+        name: "jump-to-previous-rising-edge",
+        description: "jump to previous rising edge",
+        default_keys: &["R"],
+    },
+    ActionInfo {
+        action: Action::JumpToNextFallingEdge,
+        name: "jump-to-next-falling-edge",
+        description: "jump to next falling edge",
+        default_keys: &["f"],
+    },
+    ActionInfo {
+        action: Action::JumpToPreviousFallingEdge,
+        name: "jump-to-previous-falling-edge",
+        description: "jump to previous falling edge",
+        default_keys: &["F"],
+    },
+    ActionInfo {
+        action: Action::ToggleBus,
+        name: "toggle-bus",
+        description: "expand/collapse bus or group",
+        default_keys: &["e"],
+    },
+    ActionInfo {
+        action: Action::MoveRowUp,
+        name: "move-row-up",
+        description: "move row up",
+        default_keys: &["K"],
+    },
+    ActionInfo {
+        action: Action::MoveRowDown,
+        name: "move-row-down",
+        description: "move row down",
+        default_keys: &["J"],
+    },
+    ActionInfo {
+        action: Action::HideSignal,
+        name: "hide-signal",
+        description: "hide signal",
+        default_keys: &["H"],
+    },
+    ActionInfo {
+        action: Action::DuplicateSignal,
+        name: "duplicate-signal",
+        description: "duplicate signal",
+        default_keys: &["D"],
+    },
+    ActionInfo {
+        action: Action::RepeatFind,
+        name: "repeat-find",
+        description: "repeat last find",
+        default_keys: &["n"],
+    },
+    ActionInfo {
+        action: Action::RepeatFindBackward,
+        name: "repeat-find-backward",
+        description: "repeat last find backward",
+        default_keys: &["N"],
+    },
+    ActionInfo {
+        action: Action::OpenSignalProperties,
+        name: "open-signal-properties",
+        description: "open signal property editor / toggle group / list transactions",
+        default_keys: &["enter"],
+    },
+];
+
+struct PendingSession {
+    session: Session,
+    /// Request re-probing the signals of the session, if they differ from those of the design
+    probes_request_id: Option<usize>,
+    is_dut_ready: bool,
 }
 
 enum Child {
@@ -43,6 +290,9 @@ enum Child {
     InstanceHierView,
     FileExplorer,
     SignalPropertiesEditor,
+    SearchViewer,
+    RequestStatusViewer,
+    TransactionViewer,
 }
 
 impl Root {
@@ -50,10 +300,12 @@ impl Root {
         message_tx: Sender<RendererMessage>,
         request_tx: TokioSender<oombak_sim::Message>,
         command_interpreter: Arc<RwLock<CommandInterpreter>>,
+        request_status_viewer: Arc<RwLock<RequestStatusViewer>>,
     ) -> Self {
         let simulation_spec = Arc::new(RwLock::new(SimulationSpec::default()));
-        let key_mappings = Self::create_key_mappings();
-        Self {
+        let key_bindings = KeyBindings::load_or_defaults(&ACTIONS, "main");
+        let key_mappings = key_bindings.key_maps();
+        let root = Self {
             message_tx: message_tx.clone(),
             request_tx: request_tx.clone(),
             wave_viewer: WaveViewer::default().simulation(simulation_spec.clone()),
@@ -68,6 +320,15 @@ impl Root {
                 message_tx.clone(),
                 request_tx.clone(),
             ))),
+            search_viewer: Arc::new(RwLock::new(SearchViewer::new(
+                simulation_spec.clone(),
+                message_tx.clone(),
+            ))),
+            request_status_viewer,
+            transaction_viewer: Arc::new(RwLock::new(TransactionViewer::new(
+                simulation_spec.clone(),
+                message_tx.clone(),
+            ))),
             signal_properties_editor: Arc::new(RwLock::new(SignalPropertiesEditor::new(
                 simulation_spec.clone(),
                 message_tx.clone(),
@@ -75,36 +336,23 @@ impl Root {
             ))),
             focused_child: None,
             simulation_spec,
+            key_bindings,
             key_mappings,
             show_key_maps: false,
+            loaded_design_path: None,
+            loaded_top_module: String::new(),
+            probed_points: vec![],
+            pending_session: None,
+            last_find: None,
+            design_load_ids: HashSet::new(),
+        };
+        for message in key_bindings::take_load_errors() {
+            root.notify_output(TerminalOutput::Error(message));
         }
-    }
-
-    fn create_key_mappings() -> KeyMaps {
-        HashMap::from([
-            (KeyId::from('q'), KeyDesc::from("quit")),
-            (KeyId::from('o'), KeyDesc::from("load sim file")),
-            (KeyId::from('t'), KeyDesc::from("open terminal")),
-            (KeyId::from('s'), KeyDesc::from("open probe editor")),
-            (KeyId::from(':'), KeyDesc::from("open command line")),
-            (KeyId::from(KeyCode::Up), KeyDesc::from("scroll up")),
-            (KeyId::from('k'), KeyDesc::from("scroll up")),
-            (KeyId::from(KeyCode::Down), KeyDesc::from("scroll down")),
-            (KeyId::from('j'), KeyDesc::from("scroll down")),
-            (KeyId::from('l'), KeyDesc::from("scroll right")),
-            (KeyId::from(KeyCode::Right), KeyDesc::from("scroll right")),
-            (KeyId::from('h'), KeyDesc::from("scroll left")),
-            (KeyId::from(KeyCode::Left), KeyDesc::from("scroll left")),
-            (KeyId::from('z'), KeyDesc::from("zoom in")),
-            (KeyId::from('+'), KeyDesc::from("zoom in")),
-            (KeyId::from('x'), KeyDesc::from("zoom out")),
-            (KeyId::from('-'), KeyDesc::from("zoom out")),
-            (
-                KeyId::from(KeyCode::Enter),
-                KeyDesc::from("open signal property editor"),
-            ),
-        ])
-        .into()
+        if let Err(message) = theme::set_startup_theme() {
+            root.notify_output(TerminalOutput::Error(message));
+        }
+        root
     }
 
     fn notify_render(&self) {
@@ -143,96 +391,122 @@ impl Component for Root {
         if matches!(self.focused_child, Some(Child::SignalPropertiesEditor)) {
             self.render_signal_properties_editor(f, rect);
         }
+        if matches!(self.focused_child, Some(Child::SearchViewer)) {
+            self.render_search_viewer(f, rect);
+        }
+        if matches!(self.focused_child, Some(Child::RequestStatusViewer)) {
+            self.render_request_status_viewer(f, rect);
+        }
+        if matches!(self.focused_child, Some(Child::TransactionViewer)) {
+            self.render_transaction_viewer(f, rect);
+        }
         if self.show_key_maps {
             self.render_key_maps_viewer(f, rect);
         }
     }
 
     fn handle_key_event(&mut self, key_event: &KeyEvent) -> HandleResult {
-        match key_event.code {
-            KeyCode::Char('q') => {
+        match self.key_bindings.press(KeyId::from(key_event)) {
+            KeyPress::Action(Action::Quit) => {
                 self.notify_quit();
                 return HandleResult::Handled;
             }
-            KeyCode::Right | KeyCode::Char('l') => {
-                self.wave_viewer.scroll_right();
-                self.update_signal_viewer_highlight();
+            KeyPress::Action(action) => self.perform(action),
+            KeyPress::Pending => (),
+            KeyPress::Unbound => match key_event.code {
+                KeyCode::F(1) => {
+                    self.key_maps_viewer.prev_page();
+                }
+                KeyCode::F(2) => {
+                    self.show_key_maps = !self.show_key_maps;
+                }
+                KeyCode::F(3) => {
+                    self.key_maps_viewer.next_page();
+                }
+                _ => return HandleResult::NotHandled,
+            },
+        }
+        self.notify_render();
+        HandleResult::Handled
+    }
+
+    fn handle_resize_event(&mut self, _columns: u16, _rows: u16) -> HandleResult {
+        self.notify_render();
+        HandleResult::Handled
+    }
+
+    fn handle_mouse_event(&mut self, mouse_event: &MouseEvent) -> HandleResult {
+        if self.focused_child.is_some() {
+            return HandleResult::NotHandled;
+        }
+        let position = Position::new(mouse_event.column, mouse_event.row);
+        match mouse_event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let idx = self
+                    .signals_viewer
+                    .signal_at(position)
+                    .or(self.wave_viewer.wave_at(position));
+                if let Some(idx) = idx {
+                    self.signals_viewer.select(idx);
+                    self.wave_viewer.select(idx);
+                }
+                if self.wave_viewer.contains(position) {
+                    self.wave_viewer.place_cursor(position.x);
+                    self.wave_viewer.start_drag(position.x);
+                    self.update_signal_viewer_highlight();
+                }
             }
-            KeyCode::Left | KeyCode::Char('h') => {
-                self.wave_viewer.scroll_left();
+            MouseEventKind::Drag(MouseButton::Left) => {
+                self.wave_viewer.drag(position.x);
                 self.update_signal_viewer_highlight();
             }
-            KeyCode::Char('+') | KeyCode::Char('z') => {
+            MouseEventKind::Up(MouseButton::Left) => self.wave_viewer.end_drag(),
+            MouseEventKind::ScrollUp if self.wave_viewer.contains(position) => {
                 self.wave_viewer.zoom_in();
                 self.update_signal_viewer_highlight();
             }
-            KeyCode::Char('-') | KeyCode::Char('x') => {
+            MouseEventKind::ScrollDown if self.wave_viewer.contains(position) => {
                 self.wave_viewer.zoom_out();
                 self.update_signal_viewer_highlight();
             }
-            KeyCode::Up | KeyCode::Char('k') => {
+            MouseEventKind::ScrollUp => {
                 self.signals_viewer.scroll_up();
                 self.wave_viewer.scroll_up();
             }
-            KeyCode::Down | KeyCode::Char('j') => {
+            MouseEventKind::ScrollDown => {
                 self.signals_viewer.scroll_down();
                 self.wave_viewer.scroll_down();
             }
-            KeyCode::Char(':') => {
-                self.focused_child = Some(Child::CommandInterpreter);
-                self.command_interpreter.write().unwrap().set_line_mode();
-            }
-            KeyCode::Char('t') => {
-                self.focused_child = Some(Child::CommandInterpreter);
-                self.command_interpreter.write().unwrap().set_window_mode();
-            }
-            KeyCode::Char('s') => {
-                self.focused_child = Some(Child::InstanceHierView);
-            }
-            KeyCode::Char('o') => {
-                self.focused_child = Some(Child::FileExplorer);
-            }
-            KeyCode::Enter => {
-                if let Some(signal_name) = self.signals_viewer.selected_signal_name() {
-                    self.signal_properties_editor
-                        .write()
-                        .unwrap()
-                        .set_signal_name(&signal_name);
-                    self.focused_child = Some(Child::SignalPropertiesEditor);
-                }
-            }
-            KeyCode::F(1) => {
-                self.key_maps_viewer.prev_page();
-            }
-            KeyCode::F(2) => {
-                self.show_key_maps = !self.show_key_maps;
-            }
-            KeyCode::F(3) => {
-                self.key_maps_viewer.next_page();
-            }
             _ => return HandleResult::NotHandled,
         }
         self.notify_render();
         HandleResult::Handled
     }
 
-    fn handle_resize_event(&mut self, _columns: u16, _rows: u16) -> HandleResult {
-        self.notify_render();
-        HandleResult::Handled
-    }
-
     fn handle_focus_gained(&mut self) -> HandleResult {
         self.notify_render();
         self.focused_child = None;
         HandleResult::Handled
     }
 
+    fn try_propagate_event(&mut self, event: &Event) -> HandleResult {
+        let handle_result = match self.get_focused_child() {
+            Some(child) => child.write().unwrap().handle_event(event),
+            None => HandleResult::NotHandled,
+        };
+        self.serve_view_commands();
+        handle_result
+    }
+
     fn get_focused_child(&self) -> Option<Arc<RwLock<dyn Component>>> {
         match self.focused_child {
             Some(Child::CommandInterpreter) => Some(self.command_interpreter.clone()),
             Some(Child::InstanceHierView) => Some(self.instance_hier_viewer.clone()),
             Some(Child::FileExplorer) => Some(self.file_explorer.clone()),
             Some(Child::SignalPropertiesEditor) => Some(self.signal_properties_editor.clone()),
+            Some(Child::SearchViewer) => Some(self.search_viewer.clone()),
+            Some(Child::RequestStatusViewer) => Some(self.request_status_viewer.clone()),
+            Some(Child::TransactionViewer) => Some(self.transaction_viewer.clone()),
             None => None,
         }
     }
@@ -273,6 +547,10 @@ impl Root {
         // TODO: refactor
         let areas = Layout::vertical([Constraint::Min(0), Constraint::Length(3)]).split(rect);
         self.signals_viewer.render_mut(f, areas[0]);
+        if let Some(filter) = self.simulation_spec().filter() {
+            let line = Line::from(format!(" filter: {filter}")).style(FILTER_STYLE);
+            f.render_widget(line, areas[1]);
+        }
     }
 
     fn render_wave_viewer(&mut self, f: &mut Frame, rect: Rect) {
@@ -322,6 +600,48 @@ impl Root {
             .render(f, popup_area);
     }
 
+    fn render_search_viewer(&self, f: &mut Frame, rect: Rect) {
+        let popup_area = utils::layout::get_popup_area_bottom_right(rect);
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title("[Search]")
+            .title_alignment(Alignment::Center)
+            .title_style(TITLE_STYLE);
+        f.render_widget(Clear, popup_area);
+        self.search_viewer
+            .write()
+            .unwrap()
+            .render_with_block(f, popup_area, block);
+    }
+
+    fn render_request_status_viewer(&self, f: &mut Frame, rect: Rect) {
+        let popup_area = Self::get_popup_area_centered_large(rect);
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title("[Requests]")
+            .title_alignment(Alignment::Center)
+            .title_style(TITLE_STYLE);
+        let inner = block.inner(popup_area);
+        f.render_widget(Clear, popup_area);
+        f.render_widget(block, popup_area);
+        self.request_status_viewer.write().unwrap().render(f, inner);
+    }
+
+    fn render_transaction_viewer(&self, f: &mut Frame, rect: Rect) {
+        let popup_area = Self::get_popup_area_centered_large(rect);
+        let mut transaction_viewer = self.transaction_viewer.write().unwrap();
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title(format!(
+                "[Transactions of {}]",
+                transaction_viewer.decoder_name()
+            ))
+            .title_alignment(Alignment::Center)
+            .title_style(TITLE_STYLE);
+        f.render_widget(Clear, popup_area);
+        transaction_viewer.render_with_block(f, popup_area, block);
+    }
+
     fn render_key_maps_viewer(&mut self, f: &mut Frame, rect: Rect) {
         self.key_maps_viewer
             .set_key_maps(self.get_key_mappings().clone());
@@ -390,19 +710,42 @@ impl simulator_request_dispatcher::Listener for Root {
                 oombak_sim::response::Results::CurrentTime(_) => self.request_simulation_result(),
                 oombak_sim::response::Results::LoadedDut(dut) => {
                     self.set_loaded_dut(dut);
-                    self.reset_simulation_spec();
+                    // Definitions built on the probed signals outlive a change of probes
+                    if self.design_load_ids.remove(&response.id) {
+                        self.reset_simulation_spec();
+                    } else {
+                        self.request_simulation_result();
+                    }
+                    self.restore_session_probes();
                 }
                 oombak_sim::response::Results::SimulationResult(res) => {
                     self.update_simulation_spec(res);
+                    self.restore_session_layout();
                 }
                 oombak_sim::response::Results::Empty => (),
             }
         }
+        if let oombak_sim::response::Payload::Error(_) = response.payload {
+            let is_design_load = self.design_load_ids.remove(&response.id);
+            self.drop_failed_session(response.id, is_design_load);
+        }
+        // Scripts carry on as the responses come in
+        self.serve_view_commands();
+    }
+
+    fn on_request_dispatched(&mut self, request: &oombak_sim::Request) {
+        if let oombak_sim::request::Payload::Load(..) = request.payload {
+            self.design_load_ids.insert(request.id);
+        }
+        self.serve_view_commands();
     }
 }
 
 impl Root {
     fn set_loaded_dut(&mut self, loaded_dut: &oombak_sim::response::LoadedDut) {
+        self.loaded_design_path = Some(loaded_dut.path.clone());
+        self.loaded_top_module = loaded_dut.root_node.module_name.clone();
+        self.probed_points = loaded_dut.probed_points.clone();
         self.instance_hier_viewer
             .write()
             .unwrap()
@@ -435,6 +778,8 @@ impl Root {
             self.simulation_spec_mut().update_with(simulation_result);
         }
         self.reload_viewers();
+        // A restored cursor may have been reached
+        self.update_signal_viewer_highlight();
     }
 
     fn reload_viewers(&mut self) {
@@ -442,6 +787,21 @@ impl Root {
         self.wave_viewer.reload();
     }
 
+    fn reload_viewers_keeping_selection(&mut self) {
+        let selected_signal_name = self.signals_viewer.selected_signal_name();
+        self.reload_viewers();
+        let idx = selected_signal_name.and_then(|signal_name| {
+            self.simulation_spec()
+                .shown_wave_specs()
+                .into_iter()
+                .position(|ws| ws.wave.signal_name == signal_name)
+        });
+        if let Some(idx) = idx {
+            self.signals_viewer.select(idx);
+            self.wave_viewer.select(idx);
+        }
+    }
+
     fn simulation_spec(&self) -> RwLockReadGuard<'_, SimulationSpec> {
         self.simulation_spec.read().unwrap()
     }
@@ -450,3 +810,594 @@ impl Root {
         self.simulation_spec.write().unwrap()
     }
 }
+
+impl Root {
+    pub fn open_session(&mut self, path: &Path) {
+        match Session::load(path) {
+            Ok(session) => {
+                self.notify_output(TerminalOutput::Normal(format!(
+                    "opening session {}",
+                    path.display()
+                )));
+                {
+                    let mut command_interpreter = self.command_interpreter.write().unwrap();
+                    command_interpreter.set_command_history(session.history.clone());
+                    command_interpreter.load(&session.design_path, session.top_module.clone());
+                }
+                self.pending_session = Some(PendingSession {
+                    session,
+                    probes_request_id: None,
+                    is_dut_ready: false,
+                });
+            }
+            Err(message) => self.notify_output(TerminalOutput::Error(message)),
+        }
+    }
+
+    pub fn load_design(&mut self, design_path: &Path, top_module: Option<String>) {
+        self.command_interpreter
+            .write()
+            .unwrap()
+            .load(design_path, top_module);
+    }
+
+    pub fn run_script(&mut self, path: &Path) {
+        self.command_interpreter.write().unwrap().run_script(path);
+        self.serve_view_commands();
+    }
+
+    pub fn set_theme(&mut self, name: &str) {
+        if let Err(message) = theme::set(name) {
+            self.notify_output(TerminalOutput::Error(message));
+        }
+    }
+
+    fn save_session(&self, path: &Path) -> Result<(), String> {
+        let design_path = self
+            .loaded_design_path
+            .as_ref()
+            .ok_or_else(|| "no design loaded".to_string())?;
+        let design_path = design_path.canonicalize().unwrap_or(design_path.clone());
+        let session = Session::new(
+            &design_path,
+            &self.loaded_top_module,
+            &self.probed_points,
+            &self.simulation_spec(),
+            self.wave_viewer.get_highlighted_unit_time(),
+            self.command_interpreter.read().unwrap().command_history(),
+        );
+        session.save(path)
+    }
+
+    fn serve_view_commands(&mut self) {
+        let view_commands = self
+            .command_interpreter
+            .write()
+            .unwrap()
+            .take_view_commands();
+        for view_command in view_commands {
+            match view_command {
+                interpreter::Command::SaveSession(path) => {
+                    let output = match self.save_session(&path) {
+                        Ok(()) => {
+                            TerminalOutput::Normal(format!("saved session {}", path.display()))
+                        }
+                        Err(message) => TerminalOutput::Error(message),
+                    };
+                    self.notify_output(output);
+                }
+                interpreter::Command::OpenSession(path) => self.open_session(&path),
+                interpreter::Command::AddMarker(name, time) => {
+                    let time = time.unwrap_or(self.wave_viewer.get_highlighted_unit_time());
+                    self.simulation_spec_mut().add_marker(Some(&name), time);
+                }
+                interpreter::Command::DeleteMarker(name) => {
+                    let is_removed = self.simulation_spec_mut().remove_marker(&name);
+                    if !is_removed {
+                        self.notify_output(TerminalOutput::Error(format!(
+                            "unknown marker \"{name}\""
+                        )));
+                    }
+                }
+                interpreter::Command::GotoMarker(name) => {
+                    let time = self.simulation_spec().get_marker(&name).map(|m| m.time);
+                    match time {
+                        Some(time) => self.move_cursor_to(time),
+                        None => self.notify_output(TerminalOutput::Error(format!(
+                            "unknown marker \"{name}\""
+                        ))),
+                    }
+                }
+                interpreter::Command::Find(target, signal_name, is_backward) => {
+                    self.last_find = Some((target, signal_name));
+                    self.repeat_last_find(is_backward);
+                }
+                interpreter::Command::Derive(signal_name, expression) => {
+                    let result = self
+                        .simulation_spec_mut()
+                        .add_derived_signal(&signal_name, &expression);
+                    match result {
+                        Ok(()) => self.reload_viewers(),
+                        Err(message) => self.notify_output(TerminalOutput::Error(message)),
+                    }
+                }
+                interpreter::Command::RemoveDerived(signal_name) => {
+                    let is_removed = self
+                        .simulation_spec_mut()
+                        .remove_derived_signal(&signal_name);
+                    if is_removed {
+                        self.reload_viewers();
+                    } else {
+                        self.notify_output(TerminalOutput::Error(format!(
+                            "unknown derived signal \"{signal_name}\""
+                        )));
+                    }
+                }
+                interpreter::Command::Expand(signal_name, bit_range) => {
+                    let result = match bit_range {
+                        Some((msb, lsb)) => {
+                            self.simulation_spec_mut()
+                                .add_bus_group(&signal_name, msb, lsb)
+                        }
+                        None => self.simulation_spec_mut().expand_bus(&signal_name),
+                    };
+                    match result {
+                        Ok(()) => self.reload_viewers_keeping_selection(),
+                        Err(message) => self.notify_output(TerminalOutput::Error(message)),
+                    }
+                }
+                interpreter::Command::Collapse(signal_name) => {
+                    self.simulation_spec_mut().collapse_bus(&signal_name);
+                    self.reload_viewers_keeping_selection();
+                }
+                interpreter::Command::AddGroup(name) => {
+                    let before = self.signals_viewer.selected_signal_name();
+                    let result = self
+                        .simulation_spec_mut()
+                        .add_group(&name, before.as_deref());
+                    match result {
+                        Ok(()) => self.reload_viewers_keeping_selection(),
+                        Err(message) => self.notify_output(TerminalOutput::Error(message)),
+                    }
+                }
+                interpreter::Command::RemoveGroup(name) => {
+                    let is_removed = self.simulation_spec_mut().remove_group(&name);
+                    if is_removed {
+                        self.reload_viewers_keeping_selection();
+                    } else {
+                        self.notify_output(TerminalOutput::Error(format!(
+                            "unknown group \"{name}\""
+                        )));
+                    }
+                }
+                interpreter::Command::Move(signal_name, is_up) => self.move_row(signal_name, is_up),
+                interpreter::Command::Hide(signal_name) => self.hide_signal(signal_name),
+                interpreter::Command::Show(Some(signal_name)) => {
+                    let is_shown = self.simulation_spec_mut().show(&signal_name);
+                    if is_shown {
+                        self.reload_viewers_keeping_selection();
+                    } else {
+                        self.notify_output(TerminalOutput::Error(format!(
+                            "{signal_name} is not hidden"
+                        )));
+                    }
+                }
+                interpreter::Command::Show(None) => {
+                    let hidden_signal_names: Vec<String> = self
+                        .simulation_spec()
+                        .hidden_wave_specs
+                        .iter()
+                        .map(|ws| ws.wave.signal_name.clone())
+                        .collect();
+                    let output = if hidden_signal_names.is_empty() {
+                        "no hidden signals".to_string()
+                    } else {
+                        hidden_signal_names.join("\n")
+                    };
+                    self.notify_output(TerminalOutput::Normal(output));
+                }
+                interpreter::Command::Duplicate(signal_name) => self.duplicate_signal(signal_name),
+                interpreter::Command::DefineEnumMap(name, entries) => {
+                    self.simulation_spec_mut()
+                        .define_enum_map(EnumMap::new(&name, entries));
+                }
+                interpreter::Command::Decode(Some((name, protocol, arguments))) => {
+                    let result = self
+                        .simulation_spec_mut()
+                        .add_decoder(&name, &protocol, arguments);
+                    match result {
+                        Ok(num_of_transactions) => {
+                            self.reload_viewers_keeping_selection();
+                            self.notify_output(TerminalOutput::Normal(format!(
+                                "decoded {num_of_transactions} transactions into {name}"
+                            )));
+                        }
+                        Err(message) => self.notify_output(TerminalOutput::Error(message)),
+                    }
+                }
+                interpreter::Command::Decode(None) => {
+                    self.notify_output(TerminalOutput::Normal(decoder::usage()));
+                }
+                interpreter::Command::RemoveDecoder(name) => {
+                    let is_removed = self.simulation_spec_mut().remove_decoder(&name);
+                    if is_removed {
+                        self.reload_viewers_keeping_selection();
+                    } else {
+                        self.notify_output(TerminalOutput::Error(format!(
+                            "unknown decoder \"{name}\""
+                        )));
+                    }
+                }
+                interpreter::Command::ShowTransactions(name) => self.show_transactions(name),
+                interpreter::Command::SetColor(color, signal_name) => {
+                    self.set_signal_color(color, signal_name)
+                }
+                interpreter::Command::Theme(Some(name)) => self.set_theme(&name),
+                interpreter::Command::Theme(None) => {
+                    let current_name = theme::current_name();
+                    let output = theme::names().map(|names| {
+                        names
+                            .iter()
+                            .map(|name| {
+                                if *name == current_name {
+                                    format!("{name} (current)")
+                                } else {
+                                    name.clone()
+                                }
+                            })
+                            .collect::<Vec<String>>()
+                            .join("\n")
+                    });
+                    match output {
+                        Ok(output) => self.notify_output(TerminalOutput::Normal(output)),
+                        Err(message) => self.notify_output(TerminalOutput::Error(message)),
+                    }
+                }
+                interpreter::Command::Filter(pattern) => {
+                    let result = self.simulation_spec_mut().set_filter(pattern.as_deref());
+                    match result {
+                        Ok(()) => self.reload_viewers_keeping_selection(),
+                        Err(message) => self.notify_output(TerminalOutput::Error(message)),
+                    }
+                }
+                interpreter::Command::Search(expression) => {
+                    let mut search_viewer = self.search_viewer.write().unwrap();
+                    search_viewer.set_expression(&expression);
+                    search_viewer.search();
+                    drop(search_viewer);
+                    self.focused_child = Some(Child::SearchViewer);
+                }
+                _ => (),
+            }
+        }
+        let time_to_show = self.search_viewer.write().unwrap().take_time_to_show();
+        if let Some(time) = time_to_show {
+            self.move_cursor_to(time);
+        }
+        let time_to_show = self.transaction_viewer.write().unwrap().take_time_to_show();
+        if let Some(time) = time_to_show {
+            self.move_cursor_to(time);
+        }
+        let session_to_open = self.file_explorer.write().unwrap().take_session_to_open();
+        if let Some(path) = session_to_open {
+            self.open_session(&path);
+        }
+    }
+
+    fn perform(&mut self, action: Action) {
+        match action {
+            Action::Quit => self.notify_quit(),
+            Action::ScrollRight => {
+                self.wave_viewer.scroll_right();
+                self.update_signal_viewer_highlight();
+            }
+            Action::ScrollLeft => {
+                self.wave_viewer.scroll_left();
+                self.update_signal_viewer_highlight();
+            }
+            Action::ZoomIn => {
+                self.wave_viewer.zoom_in();
+                self.update_signal_viewer_highlight();
+            }
+            Action::ZoomOut => {
+                self.wave_viewer.zoom_out();
+                self.update_signal_viewer_highlight();
+            }
+            Action::DropMarker => self.add_marker_at_cursor(),
+            Action::DeleteReferenceMarker => self.remove_reference_marker(),
+            Action::JumpToNextMarker => self.jump_to_next_marker(),
+            Action::JumpToNextChange => self.find_in_selected(FindTarget::Change, false),
+            Action::JumpToPreviousChange => self.find_in_selected(FindTarget::Change, true),
+            Action::JumpToNextRisingEdge => {
+                self.find_in_selected(FindTarget::Edge(Edge::Rising), false)
+            }
+            Action::JumpToPreviousRisingEdge => {
+                self.find_in_selected(FindTarget::Edge(Edge::Rising), true)
+            }
+            Action::JumpToNextFallingEdge => {
+                self.find_in_selected(FindTarget::Edge(Edge::Falling), false)
+            }
+            Action::JumpToPreviousFallingEdge => {
+                self.find_in_selected(FindTarget::Edge(Edge::Falling), true)
+            }
+            Action::ToggleBus => self.toggle_selected_bus(),
+            Action::MoveRowUp => self.move_row(None, true),
+            Action::MoveRowDown => self.move_row(None, false),
+            Action::HideSignal => self.hide_signal(None),
+            Action::DuplicateSignal => self.duplicate_signal(None),
+            Action::RepeatFind => self.repeat_last_find(false),
+            Action::RepeatFindBackward => self.repeat_last_find(true),
+            Action::ScrollUp => {
+                self.signals_viewer.scroll_up();
+                self.wave_viewer.scroll_up();
+            }
+            Action::ScrollDown => {
+                self.signals_viewer.scroll_down();
+                self.wave_viewer.scroll_down();
+            }
+            Action::OpenCommandLine => {
+                self.focused_child = Some(Child::CommandInterpreter);
+                self.command_interpreter.write().unwrap().set_line_mode();
+            }
+            Action::OpenTerminal => {
+                self.focused_child = Some(Child::CommandInterpreter);
+                self.command_interpreter.write().unwrap().set_window_mode();
+            }
+            Action::OpenProbeEditor => {
+                self.focused_child = Some(Child::InstanceHierView);
+            }
+            Action::LoadSimFile => {
+                self.focused_child = Some(Child::FileExplorer);
+            }
+            Action::SearchWaves => {
+                self.focused_child = Some(Child::SearchViewer);
+            }
+            Action::ShowRequests => {
+                self.focused_child = Some(Child::RequestStatusViewer);
+            }
+            Action::OpenSignalProperties => {
+                if let Some(signal_name) = self.signals_viewer.selected_signal_name() {
+                    if self.simulation_spec_mut().toggle_group(&signal_name) {
+                        self.reload_viewers_keeping_selection();
+                    } else if self.simulation_spec().is_decoder(&signal_name) {
+                        self.show_transactions(Some(signal_name));
+                    } else {
+                        self.signal_properties_editor
+                            .write()
+                            .unwrap()
+                            .set_signal_name(&signal_name);
+                        self.focused_child = Some(Child::SignalPropertiesEditor);
+                    }
+                }
+            }
+        }
+    }
+
+    fn move_cursor_to(&mut self, time: usize) {
+        self.wave_viewer.set_highlighted_unit_time(time);
+        self.update_signal_viewer_highlight();
+    }
+
+    fn add_marker_at_cursor(&mut self) {
+        let time = self.wave_viewer.get_highlighted_unit_time();
+        self.simulation_spec_mut().add_marker(None, time);
+    }
+
+    fn remove_reference_marker(&mut self) {
+        let mut simulation_spec = self.simulation_spec_mut();
+        if let Some(name) = simulation_spec.reference_marker().map(|m| m.name.clone()) {
+            simulation_spec.remove_marker(&name);
+        }
+    }
+
+    fn jump_to_next_marker(&mut self) {
+        let time = self.wave_viewer.get_highlighted_unit_time();
+        let next_time = self.simulation_spec().next_marker(time).map(|m| m.time);
+        if let Some(next_time) = next_time {
+            self.move_cursor_to(next_time);
+        }
+    }
+
+    fn toggle_selected_bus(&mut self) {
+        let signal_name = match self.signals_viewer.selected_signal_name() {
+            Some(signal_name) => signal_name,
+            None => return,
+        };
+        let mut simulation_spec = self.simulation_spec_mut();
+        let result = if simulation_spec.toggle_group(&signal_name)
+            || simulation_spec.collapse_bus(&signal_name)
+        {
+            Ok(())
+        } else {
+            simulation_spec.expand_bus(&signal_name)
+        };
+        drop(simulation_spec);
+        match result {
+            Ok(()) => self.reload_viewers_keeping_selection(),
+            Err(message) => self.notify_output(TerminalOutput::Error(message)),
+        }
+    }
+
+    fn selected_or(&self, signal_name: Option<String>) -> Result<String, String> {
+        signal_name
+            .or_else(|| self.signals_viewer.selected_signal_name())
+            .ok_or_else(|| "no signal is selected".to_string())
+    }
+
+    fn move_row(&mut self, signal_name: Option<String>, is_up: bool) {
+        if let Ok(signal_name) = self.selected_or(signal_name) {
+            let is_moved = if is_up {
+                self.simulation_spec_mut().move_up(&signal_name)
+            } else {
+                self.simulation_spec_mut().move_down(&signal_name)
+            };
+            if is_moved {
+                self.reload_viewers_keeping_selection();
+            }
+        }
+    }
+
+    fn hide_signal(&mut self, signal_name: Option<String>) {
+        let result = self.selected_or(signal_name).and_then(|signal_name| {
+            if self.simulation_spec_mut().hide(&signal_name) {
+                Ok(())
+            } else {
+                Err(format!("cannot hide \"{signal_name}\""))
+            }
+        });
+        match result {
+            Ok(()) => self.reload_viewers_keeping_selection(),
+            Err(message) => self.notify_output(TerminalOutput::Error(message)),
+        }
+    }
+
+    fn show_transactions(&mut self, decoder_name: Option<String>) {
+        let result = self.selected_or(decoder_name).and_then(|decoder_name| {
+            if self.simulation_spec().is_decoder(&decoder_name) {
+                Ok(decoder_name)
+            } else {
+                Err(format!("{decoder_name} is not a decoder"))
+            }
+        });
+        match result {
+            Ok(decoder_name) => {
+                self.transaction_viewer
+                    .write()
+                    .unwrap()
+                    .set_decoder(&decoder_name);
+                self.focused_child = Some(Child::TransactionViewer);
+            }
+            Err(message) => self.notify_output(TerminalOutput::Error(message)),
+        }
+    }
+
+    fn duplicate_signal(&mut self, signal_name: Option<String>) {
+        let result = self
+            .selected_or(signal_name)
+            .and_then(|signal_name| self.simulation_spec_mut().duplicate(&signal_name));
+        match result {
+            Ok(_) => self.reload_viewers_keeping_selection(),
+            Err(message) => self.notify_output(TerminalOutput::Error(message)),
+        }
+    }
+
+    fn set_signal_color(&mut self, color: Option<Color>, signal_name: Option<String>) {
+        let result = self.selected_or(signal_name).and_then(|signal_name| {
+            match self.simulation_spec_mut().get_wave_spec_mut(&signal_name) {
+                Some(wave_spec) => {
+                    wave_spec.color = color;
+                    Ok(())
+                }
+                None => Err(format!("unknown signal \"{signal_name}\"")),
+            }
+        });
+        if let Err(message) = result {
+            self.notify_output(TerminalOutput::Error(message));
+        }
+    }
+
+    fn find_in_selected(&mut self, target: FindTarget, is_backward: bool) {
+        self.last_find = Some((target, None));
+        self.repeat_last_find(is_backward);
+    }
+
+    fn repeat_last_find(&mut self, is_backward: bool) {
+        if let Some((target, signal_name)) = self.last_find.clone() {
+            if let Err(message) = self.find(&target, signal_name, is_backward) {
+                self.notify_output(TerminalOutput::Error(message));
+            }
+        }
+    }
+
+    fn find(
+        &mut self,
+        target: &FindTarget,
+        signal_name: Option<String>,
+        is_backward: bool,
+    ) -> Result<(), String> {
+        let signal_name = self.selected_or(signal_name)?;
+        let time = self.wave_viewer.get_highlighted_unit_time();
+        let found_time = {
+            let simulation_spec = self.simulation_spec();
+            let wave = match simulation_spec.get_wave_spec(&signal_name) {
+                Some(wave_spec) => &wave_spec.wave,
+                None => return Err(format!("unknown signal \"{signal_name}\"")),
+            };
+            match (target, is_backward) {
+                (FindTarget::Edge(_), _) if wave.width != 1 => {
+                    return Err(format!("{signal_name} is not a 1-bit signal"))
+                }
+                (FindTarget::Change, false) => wave.next_change(time),
+                (FindTarget::Change, true) => wave.previous_change(time),
+                (FindTarget::Edge(edge), false) => wave.next_edge(time, *edge),
+                (FindTarget::Edge(edge), true) => wave.previous_edge(time, *edge),
+                (FindTarget::Value(value), false) => wave.next_occurrence(time, value),
+                (FindTarget::Value(value), true) => wave.previous_occurrence(time, value),
+            }
+        };
+        match found_time {
+            Some(found_time) => {
+                self.move_cursor_to(found_time);
+                Ok(())
+            }
+            None => Err(format!("no match found in {signal_name}")),
+        }
+    }
+
+    fn restore_session_probes(&mut self) {
+        if let Some(pending_session) = self.pending_session.as_mut() {
+            let modification = pending_session
+                .session
+                .probe_points_modification(&self.probed_points);
+            let is_modified = !modification.to_add.is_empty() || !modification.to_remove.is_empty();
+            if is_modified && pending_session.probes_request_id.is_none() {
+                let message = oombak_sim::Request::modify_probe_points(modification);
+                if let oombak_sim::Message::Request(request) = &message {
+                    pending_session.probes_request_id = Some(request.id);
+                }
+                self.request_tx.blocking_send(message).unwrap();
+            } else {
+                pending_session.is_dut_ready = true;
+            }
+        }
+    }
+
+    /// Gives up restoring the session once loading its design or probing its signals fails.
+    fn drop_failed_session(&mut self, request_id: usize, is_design_load: bool) {
+        let is_failed = self
+            .pending_session
+            .as_ref()
+            .is_some_and(|pending_session| match pending_session.probes_request_id {
+                Some(probes_request_id) => probes_request_id == request_id,
+                None => is_design_load,
+            });
+        if is_failed {
+            self.pending_session = None;
+            self.notify_output(TerminalOutput::Error("session not restored".to_string()));
+        }
+    }
+
+    fn restore_session_layout(&mut self) {
+        if !self
+            .pending_session
+            .as_ref()
+            .is_some_and(|pending_session| pending_session.is_dut_ready)
+        {
+            return;
+        }
+        let session = self.pending_session.take().unwrap().session;
+        session.apply_to(&mut self.simulation_spec_mut());
+        self.reload_viewers();
+        self.wave_viewer
+            .restore_highlighted_unit_time(session.cursor);
+        self.update_signal_viewer_highlight();
+        self.notify_render();
+    }
+
+    fn notify_output(&self, output: TerminalOutput) {
+        self.command_interpreter
+            .write()
+            .unwrap()
+            .append_output(output);
+    }
+}
@@ -0,0 +1,247 @@
+use std::sync::{mpsc::Sender, Arc, RwLock};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    layout::{Constraint, Layout, Position, Rect},
+    text::Line,
+    widgets::{List, ListState},
+    Frame,
+};
+
+use crate::{
+    backend::{
+        expression,
+        key_bindings::{ActionInfo, KeyBindings, KeyPress},
+    },
+    component::{Component, HandleResult},
+    styles::{
+        global::SELECTED_ITEM_STYLE,
+        search_viewer::{ERROR_STYLE, STATUS_STYLE},
+    },
+    threads::RendererMessage,
+    utils,
+    widgets::{self, CommandLine, CommandLineState, KeyId, KeyMaps},
+};
+
+use super::models::SimulationSpec;
+
+#[derive(Clone, Copy)]
+enum Action {
+    Close,
+    Search,
+    PreviousMatch,
+    NextMatch,
+}
+
+const ACTIONS: [ActionInfo<Action>; 4] = [
+    ActionInfo {
+        action: Action::Close,
+        name: "close",
+        description: "close window",
+        default_keys: &["esc", "ctrl-d"],
+    },
+    ActionInfo {
+        action: Action::Search,
+        name: "search",
+        description: "search",
+        default_keys: &["enter"],
+    },
+    ActionInfo {
+        action: Action::PreviousMatch,
+        name: "previous-match",
+        description: "previous match",
+        default_keys: &["up"],
+    },
+    ActionInfo {
+        action: Action::NextMatch,
+        name: "next-match",
+        description: "next match",
+        default_keys: &["down"],
+    },
+];
+
+pub struct SearchViewer {
+    message_tx: Sender<RendererMessage>,
+    simulation_spec: Arc<RwLock<SimulationSpec>>,
+    command_line_state: CommandLineState,
+    matches: Vec<(usize, usize)>,
+    error: Option<String>,
+    list_state: ListState,
+    list_area: Rect,
+    key_bindings: KeyBindings<Action>,
+    key_mappings: KeyMaps,
+    time_to_show: Option<usize>,
+}
+
+impl SearchViewer {
+    pub fn new(
+        simulation_spec: Arc<RwLock<SimulationSpec>>,
+        message_tx: Sender<RendererMessage>,
+    ) -> Self {
+        let key_bindings = KeyBindings::load_or_defaults(&ACTIONS, "search");
+        let key_mappings = key_bindings.key_maps();
+        Self {
+            message_tx,
+            simulation_spec,
+            command_line_state: CommandLineState::default(),
+            matches: vec![],
+            error: None,
+            list_state: ListState::default(),
+            list_area: Rect::default(),
+            key_bindings,
+            key_mappings,
+            time_to_show: None,
+        }
+    }
+
+    pub fn set_expression(&mut self, expression: &str) {
+        self.command_line_state.set_text(expression);
+    }
+
+    pub fn search(&mut self) {
+        let result = expression::parse(self.command_line_state.text()).and_then(|expression| {
+            expression.find_intervals(&self.simulation_spec.read().unwrap())
+        });
+        match result {
+            Ok(matches) => {
+                self.matches = matches;
+                self.error = None;
+                self.select(0);
+            }
+            Err(message) => {
+                self.matches.clear();
+                self.error = Some(message);
+                self.list_state.select(None);
+            }
+        }
+    }
+
+    pub fn take_time_to_show(&mut self) -> Option<usize> {
+        self.time_to_show.take()
+    }
+
+    fn select(&mut self, idx: usize) {
+        if let Some((start_time, _)) = self.matches.get(idx) {
+            self.list_state.select(Some(idx));
+            self.time_to_show = Some(*start_time);
+        }
+    }
+
+    fn select_next(&mut self) {
+        let idx = self.list_state.selected().map_or(0, |idx| idx + 1);
+        self.select(idx);
+    }
+
+    fn select_previous(&mut self) {
+        if let Some(idx) = self.list_state.selected() {
+            self.select(idx.saturating_sub(1));
+        }
+    }
+
+    fn perform(&mut self, action: Action) -> HandleResult {
+        match action {
+            Action::Close => return HandleResult::ReleaseFocus,
+            Action::Search => self.search(),
+            Action::PreviousMatch => self.select_previous(),
+            Action::NextMatch => self.select_next(),
+        }
+        self.notify_render();
+        HandleResult::Handled
+    }
+
+    fn notify_render(&self) {
+        self.message_tx.send(RendererMessage::Render).unwrap();
+    }
+}
+
+impl Component for SearchViewer {
+    fn render(&mut self, f: &mut Frame, rect: Rect) {
+        let areas = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .split(rect);
+        f.render_stateful_widget(
+            CommandLine::default(),
+            areas[0],
+            &mut self.command_line_state,
+        );
+
+        let status = match &self.error {
+            Some(message) => Line::from(&message[..]).style(ERROR_STYLE),
+            None => Line::from(format!(" {} matches", self.matches.len())).style(STATUS_STYLE),
+        };
+        f.render_widget(status, areas[1]);
+
+        let time_step_ps = self.simulation_spec.read().unwrap().time_step_ps();
+        let items = self.matches.iter().map(|(start_time, end_time)| {
+            format!(
+                "{} - {}",
+                widgets::format_time((start_time * time_step_ps) as f64),
+                widgets::format_time((end_time * time_step_ps) as f64)
+            )
+        });
+        let list = List::new(items).highlight_style(SELECTED_ITEM_STYLE);
+        f.render_stateful_widget(list, areas[2], &mut self.list_state);
+        self.list_area = areas[2];
+    }
+
+    fn handle_key_event(&mut self, key_event: &KeyEvent) -> HandleResult {
+        match self.key_bindings.press(KeyId::from(key_event)) {
+            KeyPress::Action(action) => return self.perform(action),
+            KeyPress::Pending => return HandleResult::Handled,
+            KeyPress::Unbound => (),
+        }
+        match (key_event.code, key_event.modifiers) {
+            (KeyCode::Char(c), modifier) if (modifier - KeyModifiers::SHIFT).is_empty() => {
+                self.command_line_state.put(c)
+            }
+            (KeyCode::Backspace, _) => self.command_line_state.backspace(),
+            (KeyCode::Right, _) => self.command_line_state.move_cursor_right(),
+            (KeyCode::Left, _) => self.command_line_state.move_cursor_left(),
+            _ => return HandleResult::NotHandled,
+        }
+        self.notify_render();
+        HandleResult::Handled
+    }
+
+    fn handle_resize_event(&mut self, _columns: u16, _rows: u16) -> HandleResult {
+        HandleResult::NotHandled
+    }
+
+    fn handle_mouse_event(&mut self, mouse_event: &MouseEvent) -> HandleResult {
+        match mouse_event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let position = Position::new(mouse_event.column, mouse_event.row);
+                let idx = utils::layout::get_list_item_at(
+                    self.list_area,
+                    self.list_state.offset(),
+                    vec![1; self.matches.len()],
+                    position,
+                );
+                match idx {
+                    Some(idx) => self.select(idx),
+                    None => return HandleResult::NotHandled,
+                }
+            }
+            MouseEventKind::ScrollUp => self.select_previous(),
+            MouseEventKind::ScrollDown => self.select_next(),
+            _ => return HandleResult::NotHandled,
+        }
+        self.notify_render();
+        HandleResult::Handled
+    }
+
+    fn handle_focus_gained(&mut self) -> HandleResult {
+        HandleResult::Handled
+    }
+
+    fn get_focused_child(&self) -> Option<Arc<RwLock<dyn Component>>> {
+        None
+    }
+
+    fn get_key_mappings(&self) -> KeyMaps {
+        self.key_mappings.clone()
+    }
+}
@@ -1,29 +1,64 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
-    layout::{Alignment, Rect},
+    layout::{Alignment, Position, Rect},
     text::Text,
     widgets::{Block, BorderType, Clear, List, ListState},
     Frame,
 };
-use std::{
-    collections::HashMap,
-    sync::{mpsc::Sender, Arc, RwLock},
-};
+use std::sync::{mpsc::Sender, Arc, RwLock};
 
 use crate::{
+    backend::key_bindings::{ActionInfo, KeyBindings, KeyPress},
     component::{Component, HandleResult},
     styles::{global::SELECTED_ITEM_STYLE, selector::DISABLED_ITEM_STYLE},
     threads::RendererMessage,
     utils,
-    widgets::{KeyDesc, KeyId, KeyMaps},
+    widgets::{KeyId, KeyMaps},
 };
 
+#[derive(Clone, Copy)]
+enum Action {
+    Close,
+    MoveUp,
+    MoveDown,
+    Select,
+}
+
+const ACTIONS: [ActionInfo<Action>; 4] = [
+    ActionInfo {
+        action: Action::Close,
+        name: "close",
+        description: "close window",
+        default_keys: &["q", "esc"],
+    },
+    ActionInfo {
+        action: Action::MoveUp,
+        name: "move-up",
+        description: "move up",
+        default_keys: &["up", "k"],
+    },
+    ActionInfo {
+        action: Action::MoveDown,
+        name: "move-down",
+        description: "move down",
+        default_keys: &["down", "j"],
+    },
+    ActionInfo {
+        action: Action::Select,
+        name: "select",
+        description: "select highlighted",
+        default_keys: &["enter"],
+    },
+];
+
 pub struct Selector {
     selection: Vec<Selection>,
     title: String,
     list_state: ListState,
+    list_area: Rect,
     child: Option<usize>,
     renderer_channel: Sender<RendererMessage>,
+    key_bindings: KeyBindings<Action>,
     key_maps: KeyMaps,
 }
 
@@ -35,13 +70,17 @@ pub struct Selection {
 
 impl Selector {
     pub fn new(selection: Vec<Selection>, renderer_channel: Sender<RendererMessage>) -> Self {
+        let key_bindings = KeyBindings::load_or_defaults(&ACTIONS, "menu");
+        let key_maps = key_bindings.key_maps();
         Self {
             selection,
             title: String::new(),
             list_state: ListState::default(),
+            list_area: Rect::default(),
             child: None,
             renderer_channel,
-            key_maps: Self::create_key_maps(),
+            key_bindings,
+            key_maps,
         }
     }
 
@@ -71,19 +110,23 @@ impl Selector {
         }
     }
 
-    fn create_key_maps() -> KeyMaps {
-        KeyMaps::from(HashMap::from([
-            (KeyId::from('q'), KeyDesc::from("close window")),
-            (KeyId::from(KeyCode::Esc), KeyDesc::from("close window")),
-            (KeyId::from('k'), KeyDesc::from("move up")),
-            (KeyId::from(KeyCode::Up), KeyDesc::from("move up")),
-            (KeyId::from('j'), KeyDesc::from("move down")),
-            (KeyId::from(KeyCode::Down), KeyDesc::from("move down")),
-            (
-                KeyId::from(KeyCode::Enter),
-                KeyDesc::from("select highlighted"),
-            ),
-        ]))
+    fn perform(&mut self, action: Action) -> HandleResult {
+        match action {
+            Action::Close => return HandleResult::ReleaseFocus,
+            Action::MoveUp => self.list_state.select_previous(),
+            Action::MoveDown => self.list_state.select_next(),
+            Action::Select => {
+                self.child = self
+                    .list_state
+                    .selected()
+                    .filter(|i| !self.selection[*i].disabled);
+                if self.child.is_some() {
+                    self.list_state.select_first();
+                }
+            }
+        }
+        self.renderer_channel.send(RendererMessage::Render).unwrap();
+        HandleResult::Handled
     }
 
     fn render_self(&mut self, f: &mut Frame, rect: Rect) {
@@ -105,6 +148,7 @@ impl Selector {
         f.render_widget(Clear, render_area);
         f.render_widget(block, render_area);
         f.render_stateful_widget(list, inner_area, &mut self.list_state);
+        self.list_area = inner_area;
     }
 
     fn try_select_default_item(&mut self) {
@@ -141,30 +185,46 @@ impl Component for Selector {
     }
 
     fn handle_key_event(&mut self, key_event: &KeyEvent) -> HandleResult {
-        match key_event.code {
-            KeyCode::Up | KeyCode::Char('k') => self.list_state.select_previous(),
-            KeyCode::Down | KeyCode::Char('j') => self.list_state.select_next(),
-            KeyCode::Enter => {
-                self.child = self
-                    .list_state
-                    .selected()
-                    .filter(|i| !self.selection[*i].disabled);
-                if self.child.is_some() {
-                    self.list_state.select_first();
-                }
+        match self.key_bindings.press(KeyId::from(key_event)) {
+            KeyPress::Action(action) => self.perform(action),
+            KeyPress::Unbound if matches!(key_event.code, KeyCode::F(_)) => {
+                HandleResult::NotHandled
             }
-            KeyCode::Char('q') | KeyCode::Esc => return HandleResult::ReleaseFocus,
-            KeyCode::F(_) => return HandleResult::NotHandled,
-            _ => (),
+            KeyPress::Pending | KeyPress::Unbound => HandleResult::Handled,
         }
-        self.renderer_channel.send(RendererMessage::Render).unwrap();
-        HandleResult::Handled
     }
 
     fn handle_resize_event(&mut self, _: u16, _: u16) -> HandleResult {
         HandleResult::NotHandled
     }
 
+    fn handle_mouse_event(&mut self, mouse_event: &MouseEvent) -> HandleResult {
+        match mouse_event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let position = Position::new(mouse_event.column, mouse_event.row);
+                let item_heights = vec![1; self.selection.len()];
+                let idx = utils::layout::get_list_item_at(
+                    self.list_area,
+                    self.list_state.offset(),
+                    item_heights,
+                    position,
+                );
+                match idx {
+                    Some(idx) if self.list_state.selected() == Some(idx) => {
+                        return self.perform(Action::Select)
+                    }
+                    Some(idx) => self.list_state.select(Some(idx)),
+                    None => return HandleResult::NotHandled,
+                }
+            }
+            MouseEventKind::ScrollUp => self.list_state.select_previous(),
+            MouseEventKind::ScrollDown => self.list_state.select_next(),
+            _ => return HandleResult::NotHandled,
+        }
+        self.renderer_channel.send(RendererMessage::Render).unwrap();
+        HandleResult::Handled
+    }
+
     fn handle_focus_gained(&mut self) -> crate::component::HandleResult {
         self.child = None;
         HandleResult::ReleaseFocus
@@ -1,26 +1,43 @@
-use std::{
-    collections::HashMap,
-    sync::{mpsc::Sender, Arc, RwLock, RwLockReadGuard},
-};
+use std::sync::{mpsc::Sender, Arc, RwLock, RwLockReadGuard};
 
-use crossterm::event::{KeyCode, KeyEvent};
-use ratatui::{layout::Rect, Frame};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    layout::{Position, Rect},
+    Frame,
+};
 
 use crate::{
+    backend::key_bindings::{KeyBindings, KeyPress},
     component::{Component, HandleResult},
-    components::models::{PlotType, SimulationSpec},
+    components::{
+        models::{PlotType, SimulationSpec},
+        signal_properties_editor::FormAction,
+    },
+    plugin,
+    styles::theme,
     threads::RendererMessage,
     utils::bitvec_str::Radix,
-    widgets::{
-        CommandLineState, DropDownState, Form, FormState, InputField, KeyDesc, KeyId, KeyMaps,
-    },
+    widgets::{CommandLineState, DropDownState, Form, FormState, InputField, KeyId, KeyMaps},
 };
 
+const RADIX_ITEMS: [&str; 9] = [
+    "Binary",
+    "Hexadecimal",
+    "Octal",
+    "Decimal",
+    "ASCII",
+    "Fixed-point",
+    "Float",
+    "Enum",
+    "Custom",
+];
+
 pub struct SignalDisplayPropertiesSetter {
     simulation_spec: Arc<RwLock<SimulationSpec>>,
     signal_name: String,
     form_state: FormState,
     renderer_channel: Sender<RendererMessage>,
+    key_bindings: KeyBindings<FormAction>,
     key_maps: KeyMaps,
 }
 
@@ -29,12 +46,34 @@ impl SignalDisplayPropertiesSetter {
         signal_name: String,
         simulation_spec: Arc<RwLock<SimulationSpec>>,
         renderer_channel: Sender<RendererMessage>,
+        key_bindings: KeyBindings<FormAction>,
     ) -> Self {
+        let enum_map_names: Vec<String> = simulation_spec
+            .read()
+            .unwrap()
+            .enum_maps
+            .iter()
+            .map(|m| m.name.clone())
+            .collect();
+        let enum_map_items: Vec<&str> = std::iter::once("None")
+            .chain(enum_map_names.iter().map(|name| &name[..]))
+            .collect();
+        let formatters = plugin::formatters();
+        let formatter_items: Vec<&str> = std::iter::once("None")
+            .chain(formatters.iter().map(|formatter| formatter.name()))
+            .collect();
         let input_fields = vec![
-            InputField::dropdown("Radix", &["Binary", "Hexadecimal", "Octal", "Decimal"]),
+            InputField::dropdown("Radix", &RADIX_ITEMS),
             InputField::dropdown("Signedness", &["Unsigned", "Signed"]),
             InputField::dropdown("Plot type", &["Digital", "Analog"]),
             InputField::text("Plot height"),
+            InputField::text("Fraction bits"),
+            InputField::dropdown("Enum map", &enum_map_items),
+            InputField::text("Analog min"),
+            InputField::text("Analog max"),
+            InputField::dropdown("Analog interpolation", &["Step", "Linear"]),
+            InputField::text("Color"),
+            InputField::dropdown("Formatter", &formatter_items),
         ];
         let mut form_state = FormState::new(input_fields);
         Self::set_form_state_initial_values(
@@ -47,24 +86,47 @@ impl SignalDisplayPropertiesSetter {
             signal_name,
             form_state,
             renderer_channel,
-            key_maps: Self::create_key_maps(),
+            key_maps: key_bindings.key_maps(),
+            key_bindings,
         }
     }
 
-    fn create_key_maps() -> KeyMaps {
-        KeyMaps::from(HashMap::from([
-            (KeyId::from('q'), KeyDesc::from("close window")),
-            (KeyId::from(KeyCode::Esc), KeyDesc::from("close window")),
-            (KeyId::from('k'), KeyDesc::from("move up")),
-            (KeyId::from(KeyCode::Up), KeyDesc::from("move up")),
-            (KeyId::from('j'), KeyDesc::from("move down")),
-            (KeyId::from(KeyCode::Down), KeyDesc::from("move down")),
-            (KeyId::from(KeyCode::Tab), KeyDesc::from("move down")),
-            (
-                KeyId::from(KeyCode::Enter),
-                KeyDesc::from("open / close dropdown; confirm; move down"),
-            ),
-        ]))
+    fn perform(&mut self, action: FormAction) -> HandleResult {
+        match action {
+            FormAction::Close => return HandleResult::ReleaseFocus,
+            FormAction::MoveUp => self.form_state.up(),
+            FormAction::MoveDown => self.form_state.down(),
+            FormAction::MoveLeft => self.form_state.left(),
+            FormAction::MoveRight => self.form_state.right(),
+            FormAction::Confirm => {
+                if self.form_state.is_apply() {
+                    let entries = self.form_state.entries();
+                    let (radix, signed, plot_type, height) =
+                        Self::parse_user_input(&entries).unwrap();
+                    self.set_wave_spec_radix(radix);
+                    self.set_wave_spec_fraction_bits(&entries[4]);
+                    self.set_wave_spec_enum_map(&entries[5]);
+                    self.set_wave_spec_analog_range(&entries[6], &entries[7]);
+                    self.set_wave_spec_interpolate(&entries[8]);
+                    self.set_wave_spec_color(&entries[9]);
+                    self.set_wave_spec_formatter(&entries[10]);
+                    self.set_wave_spec_signed(signed);
+                    self.set_wave_spec_plot_type(plot_type);
+                    if matches!(plot_type, PlotType::Analog) {
+                        self.set_wave_spec_height(height);
+                    }
+                    return HandleResult::ReleaseFocus;
+                } else if self.form_state.is_cancel() {
+                    return HandleResult::ReleaseFocus;
+                } else if self.form_state.is_dropdown() {
+                    self.form_state.enter();
+                } else {
+                    self.form_state.down();
+                }
+            }
+        }
+        self.renderer_channel.send(RendererMessage::Render).unwrap();
+        HandleResult::Handled
     }
 
     fn set_form_state_initial_values(
@@ -72,6 +134,68 @@ impl SignalDisplayPropertiesSetter {
         signal_name: &str,
         simulation_spec: RwLockReadGuard<'_, SimulationSpec>,
     ) {
+        let wave_spec = simulation_spec.get_wave_spec(signal_name).unwrap();
+        let state: &mut DropDownState = form_state
+            .get_input_state_mut(10)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let formatter_idx = wave_spec.formatter.as_ref().and_then(|formatter| {
+            plugin::formatters()
+                .iter()
+                .position(|f| f.name() == formatter.name())
+        });
+        state
+            .select(formatter_idx.map_or(0, |idx| idx + 1))
+            .unwrap();
+
+        let state: &mut CommandLineState = form_state
+            .get_input_state_mut(9)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        state.set_text(
+            &wave_spec
+                .color
+                .map_or(String::new(), |color| color.to_string()),
+        );
+
+        let state: &mut DropDownState = form_state
+            .get_input_state_mut(8)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        state.select(wave_spec.interpolate as usize).unwrap();
+
+        for (idx, bound) in [(6, wave_spec.analog_min), (7, wave_spec.analog_max)] {
+            let state: &mut CommandLineState = form_state
+                .get_input_state_mut(idx)
+                .unwrap()
+                .try_into()
+                .unwrap();
+            state.set_text(&bound.map_or(String::new(), |bound| bound.to_string()));
+        }
+
+        let state: &mut DropDownState = form_state
+            .get_input_state_mut(5)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let enum_map_idx = wave_spec.enum_map.as_ref().and_then(|enum_map| {
+            simulation_spec
+                .enum_maps
+                .iter()
+                .position(|m| m.name == enum_map.name)
+        });
+        state.select(enum_map_idx.map_or(0, |idx| idx + 1)).unwrap();
+
+        let state: &mut CommandLineState = form_state
+            .get_input_state_mut(4)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        state.set_text(&wave_spec.fraction_bits.to_string());
+
         let state: &mut CommandLineState = form_state
             .get_input_state_mut(3)
             .unwrap()
@@ -117,6 +241,11 @@ impl SignalDisplayPropertiesSetter {
             Radix::Hexadecimal => state.select(1).unwrap(),
             Radix::Octal => state.select(2).unwrap(),
             Radix::Decimal => state.select(3).unwrap(),
+            Radix::Ascii => state.select(4).unwrap(),
+            Radix::FixedPoint => state.select(5).unwrap(),
+            Radix::Float => state.select(6).unwrap(),
+            Radix::Enum => state.select(7).unwrap(),
+            Radix::Custom => state.select(8).unwrap(),
         };
     }
 
@@ -126,6 +255,11 @@ impl SignalDisplayPropertiesSetter {
             "Hexadecimal" => Radix::Hexadecimal,
             "Octal" => Radix::Octal,
             "Decimal" => Radix::Decimal,
+            "ASCII" => Radix::Ascii,
+            "Fixed-point" => Radix::FixedPoint,
+            "Float" => Radix::Float,
+            "Enum" => Radix::Enum,
+            "Custom" => Radix::Custom,
             _ => panic!(""),
         };
 
@@ -156,6 +290,77 @@ impl SignalDisplayPropertiesSetter {
             .radix = radix;
     }
 
+    fn set_wave_spec_fraction_bits(&mut self, entry: &str) {
+        let mut simulation_spec = self.simulation_spec.write().unwrap();
+        let wave_spec = simulation_spec
+            .get_wave_spec_mut(&self.signal_name)
+            .unwrap();
+        // Invalid input, or more fraction bits than the signal has, keeps the current number
+        match entry.parse() {
+            Ok(fraction_bits) if fraction_bits <= wave_spec.wave.width => {
+                wave_spec.fraction_bits = fraction_bits
+            }
+            _ => (),
+        }
+    }
+
+    fn set_wave_spec_enum_map(&mut self, name: &str) {
+        let mut simulation_spec = self.simulation_spec.write().unwrap();
+        let enum_map = simulation_spec.get_enum_map(name).cloned();
+        simulation_spec
+            .get_wave_spec_mut(&self.signal_name)
+            .unwrap()
+            .enum_map = enum_map;
+    }
+
+    fn set_wave_spec_formatter(&mut self, name: &str) {
+        self.simulation_spec
+            .write()
+            .unwrap()
+            .get_wave_spec_mut(&self.signal_name)
+            .unwrap()
+            .formatter = plugin::find_formatter(name);
+    }
+
+    fn set_wave_spec_analog_range(&mut self, min: &str, max: &str) {
+        // An empty bound follows the values in view, and invalid input keeps the current bound
+        let parse_bound = |entry: &str, current| match entry.trim() {
+            "" => None,
+            entry => entry.parse().ok().or(current),
+        };
+        let mut simulation_spec = self.simulation_spec.write().unwrap();
+        let wave_spec = simulation_spec
+            .get_wave_spec_mut(&self.signal_name)
+            .unwrap();
+        wave_spec.analog_min = parse_bound(min, wave_spec.analog_min);
+        wave_spec.analog_max = parse_bound(max, wave_spec.analog_max);
+    }
+
+    fn set_wave_spec_interpolate(&mut self, interpolation: &str) {
+        self.simulation_spec
+            .write()
+            .unwrap()
+            .get_wave_spec_mut(&self.signal_name)
+            .unwrap()
+            .interpolate = interpolation == "Linear";
+    }
+
+    fn set_wave_spec_color(&mut self, entry: &str) {
+        // An empty entry goes back to the theme's color, and invalid input keeps the current one
+        let mut simulation_spec = self.simulation_spec.write().unwrap();
+        let wave_spec = simulation_spec
+            .get_wave_spec_mut(&self.signal_name)
+            .unwrap();
+        match entry.trim() {
+            "" => wave_spec.color = None,
+            entry => {
+                if let Ok(color) = theme::parse_color(entry) {
+                    wave_spec.color = Some(color);
+                }
+            }
+        }
+    }
+
     fn set_wave_spec_signed(&mut self, signed: bool) {
         self.simulation_spec
             .write()
@@ -194,32 +399,13 @@ impl Component for SignalDisplayPropertiesSetter {
         match key_event.code {
             KeyCode::Char(ch) if self.form_state.is_command_line() => self.form_state.put(ch),
             KeyCode::Backspace => self.form_state.backspace(),
-            KeyCode::Up | KeyCode::Char('k') => self.form_state.up(),
-            KeyCode::Down | KeyCode::Char('j') | KeyCode::Tab => self.form_state.down(),
-            KeyCode::Left | KeyCode::Char('h') => self.form_state.left(),
-            KeyCode::Right | KeyCode::Char('l') => self.form_state.right(),
-            KeyCode::Enter => {
-                if self.form_state.is_apply() {
-                    let (radix, signed, plot_type, height) =
-                        Self::parse_user_input(&self.form_state.entries()).unwrap();
-                    self.set_wave_spec_radix(radix);
-                    self.set_wave_spec_signed(signed);
-                    self.set_wave_spec_plot_type(plot_type);
-                    if matches!(plot_type, PlotType::Analog) {
-                        self.set_wave_spec_height(height);
-                    }
-                    return HandleResult::ReleaseFocus;
-                } else if self.form_state.is_cancel() {
-                    return HandleResult::ReleaseFocus;
-                } else if self.form_state.is_dropdown() {
-                    self.form_state.enter();
-                } else {
-                    self.form_state.down();
+            _ => match self.key_bindings.press(KeyId::from(key_event)) {
+                KeyPress::Action(action) => return self.perform(action),
+                KeyPress::Unbound if matches!(key_event.code, KeyCode::F(_)) => {
+                    return HandleResult::NotHandled
                 }
-            }
-            KeyCode::Char('q') | KeyCode::Esc => return HandleResult::ReleaseFocus,
-            KeyCode::F(_) => return HandleResult::NotHandled,
-            _ => (),
+                KeyPress::Pending | KeyPress::Unbound => (),
+            },
         }
         self.renderer_channel.send(RendererMessage::Render).unwrap();
         HandleResult::Handled
@@ -229,6 +415,18 @@ impl Component for SignalDisplayPropertiesSetter {
         HandleResult::NotHandled
     }
 
+    fn handle_mouse_event(&mut self, mouse_event: &MouseEvent) -> HandleResult {
+        if mouse_event.kind != MouseEventKind::Down(MouseButton::Left) {
+            return HandleResult::NotHandled;
+        }
+        let position = Position::new(mouse_event.column, mouse_event.row);
+        if self.form_state.click(position) {
+            return self.perform(FormAction::Confirm);
+        }
+        self.renderer_channel.send(RendererMessage::Render).unwrap();
+        HandleResult::Handled
+    }
+
     fn handle_focus_gained(&mut self) -> HandleResult {
         unimplemented!()
     }
@@ -1,10 +1,11 @@
 use std::sync::{mpsc::Sender, Arc, RwLock};
 
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, MouseEvent};
 use oombak_sim::response::LoadedDut;
 use ratatui::{layout::Rect, Frame};
 
 use crate::{
+    backend::key_bindings::{ActionInfo, KeyBindings},
     component::{Component, HandleResult},
     components::{models::SimulationSpec, TokioSender},
     threads::RendererMessage,
@@ -18,8 +19,59 @@ use super::{
     signal_value_setter::SignalValueSetter,
 };
 
+/// Actions of the setter forms, which share the `form` table of `keys.toml`
+#[derive(Clone, Copy)]
+pub enum FormAction {
+    Close,
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Confirm,
+}
+
+const FORM_ACTIONS: [ActionInfo<FormAction>; 6] = [
+    ActionInfo {
+        action: FormAction::Close,
+        name: "close",
+        description: "close window",
+        default_keys: &["q", "esc"],
+    },
+    ActionInfo {
+        action: FormAction::MoveUp,
+        name: "move-up",
+        description: "move up",
+        default_keys: &["up", "k"],
+    },
+    ActionInfo {
+        action: FormAction::MoveDown,
+        name: "move-down",
+        description: "move down",
+        default_keys: &["down", "j", "tab"],
+    },
+    ActionInfo {
+        action: FormAction::MoveLeft,
+        name: "move-left",
+        description: "move left",
+        default_keys: &["left", "h"],
+    },
+    ActionInfo {
+        action: FormAction::MoveRight,
+        name: "move-right",
+        description: "move right",
+        default_keys: &["right", "l"],
+    },
+    ActionInfo {
+        action: FormAction::Confirm,
+        name: "confirm",
+        description: "open / close dropdown; confirm; move down",
+        default_keys: &["enter"],
+    },
+];
+
 pub struct SignalPropertiesEditor {
     selector: Selector,
+    form_key_bindings: KeyBindings<FormAction>,
     signal_name: Option<String>,
     input_ports: Vec<String>,
     simulation_spec: Arc<RwLock<SimulationSpec>>,
@@ -35,6 +87,7 @@ impl SignalPropertiesEditor {
     ) -> Self {
         Self {
             selector: Selector::new(vec![], renderer_channel.clone()),
+            form_key_bindings: KeyBindings::load_or_defaults(&FORM_ACTIONS, "form"),
             signal_name: None,
             input_ports: vec![],
             simulation_spec,
@@ -64,6 +117,7 @@ impl SignalPropertiesEditor {
                         name.to_string(),
                         self.renderer_channel.clone(),
                         self.sim_request_channel.clone(),
+                        self.form_key_bindings.clone(),
                     ))),
                 ),
                 Selection::new(
@@ -72,6 +126,7 @@ impl SignalPropertiesEditor {
                         name.to_string(),
                         self.renderer_channel.clone(),
                         self.sim_request_channel.clone(),
+                        self.form_key_bindings.clone(),
                     ))),
                 ),
                 Selection::new(
@@ -80,6 +135,7 @@ impl SignalPropertiesEditor {
                         name.to_string(),
                         self.simulation_spec.clone(),
                         self.renderer_channel.clone(),
+                        self.form_key_bindings.clone(),
                     ))),
                 ),
             ];
@@ -129,6 +185,10 @@ impl Component for SignalPropertiesEditor {
         self.selector.handle_resize_event(columns, rows)
     }
 
+    fn handle_mouse_event(&mut self, mouse_event: &MouseEvent) -> HandleResult {
+        self.selector.handle_mouse_event(mouse_event)
+    }
+
     fn handle_focus_gained(&mut self) -> HandleResult {
         self.selector.handle_focus_gained()
     }
@@ -1,18 +1,19 @@
-use std::{
-    collections::HashMap,
-    sync::{mpsc::Sender, Arc, RwLock},
-};
+use std::sync::{mpsc::Sender, Arc, RwLock};
 
 use bitvec::vec::BitVec;
-use crossterm::event::{KeyCode, KeyEvent};
-use ratatui::{layout::Rect, Frame};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    layout::{Position, Rect},
+    Frame,
+};
 
 use crate::{
+    backend::key_bindings::{KeyBindings, KeyPress},
     component::{Component, HandleResult},
-    components::TokioSender,
+    components::{signal_properties_editor::FormAction, TokioSender},
     threads::RendererMessage,
     utils,
-    widgets::{Form, FormState, InputField, KeyDesc, KeyId, KeyMaps},
+    widgets::{Form, FormState, InputField, KeyId, KeyMaps},
 };
 
 pub struct SignalValueSetter {
@@ -20,6 +21,7 @@ pub struct SignalValueSetter {
     renderer_channel: Sender<RendererMessage>,
     sim_request_channel: TokioSender<oombak_sim::Message>,
     form_state: FormState,
+    key_bindings: KeyBindings<FormAction>,
     key_maps: KeyMaps,
 }
 
@@ -28,6 +30,7 @@ impl SignalValueSetter {
         signal_name: String,
         renderer_channel: Sender<RendererMessage>,
         sim_request_channel: TokioSender<oombak_sim::Message>,
+        key_bindings: KeyBindings<FormAction>,
     ) -> Self {
         let input_fields = vec![InputField::text("Value")];
         let form_state = FormState::new(input_fields);
@@ -36,24 +39,34 @@ impl SignalValueSetter {
             renderer_channel,
             sim_request_channel,
             form_state,
-            key_maps: Self::create_key_maps(),
+            key_maps: key_bindings.key_maps(),
+            key_bindings,
         }
     }
 
-    fn create_key_maps() -> KeyMaps {
-        KeyMaps::from(HashMap::from([
-            (KeyId::from('q'), KeyDesc::from("close window")),
-            (KeyId::from(KeyCode::Esc), KeyDesc::from("close window")),
-            (KeyId::from('k'), KeyDesc::from("move up")),
-            (KeyId::from(KeyCode::Up), KeyDesc::from("move up")),
-            (KeyId::from('j'), KeyDesc::from("move down")),
-            (KeyId::from(KeyCode::Down), KeyDesc::from("move down")),
-            (KeyId::from(KeyCode::Tab), KeyDesc::from("move down")),
-            (
-                KeyId::from(KeyCode::Enter),
-                KeyDesc::from("confirm; move down"),
-            ),
-        ]))
+    fn perform(&mut self, action: FormAction) -> HandleResult {
+        match action {
+            FormAction::Close => return HandleResult::ReleaseFocus,
+            FormAction::MoveUp => self.form_state.up(),
+            FormAction::MoveDown => self.form_state.down(),
+            FormAction::MoveLeft => self.form_state.left(),
+            FormAction::MoveRight => self.form_state.right(),
+            FormAction::Confirm => {
+                if self.form_state.is_apply() {
+                    match Self::parse_user_input(&self.form_state.entries()) {
+                        Ok(value) => self.request_set_signal(value),
+                        Err(_) => todo!(),
+                    }
+                    return HandleResult::ReleaseFocus;
+                } else if self.form_state.is_cancel() {
+                    return HandleResult::ReleaseFocus;
+                } else {
+                    self.form_state.down();
+                }
+            }
+        }
+        self.renderer_channel.send(RendererMessage::Render).unwrap();
+        HandleResult::Handled
     }
 
     fn parse_user_input(entries: &[String]) -> Result<BitVec<u32>, String> {
@@ -82,26 +95,13 @@ impl Component for SignalValueSetter {
         match key_event.code {
             KeyCode::Char(ch) if self.form_state.is_command_line() => self.form_state.put(ch),
             KeyCode::Backspace => self.form_state.backspace(),
-            KeyCode::Up => self.form_state.up(),
-            KeyCode::Down | KeyCode::Tab => self.form_state.down(),
-            KeyCode::Left => self.form_state.left(),
-            KeyCode::Right => self.form_state.right(),
-            KeyCode::Enter => {
-                if self.form_state.is_apply() {
-                    match Self::parse_user_input(&self.form_state.entries()) {
-                        Ok(value) => self.request_set_signal(value),
-                        Err(_) => todo!(),
-                    }
-                    return HandleResult::ReleaseFocus;
-                } else if self.form_state.is_cancel() {
-                    return HandleResult::ReleaseFocus;
-                } else {
-                    self.form_state.down();
+            _ => match self.key_bindings.press(KeyId::from(key_event)) {
+                KeyPress::Action(action) => return self.perform(action),
+                KeyPress::Unbound if matches!(key_event.code, KeyCode::F(_)) => {
+                    return HandleResult::NotHandled
                 }
-            }
-            KeyCode::Char('q') | KeyCode::Esc => return HandleResult::ReleaseFocus,
-            KeyCode::F(_) => return HandleResult::NotHandled,
-            _ => (),
+                KeyPress::Pending | KeyPress::Unbound => (),
+            },
         }
         self.renderer_channel.send(RendererMessage::Render).unwrap();
         HandleResult::Handled
@@ -111,6 +111,18 @@ impl Component for SignalValueSetter {
         HandleResult::NotHandled
     }
 
+    fn handle_mouse_event(&mut self, mouse_event: &MouseEvent) -> HandleResult {
+        if mouse_event.kind != MouseEventKind::Down(MouseButton::Left) {
+            return HandleResult::NotHandled;
+        }
+        let position = Position::new(mouse_event.column, mouse_event.row);
+        if self.form_state.click(position) {
+            return self.perform(FormAction::Confirm);
+        }
+        self.renderer_channel.send(RendererMessage::Render).unwrap();
+        HandleResult::Handled
+    }
+
     fn handle_focus_gained(&mut self) -> HandleResult {
         unimplemented!()
     }
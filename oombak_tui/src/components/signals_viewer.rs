@@ -1,6 +1,7 @@
 use std::sync::{Arc, RwLock, RwLockReadGuard};
 
 use ratatui::{
+    layout::{Position, Rect},
     style::Style,
     symbols,
     text::{Line, Span},
@@ -9,12 +10,13 @@ use ratatui::{
 
 use crate::{
     styles::signals_viewer::{
-        SELECTED_SIGNAL_STYLE, SIGNAL_NAME_STYLE, SIGNAL_VALUE_STYLE, SIGNAL_WIDTH_STYLE,
+        GROUP_NAME_STYLE, MARKER_VALUE_STYLE, SELECTED_SIGNAL_STYLE, SIGNAL_NAME_STYLE,
+        SIGNAL_VALUE_STYLE, SIGNAL_WIDTH_STYLE,
     },
     utils::{self, bitvec_str},
 };
 
-use super::models::{SimulationSpec, WaveSpec};
+use super::models::{Group, Marker, SimulationSpec, WaveSpec};
 
 #[derive(Default)]
 pub struct SignalsViewer {
@@ -22,6 +24,7 @@ pub struct SignalsViewer {
     list_state: ListState,
     selected_idx: Option<usize>,
     highlight_idx: usize,
+    list_area: Rect,
 }
 
 impl SignalsViewer {
@@ -35,7 +38,7 @@ impl SignalsViewer {
     }
 
     pub fn reload(&mut self) {
-        if !self.get_simulation().wave_specs.is_empty() {
+        if !self.get_simulation().shown_wave_specs().is_empty() {
             self.selected_idx = Some(0);
             self.list_state.select_first();
         } else {
@@ -46,7 +49,7 @@ impl SignalsViewer {
     pub fn scroll_down(&mut self) {
         if let Some(idx) = self.selected_idx {
             self.list_state.select_next();
-            let number_of_waves = self.get_simulation().wave_specs.len();
+            let number_of_waves = self.get_simulation().shown_wave_specs().len();
             self.selected_idx = Some(usize::min(number_of_waves - 1, idx + 1));
         }
     }
@@ -58,12 +61,32 @@ impl SignalsViewer {
         }
     }
 
+    pub fn select(&mut self, idx: usize) {
+        self.list_state.select(Some(idx));
+        self.selected_idx = Some(idx);
+    }
+
+    pub fn signal_at(&self, position: Position) -> Option<usize> {
+        let item_heights: Vec<u16> = self
+            .get_simulation()
+            .shown_wave_specs()
+            .into_iter()
+            .map(|ws| ws.height * 2 + 2)
+            .collect();
+        utils::layout::get_list_item_at(
+            self.list_area,
+            self.list_state.offset(),
+            item_heights,
+            position,
+        )
+    }
+
     pub fn selected_signal_name(&self) -> Option<String> {
-        self.selected_idx.map(|i| {
-            self.get_simulation().wave_specs[i]
-                .wave
-                .signal_name
-                .to_string()
+        self.selected_idx.and_then(|i| {
+            self.get_simulation()
+                .shown_wave_specs()
+                .get(i)
+                .map(|ws| ws.wave.signal_name.to_string())
         })
     }
 
@@ -71,58 +94,126 @@ impl SignalsViewer {
         let items = self.create_list_items(rect.width);
         let list = List::new(items);
         f.render_stateful_widget(list, rect, &mut self.list_state);
+        self.list_area = rect;
     }
 
     fn create_list_items<'a>(&self, width: u16) -> Vec<ListItem<'a>> {
-        self.get_simulation()
-            .wave_specs
-            .iter()
+        let simulation = self.get_simulation();
+        let marker = simulation.reference_marker();
+        simulation
+            .shown_wave_specs()
+            .into_iter()
             .enumerate()
             .map(|(i, spec)| {
+                let signal_name = &spec.wave.signal_name;
+                let prefix = if simulation.is_collapsed(signal_name) {
+                    "▸"
+                } else if spec.group.is_some() {
+                    "▾"
+                } else if simulation.parent_of(signal_name).is_some() {
+                    "  └"
+                } else if simulation.is_expanded(signal_name) {
+                    "▾"
+                } else if spec.wave.width > 1 {
+                    "▸"
+                } else {
+                    " "
+                };
                 if Some(i) == self.selected_idx {
-                    self.new_list_item(spec, width, SELECTED_SIGNAL_STYLE)
+                    self.new_list_item(spec, prefix, marker, width, SELECTED_SIGNAL_STYLE.get())
                 } else {
-                    self.new_list_item(spec, width, Style::default())
+                    self.new_list_item(spec, prefix, marker, width, Style::default())
                 }
             })
             .collect()
     }
 
-    fn new_list_item<'a>(&self, wave_spec: &WaveSpec, width: u16, style: Style) -> ListItem<'a> {
+    fn new_list_item<'a>(
+        &self,
+        wave_spec: &WaveSpec,
+        prefix: &'static str,
+        marker: Option<&Marker>,
+        width: u16,
+        style: Style,
+    ) -> ListItem<'a> {
         let list_item_height = (wave_spec.height * 2 + 1) as usize;
         let mut lines = vec![Line::from(" ").style(style); list_item_height];
         let horizontal_line = Self::create_horizontal_line(width);
-        lines[list_item_height / 2] = self.new_signal_description(wave_spec, style);
+        lines[list_item_height / 2] = match &wave_spec.group {
+            Some(group) => Self::new_group_description(wave_spec, group, prefix, style),
+            None => self.new_signal_description(wave_spec, prefix, marker, style),
+        };
         lines.push(horizontal_line);
         lines.into()
     }
 
-    fn new_signal_description<'a>(&self, wave_spec: &WaveSpec, style: Style) -> Line<'a> {
+    fn new_group_description<'a>(
+        wave_spec: &WaveSpec,
+        group: &Group,
+        prefix: &'static str,
+        style: Style,
+    ) -> Line<'a> {
+        let mut spans = vec![
+            Span::from(prefix),
+            Span::from(" "),
+            Span::from(wave_spec.wave.signal_name.clone()).style(GROUP_NAME_STYLE),
+        ];
+        if !group.collapsed_rows.is_empty() {
+            spans.push(Span::from(format!(
+                " ({} rows)",
+                group.collapsed_rows.len()
+            )));
+        }
+        Line::from(spans).style(style)
+    }
+
+    fn new_signal_description<'a>(
+        &self,
+        wave_spec: &WaveSpec,
+        prefix: &'static str,
+        marker: Option<&Marker>,
+        style: Style,
+    ) -> Line<'a> {
         let signal_name = Span::from(wave_spec.wave.signal_name.clone()).style(SIGNAL_NAME_STYLE);
         let signal_width =
             Span::from(format!("[{}:0]", wave_spec.wave.width - 1)).style(SIGNAL_WIDTH_STYLE);
-        let signal_value = Span::from(self.get_highlighted_value_of(wave_spec).to_string())
-            .style(SIGNAL_VALUE_STYLE);
-        Line::from(vec![
+        let signal_value =
+            Span::from(Self::get_value_at(wave_spec, self.highlight_idx)).style(SIGNAL_VALUE_STYLE);
+        let mut spans = vec![
+            Span::from(prefix),
             Span::from(" "),
             signal_name,
             Span::from(" "),
             signal_width,
             Span::from(" ("),
             signal_value,
-            Span::from(")"),
-        ])
-        .style(style)
+        ];
+        if let Some(marker) = marker {
+            let marker_value = format!(
+                "{}: {}",
+                marker.name,
+                Self::get_value_at(wave_spec, marker.time)
+            );
+            spans.push(Span::from(" | "));
+            spans.push(Span::from(marker_value).style(MARKER_VALUE_STYLE));
+        }
+        spans.push(Span::from(")"));
+        Line::from(spans).style(style)
     }
 
-    fn get_highlighted_value_of(&self, wave_spec: &WaveSpec) -> String {
-        if let Some(value) = wave_spec.wave.at(self.highlight_idx) {
+    fn get_value_at(wave_spec: &WaveSpec, time: usize) -> String {
+        if let Some(value) = wave_spec.wave.at(time) {
             let option = bitvec_str::Option::from(wave_spec);
             let prefix = match option.radix {
                 bitvec_str::Radix::Binary => "0b",
                 bitvec_str::Radix::Hexadecimal => "0x",
                 bitvec_str::Radix::Octal => "0o",
-                bitvec_str::Radix::Decimal => "",
+                bitvec_str::Radix::Decimal
+                | bitvec_str::Radix::Ascii
+                | bitvec_str::Radix::FixedPoint
+                | bitvec_str::Radix::Float
+                | bitvec_str::Radix::Enum
+                | bitvec_str::Radix::Custom => "",
             };
             format!("{prefix}{}", utils::bitvec_str::from(value, &option))
         } else {
@@ -0,0 +1,275 @@
+use std::sync::{mpsc::Sender, Arc, RwLock};
+
+use crossterm::event::{KeyEvent, MouseEvent, MouseEventKind};
+use ratatui::{
+    layout::{Constraint, Rect},
+    text::Line,
+    widgets::{Cell, Row, Table, TableState},
+    Frame,
+};
+
+use crate::{
+    backend::key_bindings::{ActionInfo, KeyBindings, KeyPress},
+    component::{Component, HandleResult},
+    styles::{
+        global::SELECTED_ITEM_STYLE,
+        transaction_viewer::{ERROR_STYLE, HEADER_STYLE},
+    },
+    threads::RendererMessage,
+    widgets::{self, KeyId, KeyMaps},
+};
+
+use super::models::SimulationSpec;
+
+#[derive(Clone, Copy)]
+enum Action {
+    Close,
+    PreviousTransaction,
+    NextTransaction,
+    FirstTransaction,
+    LastTransaction,
+}
+
+const ACTIONS: [ActionInfo<Action>; 5] = [
+    ActionInfo {
+        action: Action::Close,
+        name: "close",
+        description: "close window",
+        default_keys: &["esc", "ctrl-d"],
+    },
+    ActionInfo {
+        action: Action::PreviousTransaction,
+        name: "previous-transaction",
+        description: "previous transaction",
+        default_keys: &["up"],
+    },
+    ActionInfo {
+        action: Action::NextTransaction,
+        name: "next-transaction",
+        description: "next transaction",
+        default_keys: &["down"],
+    },
+    ActionInfo {
+        action: Action::FirstTransaction,
+        name: "first-transaction",
+        description: "first transaction",
+        default_keys: &["home"],
+    },
+    ActionInfo {
+        action: Action::LastTransaction,
+        name: "last-transaction",
+        description: "last transaction",
+        default_keys: &["end"],
+    },
+];
+
+/// Lists the transactions of a decoder, moving the cursor to the selected one.
+pub struct TransactionViewer {
+    message_tx: Sender<RendererMessage>,
+    simulation_spec: Arc<RwLock<SimulationSpec>>,
+    decoder_name: String,
+    table_state: TableState,
+    key_bindings: KeyBindings<Action>,
+    key_mappings: KeyMaps,
+    time_to_show: Option<usize>,
+}
+
+impl TransactionViewer {
+    pub fn new(
+        simulation_spec: Arc<RwLock<SimulationSpec>>,
+        message_tx: Sender<RendererMessage>,
+    ) -> Self {
+        let key_bindings = KeyBindings::load_or_defaults(&ACTIONS, "transactions");
+        let key_mappings = key_bindings.key_maps();
+        Self {
+            message_tx,
+            simulation_spec,
+            decoder_name: String::new(),
+            table_state: TableState::default(),
+            key_bindings,
+            key_mappings,
+            time_to_show: None,
+        }
+    }
+
+    pub fn set_decoder(&mut self, decoder_name: &str) {
+        self.decoder_name = decoder_name.to_string();
+        self.table_state.select(None);
+    }
+
+    pub fn decoder_name(&self) -> &str {
+        &self.decoder_name
+    }
+
+    pub fn take_time_to_show(&mut self) -> Option<usize> {
+        self.time_to_show.take()
+    }
+
+    fn num_of_transactions(&self) -> usize {
+        self.simulation_spec
+            .read()
+            .unwrap()
+            .get_decoder(&self.decoder_name)
+            .map_or(0, |decoder_spec| decoder_spec.transactions.len())
+    }
+
+    fn select(&mut self, idx: usize) {
+        let simulation_spec = self.simulation_spec.read().unwrap();
+        let transaction = simulation_spec
+            .get_decoder(&self.decoder_name)
+            .and_then(|decoder_spec| decoder_spec.transactions.get(idx));
+        if let Some(transaction) = transaction {
+            self.table_state.select(Some(idx));
+            self.time_to_show = Some(transaction.start_time);
+        }
+    }
+
+    fn select_next(&mut self) {
+        let idx = self.table_state.selected().map_or(0, |idx| idx + 1);
+        self.select(idx);
+    }
+
+    fn select_previous(&mut self) {
+        if let Some(idx) = self.table_state.selected() {
+            self.select(idx.saturating_sub(1));
+        }
+    }
+
+    fn perform(&mut self, action: Action) -> HandleResult {
+        match action {
+            Action::Close => return HandleResult::ReleaseFocus,
+            Action::PreviousTransaction => self.select_previous(),
+            Action::NextTransaction => self.select_next(),
+            Action::FirstTransaction => self.select(0),
+            Action::LastTransaction => self.select(self.num_of_transactions().saturating_sub(1)),
+        }
+        self.notify_render();
+        HandleResult::Handled
+    }
+
+    fn notify_render(&self) {
+        self.message_tx.send(RendererMessage::Render).unwrap();
+    }
+}
+
+impl Component for TransactionViewer {
+    fn render(&mut self, f: &mut Frame, rect: Rect) {
+        let simulation_spec = self.simulation_spec.read().unwrap();
+        let transactions = match simulation_spec.get_decoder(&self.decoder_name) {
+            Some(decoder_spec) if !decoder_spec.transactions.is_empty() => {
+                &decoder_spec.transactions
+            }
+            Some(_) => {
+                f.render_widget(Line::from(" No transaction decoded"), rect);
+                return;
+            }
+            None => {
+                let message = format!(" {} is not a decoder", self.decoder_name);
+                f.render_widget(Line::from(message).style(ERROR_STYLE), rect);
+                return;
+            }
+        };
+        let time_step_ps = simulation_spec.time_step_ps();
+        let rows = transactions.iter().map(|transaction| {
+            let row = Row::new(vec![
+                Cell::from(widgets::format_time(
+                    (transaction.start_time * time_step_ps) as f64,
+                )),
+                Cell::from(widgets::format_time(
+                    (transaction.end_time * time_step_ps) as f64,
+                )),
+                Cell::from(transaction.label.clone()),
+            ]);
+            if transaction.is_error {
+                row.style(ERROR_STYLE)
+            } else {
+                row
+            }
+        });
+        let header = Row::new(["Start", "End", "Transaction"]).style(HEADER_STYLE);
+        let widths = [
+            Constraint::Length(14),
+            Constraint::Length(14),
+            Constraint::Fill(1),
+        ];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .row_highlight_style(SELECTED_ITEM_STYLE);
+        f.render_stateful_widget(table, rect, &mut self.table_state);
+    }
+
+    fn handle_key_event(&mut self, key_event: &KeyEvent) -> HandleResult {
+        match self.key_bindings.press(KeyId::from(key_event)) {
+            KeyPress::Action(action) => self.perform(action),
+            KeyPress::Pending => HandleResult::Handled,
+            KeyPress::Unbound => HandleResult::NotHandled,
+        }
+    }
+
+    fn handle_resize_event(&mut self, _columns: u16, _rows: u16) -> HandleResult {
+        HandleResult::NotHandled
+    }
+
+    fn handle_mouse_event(&mut self, mouse_event: &MouseEvent) -> HandleResult {
+        match mouse_event.kind {
+            MouseEventKind::ScrollUp => self.select_previous(),
+            MouseEventKind::ScrollDown => self.select_next(),
+            _ => return HandleResult::NotHandled,
+        }
+        self.notify_render();
+        HandleResult::Handled
+    }
+
+    fn handle_focus_gained(&mut self) -> HandleResult {
+        HandleResult::Handled
+    }
+
+    fn get_focused_child(&self) -> Option<Arc<RwLock<dyn Component>>> {
+        None
+    }
+
+    fn get_key_mappings(&self) -> KeyMaps {
+        self.key_mappings.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{mpsc, Arc, RwLock};
+
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use super::TransactionViewer;
+    use crate::{
+        backend::decoder::Transaction,
+        component::Component,
+        components::models::{DecoderSpec, SimulationSpec},
+    };
+
+    #[test]
+    fn test_render_unreported_time_step() {
+        let simulation_spec = SimulationSpec {
+            time_step_ps: 0,
+            decoders: vec![DecoderSpec {
+                name: "bus".to_string(),
+                protocol: "apb".to_string(),
+                arguments: Default::default(),
+                transactions: vec![Transaction::new(2, 5, "write".to_string())],
+            }],
+            ..Default::default()
+        };
+        let (message_tx, _message_rx) = mpsc::channel();
+        let mut viewer = TransactionViewer::new(Arc::new(RwLock::new(simulation_spec)), message_tx);
+        viewer.set_decoder("bus");
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 2)).unwrap();
+        terminal.draw(|f| viewer.render(f, f.area())).unwrap();
+        let row: String = (0..40)
+            .map(|x| terminal.backend().buffer()[(x, 1)].symbol())
+            .collect();
+        assert_eq!(
+            row.split_whitespace().collect::<Vec<_>>(),
+            ["2.00", "ps", "5.00", "ps", "write"]
+        );
+    }
+}
@@ -2,15 +2,16 @@ use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 use ratatui::{
     buffer::Buffer,
-    layout::{Constraint, Layout, Rect},
+    layout::{Constraint, Layout, Position, Rect},
     symbols,
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState, StatefulWidget},
+    widgets::{Block, Borders, List, ListItem, ListState, StatefulWidget, Widget},
     Frame,
 };
 
 use crate::{
     styles::wave_viewer::SELECTED_WAVEFORM_STYLE,
+    utils,
     widgets::{ScrollState, TimeBar, Waveform},
 };
 
@@ -24,6 +25,11 @@ pub struct WaveViewer {
     list_state: ListState,
     selected_idx: Option<usize>,
     scroll_state: ScrollState,
+    area: Rect,
+    list_area: Rect,
+    drag_column: Option<u16>,
+    /// Cursor to restore once the simulation reaches it
+    pending_highlight: Option<usize>,
 }
 
 impl WaveViewer {
@@ -33,7 +39,7 @@ impl WaveViewer {
     }
 
     pub fn reload(&mut self) {
-        if !self.get_simulation().wave_specs.is_empty() {
+        if !self.get_simulation().shown_wave_specs().is_empty() {
             self.list_state.select_first();
             self.selected_idx = Some(0);
         } else {
@@ -43,17 +49,19 @@ impl WaveViewer {
     }
 
     pub fn scroll_right(&mut self) {
+        self.pending_highlight = None;
         self.scroll_state.next();
     }
 
     pub fn scroll_left(&mut self) {
+        self.pending_highlight = None;
         self.scroll_state.prev();
     }
 
     pub fn scroll_down(&mut self) {
         if let Some(idx) = self.selected_idx {
             self.list_state.select_next();
-            let number_of_waves = self.get_simulation().wave_specs.len();
+            let number_of_waves = self.get_simulation().shown_wave_specs().len();
             self.selected_idx = Some(usize::min(number_of_waves - 1, idx + 1));
         }
     }
@@ -65,6 +73,55 @@ impl WaveViewer {
         }
     }
 
+    pub fn select(&mut self, idx: usize) {
+        self.list_state.select(Some(idx));
+        self.selected_idx = Some(idx);
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        self.area.contains(position)
+    }
+
+    pub fn wave_at(&self, position: Position) -> Option<usize> {
+        let item_heights: Vec<u16> = self
+            .get_simulation()
+            .shown_wave_specs()
+            .into_iter()
+            .map(|ws| ws.height * 2 + 2)
+            .collect();
+        utils::layout::get_list_item_at(
+            self.list_area,
+            self.list_state.offset(),
+            item_heights,
+            position,
+        )
+    }
+
+    pub fn place_cursor(&mut self, column: u16) {
+        // Waveforms are drawn to the right of a 1 cell wide left border
+        if column > self.area.x {
+            self.pending_highlight = None;
+            let position = self.scroll_state.start_position() + (column - self.area.x - 1) as usize;
+            self.scroll_state.select(position);
+        }
+    }
+
+    pub fn start_drag(&mut self, column: u16) {
+        self.drag_column = Some(column);
+    }
+
+    pub fn drag(&mut self, column: u16) {
+        if let Some(drag_column) = self.drag_column {
+            self.scroll_state
+                .shift(drag_column as isize - column as isize);
+            self.drag_column = Some(column);
+        }
+    }
+
+    pub fn end_drag(&mut self) {
+        self.drag_column = None;
+    }
+
     pub fn zoom_in(&mut self) {
         {
             let mut simulation = self.get_simulation_mut();
@@ -87,6 +144,18 @@ impl WaveViewer {
         absolute_highlight_position / self.unit_width()
     }
 
+    pub fn set_highlighted_unit_time(&mut self, unit_time: usize) {
+        self.pending_highlight = None;
+        let position = unit_time * self.unit_width();
+        self.scroll_state.select(position);
+    }
+
+    /// Moves the cursor to the time, or, if it is not simulated yet, as soon as it is.
+    pub fn restore_highlighted_unit_time(&mut self, unit_time: usize) {
+        self.pending_highlight = Some(unit_time);
+        self.update_scroll_state_content_length();
+    }
+
     pub fn render_mut(&mut self, f: &mut Frame, rect: Rect) {
         let mut scroll_state = self.scroll_state;
         let items = self.new_list_items(rect.width, &mut scroll_state);
@@ -96,13 +165,27 @@ impl WaveViewer {
         let areas = Layout::vertical(vec![Constraint::Min(0), Constraint::Length(3)]).split(rect);
         f.render_stateful_widget(list, areas[0], &mut self.list_state);
         self.render_time_bar(f, areas[1]);
+        self.area = rect;
+        self.list_area = areas[0];
     }
 
     fn render_time_bar(&mut self, f: &mut Frame, area: Rect) {
         let (tick_count, tick_period) = self.calculate_preferred_tick();
-        let time_bar = TimeBar::default()
+        let unit_width = self.unit_width();
+        let mut time_bar = TimeBar::default()
             .tick_count(tick_count)
-            .tick_period(tick_period);
+            .tick_period(tick_period)
+            .time_step_ps(self.get_simulation().time_step_ps())
+            .markers(
+                self.get_simulation()
+                    .markers
+                    .iter()
+                    .map(|m| m.time * unit_width)
+                    .collect(),
+            );
+        if let Some(marker) = self.get_simulation().reference_marker() {
+            time_bar = time_bar.reference_marker(&marker.name, marker.time * unit_width);
+        }
 
         let block = Block::new().borders(Borders::LEFT);
         f.render_stateful_widget(time_bar, block.inner(area), &mut self.scroll_state);
@@ -113,6 +196,9 @@ impl WaveViewer {
         let total_time = self.get_simulation().total_time;
         let content_length = self.unit_width() * total_time;
         self.scroll_state.set_content_length(content_length);
+        if let Some(unit_time) = self.pending_highlight.filter(|t| *t < total_time) {
+            self.set_highlighted_unit_time(unit_time);
+        }
     }
 
     fn calculate_preferred_tick(&self) -> (usize, f64) {
@@ -138,10 +224,10 @@ impl WaveViewer {
         render_area_width: u16,
         scroll_state: &mut ScrollState,
     ) -> Vec<ListItem<'a>> {
-        let number_of_items = self.get_simulation().wave_specs.len();
+        let number_of_items = self.get_simulation().shown_wave_specs().len();
         self.get_simulation()
-            .wave_specs
-            .iter()
+            .shown_wave_specs()
+            .into_iter()
             .enumerate()
             .map(|(i, ws)| {
                 self.new_list_item(
@@ -172,8 +258,8 @@ impl WaveViewer {
             .border_set(border_set);
         let waveform = Waveform::new(wave_spec)
             .zoom(self.get_simulation().zoom)
-            .block(block)
-            .selected_style(SELECTED_WAVEFORM_STYLE)
+            .block(block.clone())
+            .selected_style(SELECTED_WAVEFORM_STYLE.get())
             .selected(is_selected);
         let waveform = if is_last {
             waveform
@@ -182,7 +268,11 @@ impl WaveViewer {
         };
         let list_item_height = (wave_spec.height * 2 + 1) + 1;
         let mut draw_buffer = Buffer::empty(Rect::new(0, 0, render_area_width, list_item_height));
-        waveform.render(draw_buffer.area, &mut draw_buffer, scroll_state);
+        if wave_spec.group.is_some() {
+            block.render(draw_buffer.area, &mut draw_buffer);
+        } else {
+            waveform.render(draw_buffer.area, &mut draw_buffer, scroll_state);
+        }
         ListItem::from(Self::buffer_to_lines(&draw_buffer))
     }
 
@@ -218,3 +308,26 @@ impl WaveViewer {
         NUMBER_OF_CELLS_PER_UNIT_TIME * 2usize.pow(zoom as u32)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, RwLock};
+
+    use super::WaveViewer;
+    use crate::{backend::session::Session, components::models::SimulationSpec};
+
+    #[test]
+    fn test_restore_highlighted_unit_time() {
+        let session: Session = toml::from_str("design_path = \"adder.sv\"\ncursor = 12").unwrap();
+        let simulation_spec = Arc::new(RwLock::new(SimulationSpec::default()));
+        let mut wave_viewer = WaveViewer::default().simulation(simulation_spec.clone());
+        session.apply_to(&mut simulation_spec.write().unwrap());
+        wave_viewer.reload();
+        wave_viewer.restore_highlighted_unit_time(session.cursor);
+        assert_eq!(wave_viewer.get_highlighted_unit_time(), 0);
+
+        simulation_spec.write().unwrap().total_time = 20;
+        wave_viewer.reload();
+        assert_eq!(wave_viewer.get_highlighted_unit_time(), 12);
+    }
+}
@@ -5,6 +5,10 @@ mod styles;
 mod utils;
 mod widgets;
 
+pub mod cli;
 pub mod components;
+pub mod plugin;
 pub mod threads;
 pub mod tui;
+
+pub use utils::config::AppConfig;
@@ -1,17 +1,49 @@
 use oombak_local_sim::LocalSimulator;
 use oombak_tui::{
+    cli::{self, Args},
     components,
     threads::{
         setup_terminate_group_panic_hook, EventThread, RendererThread, SimulatorRequestDispatcher,
         ThreadGroup,
     },
-    tui,
+    tui, AppConfig,
+};
+use std::{
+    env,
+    fs::File,
+    process,
+    sync::{mpsc, Arc, RwLock},
 };
-use std::sync::{mpsc, Arc, RwLock};
 
 fn main() {
+    let args = match Args::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => exit_with_error(&format!("{message}\n\n{}", cli::USAGE), 2),
+    };
+    if args.is_help {
+        println!("{}", cli::USAGE);
+        return;
+    }
+    if args.is_version {
+        println!("oombak_tui {}", env!("CARGO_PKG_VERSION"));
+        return;
+    }
+    let config = AppConfig::load(args.config_path.as_deref())
+        .unwrap_or_else(|message| exit_with_error(&message, 1));
+    for plugin_path in config.plugin_paths().iter().chain(&args.plugin_paths) {
+        oombak_tui::plugin::load(plugin_path)
+            .unwrap_or_else(|message| exit_with_error(&message, 1));
+    }
+    let log_file = args.log_path.as_ref().map(|path| {
+        File::options()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap_or_else(|e| exit_with_error(&format!("cannot open {}: {e}", path.display()), 1))
+    });
+
     let terminal = tui::init_terminal().unwrap();
-    let simulator = LocalSimulator::default();
+    let simulator = LocalSimulator::new(config.build_options());
     let simulator_request_dispatcher = SimulatorRequestDispatcher::new(Arc::new(simulator));
     let (message_channel_tx, message_channel_rx) = mpsc::channel();
 
@@ -19,11 +51,19 @@ fn main() {
         message_channel_tx.clone(),
         simulator_request_dispatcher.channel(),
     )));
+    if let Some(log_file) = log_file {
+        command_interpreter.write().unwrap().set_log_file(log_file);
+    }
+
+    let request_status_viewer = Arc::new(RwLock::new(components::RequestStatusViewer::new(
+        message_channel_tx.clone(),
+    )));
 
     let root = Arc::new(RwLock::new(components::Root::new(
         message_channel_tx.clone(),
         simulator_request_dispatcher.channel(),
         command_interpreter.clone(),
+        request_status_viewer.clone(),
     )));
 
     let mut thread_group = ThreadGroup::new();
@@ -37,8 +77,25 @@ fn main() {
     );
 
     simulator_request_dispatcher.register(command_interpreter);
+    simulator_request_dispatcher.register(request_status_viewer);
     simulator_request_dispatcher.register(root.clone());
-    event_thread.register_event_listener(root);
+    event_thread.register_event_listener(root.clone());
+
+    {
+        let mut root = root.write().unwrap();
+        if let Some(theme) = &args.theme {
+            root.set_theme(theme);
+        }
+        if let Some(session_path) = &args.session_path {
+            root.open_session(session_path);
+        }
+        if let Some(design_path) = &args.design_path {
+            root.load_design(design_path, args.top_module.clone());
+        }
+        if let Some(script_path) = &args.script_path {
+            root.run_script(script_path);
+        }
+    }
 
     thread_group.add_thread(Box::new(event_thread));
     thread_group.add_thread(Box::new(renderer_thread));
@@ -56,3 +113,8 @@ fn main() {
         }
     }
 }
+
+fn exit_with_error(message: &str, code: i32) -> ! {
+    eprintln!("error: {message}");
+    process::exit(code)
+}
@@ -0,0 +1,220 @@
+//! Extension points for value formats and protocol decoders that are not built in.
+//!
+//! Formatters and decoders are registered at startup, either by a program embedding this crate
+//! with [`register_formatter`] and [`register_decoder`], or by a plugin library given with
+//! `--plugin` or `plugins` in the configuration file. A plugin library is a `cdylib` built with
+//! the same compiler and version of this crate, as checked on loading, which declares its
+//! registration function with [`declare_plugin!`](crate::declare_plugin):
+//!
+//! ```ignore
+//! fn register(registrar: &mut oombak_tui::plugin::Registrar) {
+//!     registrar.register_formatter(Box::new(Gray));
+//! }
+//!
+//! oombak_tui::declare_plugin!(register);
+//! ```
+
+use std::{
+    ffi::{c_char, CStr},
+    path::Path,
+    sync::{Arc, Mutex, OnceLock, RwLock},
+};
+
+use libloading::{Library, Symbol};
+
+// Re-exported so that plugins are built against the same versions
+pub use bitvec::vec::BitVec;
+pub use oombak_sim::{response::Wave, Edge};
+
+pub use crate::backend::decoder::{DecoderInput, SignalRole, Transaction, WaveDecoder};
+
+/// Version of this crate and of the compiler building it. The types shared with a plugin library
+/// have no stable layout, so the library must have been built with the same ones.
+pub const BUILD_ID: &CStr = match CStr::from_bytes_with_nul(
+    concat!(
+        "oombak_tui ",
+        env!("CARGO_PKG_VERSION"),
+        " built by ",
+        env!("OOMBAK_RUSTC_VERSION"),
+        "\0"
+    )
+    .as_bytes(),
+) {
+    Ok(build_id) => build_id,
+    Err(_) => panic!("the build ID holds a NUL character"),
+};
+
+/// Displays values in a format of its own, selected with the Custom radix.
+pub trait ValueFormatter: Send + Sync {
+    /// Name of the format, as listed in the signal properties
+    fn name(&self) -> &str;
+
+    /// Formats a value of a signal `width` bits wide.
+    fn format(&self, value: &BitVec<u32>, width: usize) -> String;
+}
+
+/// Collects the formatters and decoders of a plugin library.
+#[derive(Default)]
+pub struct Registrar {
+    formatters: Vec<Box<dyn ValueFormatter>>,
+    decoders: Vec<Box<dyn WaveDecoder>>,
+}
+
+type BuildIdFn = extern "C" fn() -> *const c_char;
+type RegisterFn = unsafe extern "C" fn(&mut Registrar);
+
+static FORMATTERS: RwLock<Vec<Arc<dyn ValueFormatter>>> = RwLock::new(vec![]);
+/// Loaded libraries, kept loaded as long as what they registered may be used
+static LIBRARIES: OnceLock<Mutex<Vec<Library>>> = OnceLock::new();
+
+impl Registrar {
+    pub fn register_formatter(&mut self, formatter: Box<dyn ValueFormatter>) {
+        self.formatters.push(formatter);
+    }
+
+    pub fn register_decoder(&mut self, decoder: Box<dyn WaveDecoder>) {
+        self.decoders.push(decoder);
+    }
+
+    /// Registers everything collected, stopping at the first name already taken.
+    fn finish(self) -> Result<Vec<String>, String> {
+        let mut names = vec![];
+        for formatter in self.formatters {
+            names.push(format!("formatter {}", formatter.name()));
+            register_formatter(formatter)?;
+        }
+        for decoder in self.decoders {
+            names.push(format!("decoder {}", decoder.protocol()));
+            register_decoder(decoder)?;
+        }
+        Ok(names)
+    }
+}
+
+/// Declares the function registering the formatters and decoders of a plugin library. Both entry
+/// points use the C calling convention, so that the build IDs are compared before any Rust type
+/// crosses the library boundary.
+#[macro_export]
+macro_rules! declare_plugin {
+    ($register:path) => {
+        #[no_mangle]
+        pub extern "C" fn oombak_plugin_build_id() -> *const ::std::ffi::c_char {
+            $crate::plugin::BUILD_ID.as_ptr()
+        }
+
+        /// # Safety
+        ///
+        /// The caller must have been built with the same build ID.
+        #[no_mangle]
+        pub unsafe extern "C" fn oombak_plugin_register(registrar: &mut $crate::plugin::Registrar) {
+            $register(registrar)
+        }
+    };
+}
+
+pub fn register_formatter(formatter: Box<dyn ValueFormatter>) -> Result<(), String> {
+    let mut formatters = FORMATTERS.write().unwrap();
+    let name = formatter.name();
+    if formatters.iter().any(|f| f.name() == name) {
+        return Err(format!(
+            "a formatter named \"{name}\" is already registered"
+        ));
+    }
+    formatters.push(Arc::from(formatter));
+    Ok(())
+}
+
+pub fn register_decoder(decoder: Box<dyn WaveDecoder>) -> Result<(), String> {
+    crate::backend::decoder::register(Arc::from(decoder))
+}
+
+pub fn formatters() -> Vec<Arc<dyn ValueFormatter>> {
+    FORMATTERS.read().unwrap().clone()
+}
+
+pub fn find_formatter(name: &str) -> Option<Arc<dyn ValueFormatter>> {
+    FORMATTERS
+        .read()
+        .unwrap()
+        .iter()
+        .find(|formatter| formatter.name() == name)
+        .cloned()
+}
+
+/// Loads a plugin library and registers its formatters and decoders, whose names are returned.
+pub fn load(path: &Path) -> Result<Vec<String>, String> {
+    let error = |message: String| format!("cannot load plugin {}: {message}", path.display());
+    // SAFETY: the library is trusted like the program itself, as its initialization code runs
+    // on loading
+    let library = unsafe { Library::new(path) }.map_err(|e| error(e.to_string()))?;
+    // SAFETY: the symbols are those defined by `declare_plugin!`, and the Rust types passed to the
+    // registration function have the same layout on both sides once the build IDs match
+    let register = unsafe {
+        let build_id: Symbol<BuildIdFn> = library
+            .get(b"oombak_plugin_build_id")
+            .map_err(|_| error("not declared with declare_plugin!".to_string()))?;
+        let build_id = CStr::from_ptr(build_id());
+        if build_id != BUILD_ID {
+            return Err(error(format!(
+                "built with {}, not {}",
+                build_id.to_string_lossy(),
+                BUILD_ID.to_string_lossy()
+            )));
+        }
+        let register: Symbol<RegisterFn> = library
+            .get(b"oombak_plugin_register")
+            .map_err(|e| error(e.to_string()))?;
+        *register
+    };
+    let mut registrar = Registrar::default();
+    // SAFETY: the build IDs match
+    unsafe { register(&mut registrar) };
+    // The registered trait objects point into the library, which must never be unloaded
+    LIBRARIES
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap()
+        .push(library);
+    registrar.finish().map_err(error)
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use bitvec::vec::BitVec;
+
+    use super::{find_formatter, load, register_formatter, Registrar, ValueFormatter};
+    use crate::backend::decoder;
+
+    struct Gray;
+
+    impl ValueFormatter for Gray {
+        fn name(&self) -> &str {
+            "gray-test"
+        }
+
+        fn format(&self, value: &BitVec<u32>, width: usize) -> String {
+            let gray = decoder::to_usize(Some(value));
+            let binary = (0..width).fold(0, |acc, i| acc ^ (gray >> i));
+            binary.to_string()
+        }
+    }
+
+    #[test]
+    fn test_register() {
+        let mut registrar = Registrar::default();
+        registrar.register_formatter(Box::new(Gray));
+        assert_eq!(
+            registrar.finish(),
+            Ok(vec!["formatter gray-test".to_string()])
+        );
+        assert!(register_formatter(Box::new(Gray)).is_err());
+
+        let formatter = find_formatter("gray-test").unwrap();
+        let value: BitVec<u32> = [false, true, true].into_iter().collect();
+        assert_eq!(formatter.format(&value, 3), "4");
+
+        assert!(load(Path::new("/nonexistent/libplugin.so")).is_err());
+    }
+}
@@ -1,31 +1,60 @@
 use ratatui::style::{Color, Modifier, Style};
 
+use theme::ThemedStyle;
+
+pub mod theme;
+
 pub mod global {
     use super::*;
 
-    pub const SELECTED_ITEM_STYLE: Style =
-        Style::new().bg(Color::Blue).add_modifier(Modifier::BOLD);
+    pub const SELECTED_ITEM_STYLE: ThemedStyle = ThemedStyle::new(
+        "global.selected-item",
+        Style::new().bg(Color::Blue).add_modifier(Modifier::BOLD),
+    );
 }
 
 pub mod root {
     use super::*;
 
-    pub const TITLE_STYLE: Style = Style::new().add_modifier(Modifier::BOLD);
-    pub const VERSION_STYLE: Style = Style::new()
-        .add_modifier(Modifier::ITALIC)
-        .fg(Color::DarkGray);
+    pub const TITLE_STYLE: ThemedStyle =
+        ThemedStyle::new("root.title", Style::new().add_modifier(Modifier::BOLD));
+    pub const VERSION_STYLE: ThemedStyle = ThemedStyle::new(
+        "root.version",
+        Style::new()
+            .add_modifier(Modifier::ITALIC)
+            .fg(Color::DarkGray),
+    );
+    pub const FILTER_STYLE: ThemedStyle = ThemedStyle::new(
+        "root.filter",
+        Style::new()
+            .add_modifier(Modifier::ITALIC)
+            .fg(Color::Yellow),
+    );
 }
 
 pub mod file_explorer {
     use super::*;
 
-    pub const DIR_ITEM_STYLE: Style = Style::new().fg(Color::Green);
-    pub const FILE_ITEM_STYLE: Style = Style::new();
-    pub const ERROR_FILE_TYPE_STYLE: Style =
-        Style::new().fg(Color::Red).add_modifier(Modifier::BOLD);
-    pub const DIRECTORY_FILE_TYPE_STYLE: Style = Style::new().fg(Color::Blue);
-    pub const SYSTEM_VERILOG_DETAILED_TYPE_STYLE: Style = Style::new().fg(Color::Green);
-    pub const OTHER_DETAILED_TYPE_STYLE: Style = Style::new().fg(Color::Cyan);
+    pub const DIR_ITEM_STYLE: ThemedStyle =
+        ThemedStyle::new("file-explorer.dir-item", Style::new().fg(Color::Green));
+    pub const FILE_ITEM_STYLE: ThemedStyle =
+        ThemedStyle::new("file-explorer.file-item", Style::new());
+    pub const ERROR_FILE_TYPE_STYLE: ThemedStyle = ThemedStyle::new(
+        "file-explorer.error-file-type",
+        Style::new().fg(Color::Red).add_modifier(Modifier::BOLD),
+    );
+    pub const DIRECTORY_FILE_TYPE_STYLE: ThemedStyle = ThemedStyle::new(
+        "file-explorer.directory-file-type",
+        Style::new().fg(Color::Blue),
+    );
+    pub const SYSTEM_VERILOG_DETAILED_TYPE_STYLE: ThemedStyle = ThemedStyle::new(
+        "file-explorer.system-verilog-detailed-type",
+        Style::new().fg(Color::Green),
+    );
+    pub const OTHER_DETAILED_TYPE_STYLE: ThemedStyle = ThemedStyle::new(
+        "file-explorer.other-detailed-type",
+        Style::new().fg(Color::Cyan),
+    );
 
     pub const FILE_PREVIEW_TAB_WIDTH: usize = 8;
 }
@@ -33,69 +62,252 @@ pub mod file_explorer {
 pub mod terminal {
     use super::*;
 
-    pub const COMMAND_LINE_STYLE: Style = Style::new().bg(Color::Blue).fg(Color::White);
-    pub const COMMAND_LINE_HEADER_STYLE: Style = Style::new().fg(Color::Black).bg(Color::Yellow);
-    pub const NORMAL_OUTPUT_STYLE: Style = Style::new().fg(Color::Green);
-    pub const NOTIFICATION_OUTPUT_STYLE: Style = Style::new().fg(Color::Gray);
-    pub const ERROR_OUTPUT_STYLE: Style = Style::new().fg(Color::Red);
-    pub const TEXT_CURSOR_STYLE: Style = Style::new().fg(Color::Black).bg(Color::White);
+    pub const COMMAND_LINE_STYLE: ThemedStyle = ThemedStyle::new(
+        "terminal.command-line",
+        Style::new().bg(Color::Blue).fg(Color::White),
+    );
+    pub const COMMAND_LINE_HEADER_STYLE: ThemedStyle = ThemedStyle::new(
+        "terminal.command-line-header",
+        Style::new().fg(Color::Black).bg(Color::Yellow),
+    );
+    pub const NORMAL_OUTPUT_STYLE: ThemedStyle =
+        ThemedStyle::new("terminal.normal-output", Style::new().fg(Color::Green));
+    pub const NOTIFICATION_OUTPUT_STYLE: ThemedStyle =
+        ThemedStyle::new("terminal.notification-output", Style::new().fg(Color::Gray));
+    pub const ERROR_OUTPUT_STYLE: ThemedStyle =
+        ThemedStyle::new("terminal.error-output", Style::new().fg(Color::Red));
+    pub const TEXT_CURSOR_STYLE: ThemedStyle = ThemedStyle::new(
+        "terminal.text-cursor",
+        Style::new().fg(Color::Black).bg(Color::White),
+    );
+    pub const COMPLETION_HINT_STYLE: ThemedStyle = ThemedStyle::new(
+        "terminal.completion-hint",
+        Style::new().fg(Color::White).bg(Color::DarkGray),
+    );
+    pub const TERMINAL_STATUS_STYLE: ThemedStyle = ThemedStyle::new(
+        "terminal.terminal-status",
+        Style::new().fg(Color::Black).bg(Color::Gray),
+    );
+    pub const SEARCH_MATCH_STYLE: ThemedStyle = ThemedStyle::new(
+        "terminal.search-match",
+        Style::new().fg(Color::Black).bg(Color::Yellow),
+    );
 }
 
 pub mod wave_viewer {
     use super::*;
 
-    pub const WAVEFORM_STYLE: Style = Style::new().fg(Color::White);
-    pub const TIMEBAR_STYLE: Style = Style::new().fg(Color::White);
-    pub const CURSOR_STYLE: Style = Style::new().bg(Color::DarkGray);
-    pub const TIME_INDICATOR_STYLE: Style = Style::new().fg(Color::White).bg(Color::DarkGray);
-    pub const SELECTED_WAVEFORM_STYLE: Style = Style::new().fg(Color::Green);
+    pub const WAVEFORM_STYLE: ThemedStyle =
+        ThemedStyle::new("wave-viewer.waveform", Style::new().fg(Color::White));
+    pub const TIMEBAR_STYLE: ThemedStyle =
+        ThemedStyle::new("wave-viewer.timebar", Style::new().fg(Color::White));
+    pub const CURSOR_STYLE: ThemedStyle =
+        ThemedStyle::new("wave-viewer.cursor", Style::new().bg(Color::DarkGray));
+    pub const TIME_INDICATOR_STYLE: ThemedStyle = ThemedStyle::new(
+        "wave-viewer.time-indicator",
+        Style::new().fg(Color::White).bg(Color::DarkGray),
+    );
+    pub const SELECTED_WAVEFORM_STYLE: ThemedStyle = ThemedStyle::new(
+        "wave-viewer.selected-waveform",
+        Style::new().fg(Color::Green),
+    );
+    pub const MARKER_STYLE: ThemedStyle = ThemedStyle::new(
+        "wave-viewer.marker",
+        Style::new().bg(Color::Yellow).fg(Color::Black),
+    );
+    pub const MARKER_INDICATOR_STYLE: ThemedStyle = ThemedStyle::new(
+        "wave-viewer.marker-indicator",
+        Style::new().fg(Color::Black).bg(Color::Yellow),
+    );
+    pub const AXIS_LEGEND_STYLE: ThemedStyle =
+        ThemedStyle::new("wave-viewer.axis-legend", Style::new().fg(Color::Cyan));
 }
 
 pub mod signals_viewer {
     use super::*;
 
-    pub const SELECTED_SIGNAL_STYLE: Style = Style::new().bg(Color::DarkGray);
-    pub const SIGNAL_NAME_STYLE: Style = Style::new().fg(Color::White).add_modifier(Modifier::BOLD);
-    pub const SIGNAL_WIDTH_STYLE: Style = Style::new().fg(Color::Blue);
-    pub const SIGNAL_VALUE_STYLE: Style =
-        Style::new().fg(Color::Green).add_modifier(Modifier::ITALIC);
+    pub const SELECTED_SIGNAL_STYLE: ThemedStyle = ThemedStyle::new(
+        "signals-viewer.selected-signal",
+        Style::new().bg(Color::DarkGray),
+    );
+    pub const SIGNAL_NAME_STYLE: ThemedStyle = ThemedStyle::new(
+        "signals-viewer.signal-name",
+        Style::new().fg(Color::White).add_modifier(Modifier::BOLD),
+    );
+    pub const SIGNAL_WIDTH_STYLE: ThemedStyle =
+        ThemedStyle::new("signals-viewer.signal-width", Style::new().fg(Color::Blue));
+    pub const SIGNAL_VALUE_STYLE: ThemedStyle = ThemedStyle::new(
+        "signals-viewer.signal-value",
+        Style::new().fg(Color::Green).add_modifier(Modifier::ITALIC),
+    );
+    pub const MARKER_VALUE_STYLE: ThemedStyle = ThemedStyle::new(
+        "signals-viewer.marker-value",
+        Style::new()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::ITALIC),
+    );
+    pub const GROUP_NAME_STYLE: ThemedStyle = ThemedStyle::new(
+        "signals-viewer.group-name",
+        Style::new()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD)
+            .add_modifier(Modifier::UNDERLINED),
+    );
+}
+
+pub mod search_viewer {
+    use super::*;
+
+    pub const STATUS_STYLE: ThemedStyle =
+        ThemedStyle::new("search-viewer.status", Style::new().fg(Color::Gray));
+    pub const ERROR_STYLE: ThemedStyle =
+        ThemedStyle::new("search-viewer.error", Style::new().fg(Color::Red));
+}
+
+pub mod request_status_viewer {
+    use super::*;
+
+    pub const HEADER_STYLE: ThemedStyle = ThemedStyle::new(
+        "request-status-viewer.header",
+        Style::new().add_modifier(Modifier::BOLD),
+    );
+    pub const DISPATCHED_STYLE: ThemedStyle = ThemedStyle::new(
+        "request-status-viewer.dispatched",
+        Style::new().fg(Color::Gray),
+    );
+    pub const IN_PROGRESS_STYLE: ThemedStyle = ThemedStyle::new(
+        "request-status-viewer.in-progress",
+        Style::new().fg(Color::Yellow),
+    );
+    pub const FINISHED_STYLE: ThemedStyle = ThemedStyle::new(
+        "request-status-viewer.finished",
+        Style::new().fg(Color::Green),
+    );
+    pub const ERROR_STYLE: ThemedStyle =
+        ThemedStyle::new("request-status-viewer.error", Style::new().fg(Color::Red));
+}
+
+pub mod transaction_viewer {
+    use super::*;
+
+    pub const HEADER_STYLE: ThemedStyle = ThemedStyle::new(
+        "transaction-viewer.header",
+        Style::new().add_modifier(Modifier::BOLD),
+    );
+    pub const ERROR_STYLE: ThemedStyle =
+        ThemedStyle::new("transaction-viewer.error", Style::new().fg(Color::Red));
 }
 
 pub mod instance_hier_viewer {
     use super::*;
 
-    pub const INSTANCE_ITEM_STYLE: Style = Style::new()
-        .fg(Color::White)
-        .add_modifier(Modifier::UNDERLINED);
-    pub const SIGNAL_ITEM_STYLE: Style = Style::new()
-        .fg(Color::Yellow)
-        .add_modifier(Modifier::ITALIC);
+    pub const INSTANCE_ITEM_STYLE: ThemedStyle = ThemedStyle::new(
+        "instance-hier-viewer.instance-item",
+        Style::new()
+            .fg(Color::White)
+            .add_modifier(Modifier::UNDERLINED),
+    );
+    pub const SIGNAL_ITEM_STYLE: ThemedStyle = ThemedStyle::new(
+        "instance-hier-viewer.signal-item",
+        Style::new()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::ITALIC),
+    );
 }
 
 pub mod command_keys_help_bar {
     use super::*;
 
-    pub const KEY_ID_STYLE: Style = Style::new().fg(Color::Blue).add_modifier(Modifier::BOLD);
-    pub const DESCRIPTION_STYLE: Style =
-        Style::new().fg(Color::Green).add_modifier(Modifier::ITALIC);
+    pub const KEY_ID_STYLE: ThemedStyle = ThemedStyle::new(
+        "command-keys-help-bar.key-id",
+        Style::new().fg(Color::Blue).add_modifier(Modifier::BOLD),
+    );
+    pub const DESCRIPTION_STYLE: ThemedStyle = ThemedStyle::new(
+        "command-keys-help-bar.description",
+        Style::new().fg(Color::Green).add_modifier(Modifier::ITALIC),
+    );
 }
 
 pub mod selector {
     use super::*;
 
-    pub const DISABLED_ITEM_STYLE: Style = Style::new().fg(Color::DarkGray);
+    pub const DISABLED_ITEM_STYLE: ThemedStyle =
+        ThemedStyle::new("selector.disabled-item", Style::new().fg(Color::DarkGray));
 }
 
 pub mod form {
     use super::*;
 
-    pub const HIGHLIGHTED_INPUT_FIELD_BORDER_STYLE: Style = Style::new().fg(Color::Green);
-    pub const NORMAL_FIELD_BORDER_STYLE: Style = Style::new().fg(Color::Reset);
-    pub const INPUT_FIELD_STYLE: Style = Style::new().bg(Color::Reset).fg(Color::Reset);
+    pub const HIGHLIGHTED_INPUT_FIELD_BORDER_STYLE: ThemedStyle = ThemedStyle::new(
+        "form.highlighted-input-field-border",
+        Style::new().fg(Color::Green),
+    );
+    pub const NORMAL_FIELD_BORDER_STYLE: ThemedStyle =
+        ThemedStyle::new("form.normal-field-border", Style::new().fg(Color::Reset));
+    pub const INPUT_FIELD_STYLE: ThemedStyle = ThemedStyle::new(
+        "form.input-field",
+        Style::new().bg(Color::Reset).fg(Color::Reset),
+    );
 }
 
 pub mod dropdown {
     use super::*;
 
-    pub const ITEM_DEFAULT_STYLE: Style = Style::new().fg(Color::White);
+    pub const ITEM_DEFAULT_STYLE: ThemedStyle =
+        ThemedStyle::new("dropdown.item-default", Style::new().fg(Color::White));
 }
+
+/// Every style a theme can set
+pub const THEMED_STYLES: [ThemedStyle; 51] = [
+    global::SELECTED_ITEM_STYLE,
+    root::TITLE_STYLE,
+    root::VERSION_STYLE,
+    root::FILTER_STYLE,
+    file_explorer::DIR_ITEM_STYLE,
+    file_explorer::FILE_ITEM_STYLE,
+    file_explorer::ERROR_FILE_TYPE_STYLE,
+    file_explorer::DIRECTORY_FILE_TYPE_STYLE,
+    file_explorer::SYSTEM_VERILOG_DETAILED_TYPE_STYLE,
+    file_explorer::OTHER_DETAILED_TYPE_STYLE,
+    terminal::COMMAND_LINE_STYLE,
+    terminal::COMMAND_LINE_HEADER_STYLE,
+    terminal::NORMAL_OUTPUT_STYLE,
+    terminal::NOTIFICATION_OUTPUT_STYLE,
+    terminal::ERROR_OUTPUT_STYLE,
+    terminal::TEXT_CURSOR_STYLE,
+    terminal::COMPLETION_HINT_STYLE,
+    terminal::TERMINAL_STATUS_STYLE,
+    terminal::SEARCH_MATCH_STYLE,
+    wave_viewer::WAVEFORM_STYLE,
+    wave_viewer::TIMEBAR_STYLE,
+    wave_viewer::CURSOR_STYLE,
+    wave_viewer::TIME_INDICATOR_STYLE,
+    wave_viewer::SELECTED_WAVEFORM_STYLE,
+    wave_viewer::MARKER_STYLE,
+    wave_viewer::MARKER_INDICATOR_STYLE,
+    wave_viewer::AXIS_LEGEND_STYLE,
+    signals_viewer::SELECTED_SIGNAL_STYLE,
+    signals_viewer::SIGNAL_NAME_STYLE,
+    signals_viewer::SIGNAL_WIDTH_STYLE,
+    signals_viewer::SIGNAL_VALUE_STYLE,
+    signals_viewer::MARKER_VALUE_STYLE,
+    signals_viewer::GROUP_NAME_STYLE,
+    search_viewer::STATUS_STYLE,
+    search_viewer::ERROR_STYLE,
+    request_status_viewer::HEADER_STYLE,
+    request_status_viewer::DISPATCHED_STYLE,
+    request_status_viewer::IN_PROGRESS_STYLE,
+    request_status_viewer::FINISHED_STYLE,
+    request_status_viewer::ERROR_STYLE,
+    transaction_viewer::HEADER_STYLE,
+    transaction_viewer::ERROR_STYLE,
+    instance_hier_viewer::INSTANCE_ITEM_STYLE,
+    instance_hier_viewer::SIGNAL_ITEM_STYLE,
+    command_keys_help_bar::KEY_ID_STYLE,
+    command_keys_help_bar::DESCRIPTION_STYLE,
+    selector::DISABLED_ITEM_STYLE,
+    form::HIGHLIGHTED_INPUT_FIELD_BORDER_STYLE,
+    form::NORMAL_FIELD_BORDER_STYLE,
+    form::INPUT_FIELD_STYLE,
+    dropdown::ITEM_DEFAULT_STYLE,
+];
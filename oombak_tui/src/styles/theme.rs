@@ -0,0 +1,275 @@
+use std::{
+    collections::HashMap,
+    fs,
+    str::FromStr,
+    sync::{LazyLock, RwLock},
+};
+
+use ratatui::style::{Color, Style};
+use serde::Deserialize;
+
+use crate::utils::config;
+
+use super::THEMED_STYLES;
+
+const BUILT_IN_THEMES: [&str; 3] = ["dark", "light", "high-contrast"];
+
+static THEME: LazyLock<RwLock<Theme>> = LazyLock::new(|| RwLock::new(Theme::default()));
+
+/// A color scheme, given as the replacement for each color the styles are written in, and as
+/// the colors of single styles. The styles are written for dark terminals, so the `dark` theme
+/// replaces none of them.
+#[derive(Clone)]
+pub struct Theme {
+    name: String,
+    colors: HashMap<Color, Color>,
+    styles: HashMap<String, StyleColors>,
+}
+
+/// Style of an element of the interface, named so that themes can recolor it. It converts into
+/// its style in the current theme.
+#[derive(Clone, Copy)]
+pub struct ThemedStyle {
+    name: &'static str,
+    default: Style,
+}
+
+#[derive(Clone, Copy, Default)]
+struct StyleColors {
+    fg: Option<Color>,
+    bg: Option<Color>,
+}
+
+/// Contents of `theme.toml` in the configuration directory.
+#[derive(Default, Deserialize)]
+struct ThemeFile {
+    /// Theme applied at startup
+    theme: Option<String>,
+    #[serde(default)]
+    themes: HashMap<String, ThemeSpec>,
+}
+
+#[derive(Deserialize)]
+struct ThemeSpec {
+    /// Built-in theme whose colors are replaced by `colors`
+    base: Option<String>,
+    #[serde(default)]
+    colors: HashMap<String, String>,
+    #[serde(default)]
+    styles: HashMap<String, StyleSpec>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StyleSpec {
+    fg: Option<String>,
+    bg: Option<String>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            name: "dark".to_string(),
+            colors: HashMap::new(),
+            styles: HashMap::new(),
+        }
+    }
+}
+
+impl Theme {
+    fn built_in(name: &str) -> Option<Self> {
+        let colors = match name {
+            "dark" => vec![],
+            "light" => vec![
+                (Color::White, Color::Black),
+                (Color::Black, Color::White),
+                (Color::Gray, Color::DarkGray),
+                (Color::DarkGray, Color::Gray),
+                (Color::Yellow, Color::Magenta),
+                (Color::Cyan, Color::Blue),
+            ],
+            "high-contrast" => vec![
+                (Color::Gray, Color::White),
+                (Color::DarkGray, Color::Blue),
+                (Color::Red, Color::LightRed),
+                (Color::Green, Color::LightGreen),
+                (Color::Yellow, Color::LightYellow),
+                (Color::Blue, Color::LightBlue),
+                (Color::Cyan, Color::LightCyan),
+            ],
+            _ => return None,
+        };
+        Some(Self {
+            name: name.to_string(),
+            colors: HashMap::from_iter(colors),
+            styles: HashMap::new(),
+        })
+    }
+
+    fn from_spec(name: &str, spec: &ThemeSpec) -> Result<Self, String> {
+        let mut theme = match spec.base.as_deref() {
+            Some(base) => {
+                Self::built_in(base).ok_or(format!("unknown built-in theme \"{base}\""))?
+            }
+            None => Self::default(),
+        };
+        theme.name = name.to_string();
+        for (color, replacement) in spec.colors.iter() {
+            theme
+                .colors
+                .insert(parse_color(color)?, parse_color(replacement)?);
+        }
+        for (style_name, style_spec) in spec.styles.iter() {
+            if !THEMED_STYLES.iter().any(|s| s.name == style_name) {
+                return Err(format!("unknown style \"{style_name}\""));
+            }
+            let colors = StyleColors {
+                fg: style_spec.fg.as_deref().map(parse_color).transpose()?,
+                bg: style_spec.bg.as_deref().map(parse_color).transpose()?,
+            };
+            theme.styles.insert(style_name.clone(), colors);
+        }
+        Ok(theme)
+    }
+
+    fn map(&self, color: Color) -> Color {
+        self.colors.get(&color).copied().unwrap_or(color)
+    }
+
+    fn style(&self, themed_style: ThemedStyle) -> Style {
+        let mut style = themed_style.default;
+        style.fg = style.fg.map(|color| self.map(color));
+        style.bg = style.bg.map(|color| self.map(color));
+        if let Some(colors) = self.styles.get(themed_style.name) {
+            style.fg = colors.fg.or(style.fg);
+            style.bg = colors.bg.or(style.bg);
+        }
+        style
+    }
+}
+
+impl ThemedStyle {
+    pub const fn new(name: &'static str, default: Style) -> Self {
+        Self { name, default }
+    }
+
+    pub fn get(self) -> Style {
+        THEME.read().unwrap().style(self)
+    }
+}
+
+impl From<ThemedStyle> for Style {
+    fn from(themed_style: ThemedStyle) -> Self {
+        themed_style.get()
+    }
+}
+
+pub fn parse_color(text: &str) -> Result<Color, String> {
+    Color::from_str(text).map_err(|_| format!("unknown color \"{text}\""))
+}
+
+pub fn current_name() -> String {
+    THEME.read().unwrap().name.clone()
+}
+
+pub fn set(name: &str) -> Result<(), String> {
+    let theme = find(name, &read_theme_file()?)?;
+    *THEME.write().unwrap() = theme;
+    Ok(())
+}
+
+/// Applies the theme chosen in `theme.toml`, if any.
+pub fn set_startup_theme() -> Result<(), String> {
+    match read_theme_file()?.theme {
+        Some(name) => set(&name),
+        None => Ok(()),
+    }
+}
+
+pub fn names() -> Result<Vec<String>, String> {
+    let mut names: Vec<String> = BUILT_IN_THEMES.iter().map(|s| s.to_string()).collect();
+    let mut user_theme_names: Vec<String> = read_theme_file()?
+        .themes
+        .into_keys()
+        .filter(|name| !BUILT_IN_THEMES.contains(&&name[..]))
+        .collect();
+    user_theme_names.sort();
+    names.extend(user_theme_names);
+    Ok(names)
+}
+
+fn find(name: &str, theme_file: &ThemeFile) -> Result<Theme, String> {
+    match theme_file.themes.get(name) {
+        Some(spec) => Theme::from_spec(name, spec),
+        None => Theme::built_in(name).ok_or(format!("unknown theme \"{name}\"")),
+    }
+}
+
+fn read_theme_file() -> Result<ThemeFile, String> {
+    let path = match config::config_dir().map(|dir| dir.join("theme.toml")) {
+        Some(path) if path.is_file() => path,
+        _ => return Ok(ThemeFile::default()),
+    };
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("cannot read {}: {e}", path.to_string_lossy()))?;
+    toml::from_str(&content).map_err(|e| format!("invalid {}: {e}", path.to_string_lossy()))
+}
+
+#[cfg(test)]
+mod test {
+    use ratatui::style::Color;
+
+    use super::{find, ThemeFile};
+    use crate::styles::wave_viewer::{MARKER_STYLE, TIMEBAR_STYLE, WAVEFORM_STYLE};
+
+    #[test]
+    fn test_find() {
+        let theme_file: ThemeFile = toml::from_str(
+            r##"
+            theme = "paper"
+
+            [themes.paper]
+            base = "light"
+            colors = { yellow = "#b58900", green = "dark-gray" }
+
+            [themes.sepia.styles]
+            "wave-viewer.waveforms" = { fg = "green" }
+            "##,
+        )
+        .unwrap();
+
+        let theme = find("paper", &theme_file).unwrap();
+        assert_eq!(theme.map(Color::Yellow), Color::Rgb(0xb5, 0x89, 0x00));
+        assert_eq!(theme.map(Color::Green), Color::DarkGray);
+        assert_eq!(theme.map(Color::White), Color::Black);
+        assert_eq!(theme.map(Color::Red), Color::Red);
+
+        assert!(find("dark", &theme_file).unwrap().colors.is_empty());
+        assert!(find("sepia", &theme_file).is_err());
+        assert!(find("solarized", &theme_file).is_err());
+    }
+
+    #[test]
+    fn test_style() {
+        let theme_file: ThemeFile = toml::from_str(
+            r##"
+            [themes.paper]
+            base = "light"
+
+            [themes.paper.styles]
+            "wave-viewer.waveform" = { fg = "green" }
+            "wave-viewer.marker" = { bg = "#b58900" }
+            "##,
+        )
+        .unwrap();
+        let theme = find("paper", &theme_file).unwrap();
+
+        // Both are white in the default styles
+        assert_eq!(theme.style(WAVEFORM_STYLE).fg, Some(Color::Green));
+        assert_eq!(theme.style(TIMEBAR_STYLE).fg, Some(Color::Black));
+
+        let marker_style = theme.style(MARKER_STYLE);
+        assert_eq!(marker_style.bg, Some(Color::Rgb(0xb5, 0x89, 0x00)));
+        assert_eq!(marker_style.fg, Some(Color::White));
+    }
+}
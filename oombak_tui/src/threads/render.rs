@@ -41,7 +41,7 @@ impl RendererThread {
                     root_component
                         .write()
                         .unwrap()
-                        .render_with_command_keys_help_bar(frame, frame.area())
+                        .render_with_command_keys_help_bar(frame, frame.area());
                 }) {
                     Ok(_) => (),
                     Err(e) => {
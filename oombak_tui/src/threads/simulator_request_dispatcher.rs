@@ -98,18 +98,8 @@ impl SimulatorRequestDispatcher {
     }
 
     fn notify_request_dispatched(request: &oombak_sim::Request, listeners: Arc<RwLock<Listeners>>) {
-        let response = Self::request_dispatched_notification(request);
         for listener in listeners.read().unwrap().iter() {
-            listener.write().unwrap().on_receive_reponse(&response)
-        }
-    }
-
-    fn request_dispatched_notification(request: &oombak_sim::Request) -> oombak_sim::Response {
-        let message = format!("`{}` request dispatched", request.payload);
-        let notification = oombak_sim::response::Payload::generic_notification(message);
-        oombak_sim::Response {
-            id: request.id,
-            payload: notification,
+            listener.write().unwrap().on_request_dispatched(request)
         }
     }
 
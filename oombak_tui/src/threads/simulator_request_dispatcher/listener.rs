@@ -1,9 +1,24 @@
 use std::sync::{Arc, RwLock};
 
-use oombak_sim::Response;
+use oombak_sim::{Request, Response};
 
 pub type Listeners = Vec<Arc<RwLock<dyn Listener>>>;
 
 pub trait Listener: Send + Sync {
     fn on_receive_reponse(&mut self, response: &Response);
+
+    /// Called when a request is passed on to the simulator. By default, it is received as a
+    /// notification response.
+    fn on_request_dispatched(&mut self, request: &Request) {
+        self.on_receive_reponse(&request_dispatched_notification(request));
+    }
+}
+
+fn request_dispatched_notification(request: &Request) -> Response {
+    let message = format!("`{}` request dispatched", request.payload);
+    let notification = oombak_sim::response::Payload::generic_notification(message);
+    Response {
+        id: request.id,
+        payload: notification,
+    }
 }
@@ -1,20 +1,44 @@
-use bitvec::{order::Lsb0, prelude::BitVec};
+use std::sync::Arc;
 
-use crate::components::models;
+use bitvec::{field::BitField, order::Lsb0, prelude::BitVec, slice::BitSlice};
+use serde::{Deserialize, Serialize};
+
+use crate::{components::models, plugin::ValueFormatter};
+
+/// Decimal values are parsed to at least this many bits.
+const DECIMAL_MIN_WIDTH: usize = 128;
 
 #[derive(Clone)]
 pub struct Option {
     pub radix: Radix,
     pub width: usize,
     pub twos_complement: bool,
+    /// Number of fraction bits of a [`Radix::FixedPoint`] value.
+    pub fraction_bits: usize,
+    pub enum_map: std::option::Option<Arc<EnumMap>>,
+    /// Formatter of a [`Radix::Custom`] value.
+    pub formatter: std::option::Option<Arc<dyn ValueFormatter>>,
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Radix {
     Binary,
     Hexadecimal,
     Octal,
     Decimal,
+    Ascii,
+    #[serde(rename = "fixed-point")]
+    FixedPoint,
+    Float,
+    Enum,
+    Custom,
+}
+
+/// Names given to the values of a signal, e.g. the states of a state machine.
+pub struct EnumMap {
+    pub name: String,
+    entries: Vec<(BitVec<u32>, String)>,
 }
 
 pub fn from(bit_vec: &BitVec<u32>, option: &Option) -> String {
@@ -23,6 +47,28 @@ pub fn from(bit_vec: &BitVec<u32>, option: &Option) -> String {
         Radix::Hexadecimal => hexadecimal(bit_vec, option.width, option.twos_complement),
         Radix::Octal => octal(bit_vec, option.width, option.twos_complement),
         Radix::Decimal => decimal(bit_vec, option.width, option.twos_complement),
+        Radix::Ascii => ascii(bit_vec, option.width),
+        Radix::FixedPoint => fixed_point(
+            bit_vec,
+            option.width,
+            option.twos_complement,
+            option.fraction_bits,
+        ),
+        Radix::Float => float(bit_vec, option.width),
+        Radix::Enum => {
+            let name = option
+                .enum_map
+                .as_ref()
+                .and_then(|enum_map| enum_map.name_of(bit_vec));
+            match name {
+                Some(name) => name.to_string(),
+                None => format!("0x{}", hexadecimal(bit_vec, option.width, false)),
+            }
+        }
+        Radix::Custom => match &option.formatter {
+            Some(formatter) => formatter.format(bit_vec, option.width),
+            None => format!("0x{}", hexadecimal(bit_vec, option.width, false)),
+        },
     }
 }
 
@@ -38,21 +84,17 @@ pub fn parse(value: &str) -> Result<BitVec<u32>, String> {
     }
 }
 
-pub fn i128_from_bitvec(bit_vec: &BitVec<u32>, is_signed: bool) -> i128 {
-    if (is_signed && bit_vec.last_one() > Some(128))
-        || (!is_signed && bit_vec.last_one() > Some(127))
-    {
-        unimplemented!()
-    }
-
-    let bit_vec = get_resized_bitvec(bit_vec, 128, is_signed);
-    let value = u128_from_bitvec(&bit_vec);
-
-    if is_signed && *bit_vec.last().unwrap() {
-        let (value, _) = (u128::MAX - value).overflowing_add(1);
-        -(value as i128)
+/// Converts a value of any width to the nearest floating-point number.
+pub fn f64_from_bitvec(bit_vec: &BitVec<u32>, is_signed: bool) -> f64 {
+    let (is_negative, magnitude) = sign_and_magnitude(bit_vec, bit_vec.len(), is_signed);
+    let value = words_from_bitslice(&magnitude)
+        .iter()
+        .rev()
+        .fold(0.0, |value, word| value * 2f64.powi(32) + *word as f64);
+    if is_negative {
+        -value
     } else {
-        value as i128
+        value
     }
 }
 
@@ -62,6 +104,9 @@ impl Default for Option {
             radix: Radix::Binary,
             width: 0,
             twos_complement: false,
+            fraction_bits: 0,
+            enum_map: None,
+            formatter: None,
         }
     }
 }
@@ -72,8 +117,31 @@ impl From<&models::WaveSpec> for Option {
             width: wave_spec.wave.width,
             radix: wave_spec.radix,
             twos_complement: wave_spec.signed,
+            fraction_bits: wave_spec.fraction_bits,
+            enum_map: wave_spec.enum_map.clone(),
+            formatter: wave_spec.formatter.clone(),
+        }
+    }
+}
+
+impl EnumMap {
+    pub fn new(name: &str, entries: Vec<(BitVec<u32>, String)>) -> Self {
+        Self {
+            name: name.to_string(),
+            entries,
         }
     }
+
+    pub fn entries(&self) -> &[(BitVec<u32>, String)] {
+        &self.entries
+    }
+
+    pub fn name_of(&self, value: &BitVec<u32>) -> std::option::Option<&str> {
+        self.entries
+            .iter()
+            .find(|(v, _)| is_equal_value(v, value))
+            .map(|(_, name)| &name[..])
+    }
 }
 
 fn binary(bit_vec: &BitVec<u32>, width: usize, twos_complement: bool) -> String {
@@ -112,19 +180,110 @@ fn octal(bit_vec: &BitVec<u32>, width: usize, twos_complement: bool) -> String {
 }
 
 fn decimal(bit_vec: &BitVec<u32>, width: usize, twos_complement: bool) -> String {
-    if width > 128 {
-        unimplemented!()
+    let (is_negative, magnitude) = sign_and_magnitude(bit_vec, width, twos_complement);
+    let value = unsigned_decimal(words_from_bitslice(&magnitude));
+    if is_negative {
+        format!("-{value}")
+    } else {
+        value
     }
+}
+
+/// Shows each byte, most significant first, as a character, skipping NUL padding bytes.
+fn ascii(bit_vec: &BitVec<u32>, width: usize) -> String {
+    let width = width.div_ceil(8) * 8;
+    let bit_vec = get_resized_bitvec(bit_vec, width, false);
+    bit_vec
+        .chunks(8)
+        .rev()
+        .map(|byte| u128_from_bitvec(&byte.to_bitvec()) as u8)
+        .filter(|byte| *byte != 0)
+        .map(|byte| {
+            if byte.is_ascii_graphic() || byte == b' ' {
+                byte as char
+            } else {
+                '.'
+            }
+        })
+        .collect()
+}
 
-    let bit_vec = get_resized_bitvec(bit_vec, 128, twos_complement);
-    let value = u128_from_bitvec(&bit_vec);
+/// Shows a Q-format value, i.e. an integer scaled by `2^-fraction_bits`.
+fn fixed_point(
+    bit_vec: &BitVec<u32>,
+    width: usize,
+    twos_complement: bool,
+    fraction_bits: usize,
+) -> String {
+    let (is_negative, mut magnitude) = sign_and_magnitude(bit_vec, width, twos_complement);
+    magnitude.resize(magnitude.len().max(fraction_bits), false);
+    let integer = unsigned_decimal(words_from_bitslice(&magnitude[fraction_bits..]));
 
-    if twos_complement && *bit_vec.last().unwrap() {
-        let (value, _) = (u128::MAX - value).overflowing_add(1);
-        format!("-{value}")
+    let mut fraction = magnitude[..fraction_bits].to_bitvec();
+    let mut fraction_digits = String::new();
+    while fraction.any() && fraction_digits.len() < 10 {
+        let mut words = words_from_bitslice(&fraction);
+        mul_add_words(&mut words, 10, 0);
+        let product = BitVec::<u32>::from_vec(words);
+        let digit = words_from_bitslice(&product[fraction_bits..]);
+        fraction_digits.push(char::from(b'0' + digit.first().copied().unwrap_or(0) as u8));
+        fraction = product[..fraction_bits].to_bitvec();
+    }
+
+    let sign = if is_negative { "-" } else { "" };
+    if fraction_digits.is_empty() {
+        format!("{sign}{integer}")
     } else {
-        value.to_string()
+        format!("{sign}{integer}.{fraction_digits}")
+    }
+}
+
+/// Interprets 16, 32 and 64-bit values as IEEE-754 half, single and double precision numbers.
+fn float(bit_vec: &BitVec<u32>, width: usize) -> String {
+    let bits = u128_from_bitvec(&get_resized_bitvec(bit_vec, width.min(128), false));
+    match width {
+        16 => format!("{:?}", f32_from_half(bits as u16)),
+        32 => format!("{:?}", f32::from_bits(bits as u32)),
+        64 => format!("{:?}", f64::from_bits(bits as u64)),
+        _ => hexadecimal(bit_vec, width, false),
+    }
+}
+
+fn f32_from_half(bits: u16) -> f32 {
+    let sign = if bits >> 15 == 1 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f32;
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0.0 => sign * f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
+}
+
+fn is_equal_value(a: &BitVec<u32>, b: &BitVec<u32>) -> bool {
+    let bit = |v: &BitVec<u32>, i: usize| v.get(i).is_some_and(|b| *b);
+    (0..a.len().max(b.len())).all(|i| bit(a, i) == bit(b, i))
+}
+
+/// Resizes a value to `width` bits and, if it is negative, replaces it with its magnitude.
+fn sign_and_magnitude(
+    bit_vec: &BitVec<u32>,
+    width: usize,
+    twos_complement: bool,
+) -> (bool, BitVec<u32>) {
+    let mut bit_vec = get_resized_bitvec(bit_vec, width, false);
+    let is_negative = twos_complement && bit_vec.last().is_some_and(|b| *b);
+    if is_negative {
+        bit_vec = !bit_vec;
+        for mut bit in bit_vec.iter_mut() {
+            *bit = !*bit;
+            if *bit {
+                break;
+            }
+        }
     }
+    (is_negative, bit_vec)
 }
 
 fn get_resized_bitvec(bit_vec: &BitVec<u32>, width: usize, twos_complement: bool) -> BitVec<u32> {
@@ -170,7 +329,18 @@ fn parse_binary(chars: &[char]) -> Result<BitVec<u32>, String> {
 }
 
 fn parse_decimal(chars: &[char]) -> Result<BitVec<u32>, String> {
-    parse_radix(chars, 10)
+    let mut words = vec![];
+    for c in chars {
+        let digit = c.to_digit(10).ok_or_else(|| {
+            let num_str: String = chars.iter().collect();
+            format!("cannot parse {num_str} as decimal number: invalid digit '{c}'")
+        })?;
+        mul_add_words(&mut words, 10, digit);
+    }
+    let mut result = BitVec::from_vec(words);
+    let width = result.last_one().map_or(0, |idx| idx + 1);
+    result.resize(width.max(DECIMAL_MIN_WIDTH), false);
+    Ok(result)
 }
 
 fn binary_chars_from_hexadecimal_chars(chars: &[char]) -> Result<Vec<char>, String> {
@@ -263,20 +433,8 @@ fn binary_tuple_to_octal_digit(value: (bool, bool, bool)) -> char {
     }
 }
 
-fn parse_radix(chars: &[char], radix: u32) -> Result<BitVec<u32>, String> {
-    // TODO: support decimal numbers that does not fit into a 128-bit variable
-    let num_str: String = chars.iter().collect();
-    let num = u128::from_str_radix(&num_str, radix)
-        .map_err(|e| format!("cannot parse {num_str} as radix-{radix} number: {e}"))?;
-    let mut result = bitvec::bitvec![u32, Lsb0;];
-    for i in 0..size_of::<u128>() {
-        result.push(num >> i & 0b1 == 0b1);
-    }
-    Ok(result)
-}
-
 fn round_to_nearest_larger_multiple(value: usize, multiple: usize) -> usize {
-    if value % multiple != 0 {
+    if !value.is_multiple_of(multiple) {
         (value / multiple + 1) * multiple + 1
     } else {
         value
@@ -291,3 +449,133 @@ fn u128_from_bitvec(bitvec: &BitVec<u32>) -> u128 {
         .map(|(v, shift)| v << shift)
         .sum()
 }
+
+fn words_from_bitslice(bit_slice: &BitSlice<u32>) -> Vec<u32> {
+    bit_slice
+        .chunks(32)
+        .map(|chunk| chunk.load_le::<u32>())
+        .collect()
+}
+
+/// Formats an unsigned value given as little-endian words.
+fn unsigned_decimal(mut words: Vec<u32>) -> String {
+    let mut groups = vec![];
+    while words.iter().any(|word| *word != 0) {
+        groups.push(div_rem_words(&mut words, 1_000_000_000));
+    }
+    match groups.split_last() {
+        Some((most_significant, rest)) => rest
+            .iter()
+            .rev()
+            .fold(most_significant.to_string(), |value, group| {
+                format!("{value}{group:09}")
+            }),
+        None => "0".to_string(),
+    }
+}
+
+/// Divides a value given as little-endian words in place and returns the remainder.
+fn div_rem_words(words: &mut [u32], divisor: u32) -> u32 {
+    let mut remainder = 0u64;
+    for word in words.iter_mut().rev() {
+        let dividend = (remainder << 32) | *word as u64;
+        *word = (dividend / divisor as u64) as u32;
+        remainder = dividend % divisor as u64;
+    }
+    remainder as u32
+}
+
+/// Multiplies a value given as little-endian words in place and adds to it, growing it as
+/// needed.
+fn mul_add_words(words: &mut Vec<u32>, multiplier: u32, addend: u32) {
+    let mut carry = addend as u64;
+    for word in words.iter_mut() {
+        let product = *word as u64 * multiplier as u64 + carry;
+        *word = product as u32;
+        carry = product >> 32;
+    }
+    if carry != 0 {
+        words.push(carry as u32);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use super::{f64_from_bitvec, from, parse, EnumMap, Option, Radix};
+
+    fn format(value: &str, width: usize, radix: Radix) -> String {
+        let option = Option {
+            radix,
+            width,
+            ..Default::default()
+        };
+        from(&parse(value).unwrap(), &option)
+    }
+
+    #[test]
+    fn test_from_extended_radices() {
+        assert_eq!(format("0x004869", 24, Radix::Ascii), "Hi");
+        assert_eq!(format("0x3c00", 16, Radix::Float), "1.0");
+        assert_eq!(format("0xc0490fdb", 32, Radix::Float), "-3.1415927");
+        assert_eq!(format("0x4004000000000000", 64, Radix::Float), "2.5");
+
+        let option = Option {
+            radix: Radix::FixedPoint,
+            width: 16,
+            twos_complement: true,
+            fraction_bits: 15,
+            ..Default::default()
+        };
+        assert_eq!(from(&parse("0x4000").unwrap(), &option), "0.5");
+        assert_eq!(from(&parse("0xc000").unwrap(), &option), "-0.5");
+        assert_eq!(from(&parse("0x5a82").unwrap(), &option), "0.7070922851");
+
+        let enum_map = EnumMap::new("state", vec![(parse("2").unwrap(), "BUSY".to_string())]);
+        let option = Option {
+            radix: Radix::Enum,
+            width: 2,
+            enum_map: Some(Arc::new(enum_map)),
+            ..Default::default()
+        };
+        assert_eq!(from(&parse("0b10").unwrap(), &option), "BUSY");
+        assert_eq!(from(&parse("0b01").unwrap(), &option), "0x1");
+    }
+
+    #[test]
+    fn test_wide_values() {
+        let max_u256 =
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+        assert_eq!(format(max_u256, 256, Radix::Decimal), max_u256);
+        assert_eq!(
+            format(&format!("0x{}", "f".repeat(64)), 256, Radix::Decimal),
+            max_u256
+        );
+        assert_eq!(parse(max_u256).unwrap().len(), 256);
+        assert_eq!(parse("70000").unwrap().len(), 128);
+
+        let option = Option {
+            radix: Radix::Decimal,
+            width: 200,
+            twos_complement: true,
+            ..Default::default()
+        };
+        let minus_one = parse(&format!("0x{}", "f".repeat(50))).unwrap();
+        assert_eq!(from(&minus_one, &option), "-1");
+        assert_eq!(f64_from_bitvec(&minus_one, true), -1.0);
+        assert_eq!(
+            f64_from_bitvec(&parse(max_u256).unwrap(), false),
+            2f64.powi(256)
+        );
+
+        let option = Option {
+            radix: Radix::FixedPoint,
+            width: 160,
+            fraction_bits: 150,
+            ..Default::default()
+        };
+        let value = parse(&format!("0x18{}", "0".repeat(36))).unwrap();
+        assert_eq!(from(&value, &option), "0.375");
+    }
+}
@@ -0,0 +1,133 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use oombak_local_sim::BuildOptions;
+use serde::Deserialize;
+
+/// Directory holding the configuration files, i.e. `$XDG_CONFIG_HOME/oombak`, or
+/// `~/.config/oombak` if `XDG_CONFIG_HOME` is not set.
+pub fn config_dir() -> Option<PathBuf> {
+    let config_home = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    Some(config_home.join("oombak"))
+}
+
+/// Directory holding the data kept across runs, i.e. `$XDG_DATA_HOME/oombak`, or
+/// `~/.local/share/oombak` if `XDG_DATA_HOME` is not set.
+pub fn data_dir() -> Option<PathBuf> {
+    let data_home = match env::var_os("XDG_DATA_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(".local/share"),
+    };
+    Some(data_home.join("oombak"))
+}
+
+/// Contents of `config.toml` in the configuration directory.
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AppConfig {
+    /// Plugin libraries loaded at startup
+    #[serde(default)]
+    plugins: Vec<PathBuf>,
+    #[serde(default)]
+    build: BuildConfig,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BuildConfig {
+    /// Directory in which designs are built, instead of the system's temporary directory
+    dir: Option<PathBuf>,
+    cmake: Option<PathBuf>,
+    verilator_root: Option<PathBuf>,
+    jobs: Option<usize>,
+}
+
+impl AppConfig {
+    /// Reads the given file, or `config.toml` in the configuration directory if it exists.
+    pub fn load(path: Option<&Path>) -> Result<Self, String> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => match config_dir().map(|dir| dir.join("config.toml")) {
+                Some(path) if path.is_file() => path,
+                _ => return Ok(Self::default()),
+            },
+        };
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("cannot read {}: {e}", path.to_string_lossy()))?;
+        Self::parse(&content).map_err(|e| format!("invalid {}: {e}", path.to_string_lossy()))
+    }
+
+    fn parse(content: &str) -> Result<Self, String> {
+        let config: Self = toml::from_str(content).map_err(|e| e.to_string())?;
+        if config.build.jobs == Some(0) {
+            return Err("build.jobs must be at least 1".to_string());
+        }
+        Ok(config)
+    }
+
+    pub fn build_options(&self) -> BuildOptions {
+        BuildOptions {
+            build_dir: self.build.dir.as_deref().map(expand_home),
+            cmake: self.build.cmake.as_deref().map(expand_home),
+            verilator_root: self.build.verilator_root.as_deref().map(expand_home),
+            jobs: self.build.jobs,
+        }
+    }
+
+    pub fn plugin_paths(&self) -> Vec<PathBuf> {
+        self.plugins.iter().map(|path| expand_home(path)).collect()
+    }
+}
+
+/// Replaces a leading `~` with the home directory.
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), env::var_os("HOME")) {
+        (Ok(path), Some(home)) => PathBuf::from(home).join(path),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::AppConfig;
+
+    #[test]
+    fn test_parse() {
+        let config = AppConfig::parse(
+            r#"
+            plugins = ["/opt/oombak/libgray.so"]
+
+            [build]
+            dir = "/var/cache/oombak"
+            verilator_root = "/opt/verilator"
+            jobs = 4
+            "#,
+        )
+        .unwrap();
+        let build_options = config.build_options();
+        assert_eq!(
+            build_options.build_dir,
+            Some(PathBuf::from("/var/cache/oombak"))
+        );
+        assert_eq!(build_options.cmake, None);
+        assert_eq!(
+            build_options.verilator_root,
+            Some(PathBuf::from("/opt/verilator"))
+        );
+        assert_eq!(build_options.jobs, Some(4));
+        assert_eq!(
+            config.plugin_paths(),
+            [PathBuf::from("/opt/oombak/libgray.so")]
+        );
+
+        assert!(AppConfig::parse("[build]\njobs = 0").is_err());
+        assert!(AppConfig::parse("[build]\nverilator = \"/opt\"").is_err());
+    }
+}
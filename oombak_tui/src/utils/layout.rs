@@ -1,4 +1,4 @@
-use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::layout::{Constraint, Layout, Position, Rect};
 
 pub fn get_popup_area_bottom_right(rect: Rect) -> Rect {
     let min_width = 40;
@@ -39,3 +39,22 @@ pub fn get_popup_area(
     .split(chunks[1]);
     chunks[1]
 }
+
+pub fn get_list_item_at(
+    list_area: Rect,
+    offset: usize,
+    item_heights: impl IntoIterator<Item = u16>,
+    position: Position,
+) -> Option<usize> {
+    if !list_area.contains(position) {
+        return None;
+    }
+    let mut y = list_area.y;
+    for (idx, height) in item_heights.into_iter().enumerate().skip(offset) {
+        if position.y < y + height {
+            return Some(idx);
+        }
+        y += height;
+    }
+    None
+}
@@ -1,2 +1,4 @@
 pub mod bitvec_str;
+pub mod config;
 pub mod layout;
+pub mod pattern;
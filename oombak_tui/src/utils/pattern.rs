@@ -0,0 +1,101 @@
+use regex_automata::meta::Regex;
+
+/// A pattern matched against signal and instance paths. A pattern enclosed in slashes is a
+/// regular expression, e.g. `/fifo[0-3]\.count$/`. A pattern with `*` or `?` wildcards must match
+/// the whole path, ignoring case, e.g. `*.fifo*.count`. Any other pattern matches if its
+/// characters appear in the path in order, ignoring case, so that `fcnt` matches
+/// `top.fifo.count`.
+pub enum Pattern {
+    Regex(Box<Regex>),
+    Glob(Vec<char>),
+    Fuzzy(Vec<char>),
+}
+
+impl Pattern {
+    pub fn new(text: &str) -> Result<Self, String> {
+        if let Some(regex) = text
+            .strip_prefix('/')
+            .and_then(|text| text.strip_suffix('/'))
+        {
+            let regex = Regex::new(regex).map_err(|e| format!("invalid pattern {text}: {e}"))?;
+            return Ok(Self::Regex(Box::new(regex)));
+        }
+        let chars = text.to_lowercase().chars().collect();
+        if text.contains(['*', '?']) {
+            Ok(Self::Glob(chars))
+        } else {
+            Ok(Self::Fuzzy(chars))
+        }
+    }
+
+    pub fn matches(&self, text: &str) -> bool {
+        match self {
+            Self::Regex(regex) => regex.is_match(text),
+            Self::Glob(pattern) => glob_matches(pattern, &Self::lowercase_chars(text)),
+            Self::Fuzzy(pattern) => fuzzy_matches(pattern, &Self::lowercase_chars(text)),
+        }
+    }
+
+    fn lowercase_chars(text: &str) -> Vec<char> {
+        text.to_lowercase().chars().collect()
+    }
+}
+
+fn glob_matches(pattern: &[char], text: &[char]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // Position after the last `*` seen and the text position it is currently matched up to
+    let mut backtrack = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                p += 1;
+                backtrack = Some((p, t));
+            }
+            Some(c) if *c == '?' || *c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_t)) => {
+                    p = star_p;
+                    t = star_t + 1;
+                    backtrack = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+fn fuzzy_matches(pattern: &[char], text: &[char]) -> bool {
+    let mut text = text.iter();
+    pattern.iter().all(|c| text.any(|t| t == c))
+}
+
+#[cfg(test)]
+mod test {
+    use super::Pattern;
+
+    fn matches(pattern: &str, text: &str) -> bool {
+        Pattern::new(pattern).unwrap().matches(text)
+    }
+
+    #[test]
+    fn test_matches() {
+        assert!(matches("*.fifo*.count", "top.fifo_0.count"));
+        assert!(matches("*.FIFO*.count", "top.fifo.count"));
+        assert!(!matches("*.fifo*.count", "top.fifo.count_next"));
+        assert!(matches("top.d?ta", "top.data"));
+        assert!(matches("*", ""));
+        assert!(!matches("top.*.valid", "top.valid"));
+
+        assert!(matches("fcnt", "top.fifo.count"));
+        assert!(matches("", "top.valid"));
+        assert!(!matches("cntf", "top.fifo.count"));
+
+        assert!(matches(r"/fifo[0-3]\.count$/", "top.fifo2.count"));
+        assert!(!matches(r"/fifo[0-3]\.count$/", "top.fifo4.count"));
+        assert!(Pattern::new("/fifo(/").is_err());
+    }
+}
@@ -18,6 +18,8 @@ pub struct CommandLine {
 pub struct CommandLineState {
     text: String,
     scroll_state: ScrollState,
+    /// Replaces the header, e.g. while searching the history
+    prompt: Option<String>,
 }
 
 impl StatefulWidget for CommandLine {
@@ -29,10 +31,11 @@ impl StatefulWidget for CommandLine {
         buf: &mut ratatui::prelude::Buffer,
         state: &mut Self::State,
     ) {
+        let header = state.prompt.clone().unwrap_or(Self::HEADER.to_string());
         let input_width = if self.no_header {
             area.width as usize
         } else {
-            (area.width as usize).saturating_sub(Self::HEADER.len() + 1)
+            (area.width as usize).saturating_sub(header.chars().count() + 1)
         };
         state.scroll_state.set_viewport_length(input_width);
 
@@ -45,7 +48,7 @@ impl StatefulWidget for CommandLine {
         let after_highlight = state.text.get(highlight_idx + 1..).unwrap_or(" ");
 
         let mut command_line_components = vec![
-            Span::from(Self::HEADER).style(COMMAND_LINE_HEADER_STYLE),
+            Span::from(header).style(COMMAND_LINE_HEADER_STYLE),
             Span::from(" "),
             Span::from(before_highlight),
             Span::from(highlight).style(self.cursor_style),
@@ -66,8 +69,8 @@ impl Default for CommandLine {
     fn default() -> Self {
         Self {
             no_header: false,
-            line_style: COMMAND_LINE_STYLE,
-            cursor_style: TEXT_CURSOR_STYLE,
+            line_style: COMMAND_LINE_STYLE.get(),
+            cursor_style: TEXT_CURSOR_STYLE.get(),
         }
     }
 }
@@ -104,7 +107,8 @@ impl CommandLineState {
     pub fn put(&mut self, ch: char) {
         let idx = get_utf8_index(&self.text, self.cursor_position()).unwrap_or(self.text.len());
         self.text.insert(idx, ch);
-        self.scroll_state.set_content_length(self.text.len() + 1);
+        self.scroll_state
+            .set_content_length(self.text.chars().count() + 1);
         self.scroll_state.next();
     }
 
@@ -113,10 +117,34 @@ impl CommandLineState {
             let idx = get_utf8_index(&self.text, self.cursor_position() - 1).unwrap();
             self.text.remove(idx);
             self.scroll_state.prev();
-            self.scroll_state.set_content_length(self.text.len() + 1);
+            self.scroll_state
+                .set_content_length(self.text.chars().count() + 1);
         }
     }
 
+    /// Deletes the character under the cursor.
+    pub fn delete(&mut self) {
+        let cursor_position = self.cursor_position();
+        if cursor_position < self.text.chars().count() {
+            self.remove_range(cursor_position, cursor_position + 1);
+        }
+    }
+
+    /// Deletes the whitespace-separated word before the cursor.
+    pub fn delete_word_before_cursor(&mut self) {
+        let end = self.cursor_position();
+        let start = self.word_start_before(end, |c| !c.is_whitespace());
+        self.remove_range(start, end);
+    }
+
+    pub fn delete_before_cursor(&mut self) {
+        self.remove_range(0, self.cursor_position());
+    }
+
+    pub fn delete_after_cursor(&mut self) {
+        self.remove_range(self.cursor_position(), self.text.chars().count());
+    }
+
     pub fn move_cursor_right(&mut self) {
         self.scroll_state.next();
     }
@@ -125,6 +153,48 @@ impl CommandLineState {
         self.scroll_state.prev();
     }
 
+    pub fn move_cursor_to_start(&mut self) {
+        self.scroll_state.select(0);
+    }
+
+    pub fn move_cursor_to_end(&mut self) {
+        self.scroll_state.select(self.text.chars().count());
+    }
+
+    pub fn move_cursor_word_left(&mut self) {
+        let position = self.word_start_before(self.cursor_position(), is_word_char);
+        self.scroll_state.select(position);
+    }
+
+    pub fn move_cursor_word_right(&mut self) {
+        let chars: Vec<char> = self.text.chars().collect();
+        let mut position = self.cursor_position();
+        while position < chars.len() && !is_word_char(chars[position]) {
+            position += 1;
+        }
+        while position < chars.len() && is_word_char(chars[position]) {
+            position += 1;
+        }
+        self.scroll_state.select(position);
+    }
+
+    pub fn text_before_cursor(&self) -> &str {
+        let idx = get_utf8_index(&self.text, self.cursor_position()).unwrap_or(self.text.len());
+        &self.text[..idx]
+    }
+
+    /// Replaces the text, placing the cursor before the character at `cursor_position`.
+    pub fn set_text_with_cursor(&mut self, text: &str, cursor_position: usize) {
+        self.text = text.to_string();
+        self.scroll_state
+            .set_content_length(self.text.chars().count() + 1);
+        self.scroll_state.select(cursor_position);
+    }
+
+    pub fn set_prompt(&mut self, prompt: Option<String>) {
+        self.prompt = prompt;
+    }
+
     pub fn set_text(&mut self, text: &str) {
         self.clear();
         for ch in text.chars() {
@@ -135,8 +205,67 @@ impl CommandLineState {
     fn cursor_position(&self) -> usize {
         self.scroll_state.start_position() + self.scroll_state.selected_position()
     }
+
+    fn word_start_before(&self, position: usize, is_word_char: impl Fn(char) -> bool) -> usize {
+        let chars: Vec<char> = self.text.chars().collect();
+        let mut position = position;
+        while position > 0 && !is_word_char(chars[position - 1]) {
+            position -= 1;
+        }
+        while position > 0 && is_word_char(chars[position - 1]) {
+            position -= 1;
+        }
+        position
+    }
+
+    fn remove_range(&mut self, start: usize, end: usize) {
+        let text: String = self
+            .text
+            .chars()
+            .enumerate()
+            .filter(|(i, _)| *i < start || *i >= end)
+            .map(|(_, c)| c)
+            .collect();
+        self.set_text_with_cursor(&text, start);
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
 }
 
 fn get_utf8_index(s: &str, ch_idx: usize) -> Option<usize> {
     s.char_indices().nth(ch_idx).map(|(i, _)| i)
 }
+
+#[cfg(test)]
+mod test {
+    use super::CommandLineState;
+
+    #[test]
+    fn test_editing() {
+        let mut state = CommandLineState::default();
+        state.set_text("set top.fifo.count 0x10");
+
+        state.move_cursor_word_left();
+        assert_eq!(state.text_before_cursor(), "set top.fifo.count ");
+        state.move_cursor_word_left();
+        assert_eq!(state.text_before_cursor(), "set top.fifo.");
+        state.move_cursor_word_right();
+        assert_eq!(state.text_before_cursor(), "set top.fifo.count");
+
+        state.delete_word_before_cursor();
+        assert_eq!(state.text(), "set  0x10");
+        state.delete();
+        assert_eq!(state.text(), "set 0x10");
+        state.delete_before_cursor();
+        assert_eq!(state.text(), "0x10");
+
+        state.move_cursor_to_end();
+        state.put('0');
+        assert_eq!(state.text(), "0x100");
+        state.move_cursor_to_start();
+        state.delete_after_cursor();
+        assert_eq!(state.text(), "");
+    }
+}
@@ -20,17 +20,7 @@ impl StatefulWidget for ConfirmationButtons {
     type State = ConfirmationState;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        let button_width = self.confirm_text.len().max(self.dismiss_text.len()) + 2;
-        let chunks = Layout::horizontal(vec![
-            Constraint::Min(0),
-            Constraint::Length(button_width as u16),
-            Constraint::Min(0),
-            Constraint::Length(button_width as u16),
-            Constraint::Min(0),
-        ])
-        .split(area);
-        let confirm_area = chunks[1];
-        let dismiss_area = chunks[3];
+        let (confirm_area, dismiss_area) = self.button_areas(area);
 
         let selected_style = Style::new().white().on_red();
         let not_selected_style = Style::new().black().on_white();
@@ -72,6 +62,19 @@ impl ConfirmationButtons {
         self.dismiss_text = String::from(text);
         self
     }
+
+    pub fn button_areas(&self, area: Rect) -> (Rect, Rect) {
+        let button_width = self.confirm_text.len().max(self.dismiss_text.len()) + 2;
+        let chunks = Layout::horizontal(vec![
+            Constraint::Min(0),
+            Constraint::Length(button_width as u16),
+            Constraint::Min(0),
+            Constraint::Length(button_width as u16),
+            Constraint::Min(0),
+        ])
+        .split(area);
+        (chunks[1], chunks[3])
+    }
 }
 
 impl ConfirmationState {
@@ -1,11 +1,14 @@
 use ratatui::{
     buffer::Buffer,
-    layout::{Constraint, Layout, Rect},
+    layout::{Constraint, Layout, Position, Rect},
     text::Text,
     widgets::{Block, Clear, List, ListState, StatefulWidget, Widget},
 };
 
-use crate::styles::{dropdown::ITEM_DEFAULT_STYLE, global::SELECTED_ITEM_STYLE};
+use crate::{
+    styles::{dropdown::ITEM_DEFAULT_STYLE, global::SELECTED_ITEM_STYLE},
+    utils,
+};
 
 #[derive(Default)]
 pub struct DropDown<'a> {
@@ -16,6 +19,7 @@ pub struct DropDownState {
     items: Vec<String>,
     is_opened: bool,
     list_state: ListState,
+    list_area: Rect,
 }
 
 impl<'a> DropDown<'a> {
@@ -40,6 +44,7 @@ impl<'a> StatefulWidget for DropDown<'a> {
             Widget::render(Clear, list_area, buf);
             Widget::render(self.block, list_area, buf);
             StatefulWidget::render(list, inner_list_area, buf, &mut state.list_state);
+            state.list_area = inner_list_area;
         } else {
             let inner_area = self.block.inner(area);
             let areas = Layout::horizontal(vec![Constraint::Min(0), Constraint::Length(2)])
@@ -62,6 +67,7 @@ impl DropDownState {
             items,
             is_opened,
             list_state,
+            list_area: Rect::default(),
         }
     }
 
@@ -99,6 +105,19 @@ impl DropDownState {
         self.list_state.select_previous();
     }
 
+    pub fn item_at(&self, position: Position) -> Option<usize> {
+        if !self.is_opened {
+            return None;
+        }
+        let item_heights = vec![1; self.items.len()];
+        utils::layout::get_list_item_at(
+            self.list_area,
+            self.list_state.offset(),
+            item_heights,
+            position,
+        )
+    }
+
     pub fn select(&mut self, idx: usize) -> Result<(), &'static str> {
         if idx >= self.items.len() {
             return Err("Index out of range");
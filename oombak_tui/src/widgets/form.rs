@@ -1,6 +1,6 @@
 use ratatui::{
     buffer::Buffer,
-    layout::{Constraint, Layout, Rect},
+    layout::{Constraint, Layout, Position, Rect},
     style::{Color, Style},
     widgets::{Block, BorderType, Clear, Paragraph, StatefulWidget, Widget},
 };
@@ -23,6 +23,11 @@ pub struct Form {}
 pub struct FormState {
     input_fields: Vec<InputField>,
     highlight: Option<FormHighlight>,
+    input_field_areas: Vec<Rect>,
+    /// Index of the topmost input field shown when not all of them fit
+    first_shown_input_field: usize,
+    apply_area: Rect,
+    cancel_area: Rect,
 }
 
 pub struct InputField {
@@ -49,13 +54,25 @@ impl StatefulWidget for Form {
         let block = Block::bordered().border_type(BorderType::Rounded);
         let inner_area = block.inner(area);
         let areas = Layout::vertical(vec![
-            Constraint::Length(Self::input_fields_height(state) as u16),
+            Constraint::Min(0),
             Constraint::Length(Self::confirmation_height() as u16),
         ])
         .split(inner_area);
         let confirmation_area = areas[1];
-        let input_field_areas =
-            Layout::vertical(vec![Constraint::Length(3); state.input_fields.len()]).split(areas[0]);
+        let num_of_shown_input_fields = (areas[0].height as usize / 3).max(1);
+        state.scroll_to_highlight(num_of_shown_input_fields);
+        let shown_areas = Layout::vertical(vec![Constraint::Length(3); num_of_shown_input_fields])
+            .split(areas[0]);
+        // Input fields scrolled out of view get an empty area
+        let input_field_areas: Vec<Rect> = (0..state.input_fields.len())
+            .map(|i| {
+                i.checked_sub(state.first_shown_input_field)
+                    .and_then(|i| shown_areas.get(i))
+                    .copied()
+                    .unwrap_or_default()
+            })
+            .collect();
+        state.input_field_areas = input_field_areas.clone();
         Clear.render(area, buf);
         block.render(area, buf);
         Self::render_confirmation_buttons(confirmation_area, buf, state);
@@ -81,6 +98,9 @@ impl Form {
         let mut opened_dropdown_idx = None;
 
         for (i, (input_field, area)) in state.input_fields.iter_mut().zip(areas).enumerate() {
+            if area.is_empty() {
+                continue;
+            }
             // Opened dropdown needs to be rendered last because it draws beyond its borders
             if let InputState::DropDown(state) = &input_field.state {
                 if state.is_opened() {
@@ -165,7 +185,7 @@ impl Form {
         let inner_area = block.inner(area);
         let mut input_field = CommandLine::default()
             .no_header()
-            .line_style(INPUT_FIELD_STYLE);
+            .line_style(INPUT_FIELD_STYLE.get());
 
         if highlight {
             block = block.style(HIGHLIGHTED_INPUT_FIELD_BORDER_STYLE);
@@ -200,10 +220,11 @@ impl Form {
             Some(FormHighlight::Cancel) => ConfirmationState::dismiss(),
             Some(_) | None => ConfirmationState::default(),
         };
-        ConfirmationButtons::default()
+        let confirmation_buttons = ConfirmationButtons::default()
             .confirm_text("Apply")
-            .dismiss_text("Cancel")
-            .render(area, buf, &mut confirmation_state);
+            .dismiss_text("Cancel");
+        (state.apply_area, state.cancel_area) = confirmation_buttons.button_areas(area);
+        confirmation_buttons.render(area, buf, &mut confirmation_state);
     }
 }
 
@@ -234,7 +255,27 @@ impl FormState {
         Self {
             input_fields,
             highlight,
+            input_field_areas: vec![],
+            first_shown_input_field: 0,
+            apply_area: Rect::default(),
+            cancel_area: Rect::default(),
+        }
+    }
+
+    fn scroll_to_highlight(&mut self, num_of_shown_input_fields: usize) {
+        let max_first = self
+            .input_fields
+            .len()
+            .saturating_sub(num_of_shown_input_fields);
+        let mut first = self.first_shown_input_field.min(max_first);
+        if let Some(FormHighlight::InputField(x)) = self.highlight {
+            if x < first {
+                first = x;
+            } else if x >= first + num_of_shown_input_fields {
+                first = x + 1 - num_of_shown_input_fields;
+            }
         }
+        self.first_shown_input_field = first;
     }
 
     pub fn up(&mut self) {
@@ -320,6 +361,45 @@ impl FormState {
         }
     }
 
+    /// Moves the highlight to the clicked item, returning `true` if a confirmation button is
+    /// clicked.
+    pub fn click(&mut self, position: Position) -> bool {
+        let mut closed_idx = None;
+        if let Some(FormHighlight::InputField(x)) = self.highlight {
+            if let InputState::DropDown(state) = &mut self.input_fields[x].state {
+                if let Some(idx) = state.item_at(position) {
+                    let _ = state.select(idx);
+                    state.close();
+                    return false;
+                } else if state.is_opened() {
+                    state.close();
+                    closed_idx = Some(x);
+                }
+            }
+        }
+        if self.apply_area.contains(position) {
+            self.highlight = Some(FormHighlight::Apply);
+            return true;
+        }
+        if self.cancel_area.contains(position) {
+            self.highlight = Some(FormHighlight::Cancel);
+            return true;
+        }
+        if let Some(idx) = self
+            .input_field_areas
+            .iter()
+            .position(|area| area.contains(position))
+        {
+            self.highlight = Some(FormHighlight::InputField(idx));
+            if let InputState::DropDown(state) = &mut self.input_fields[idx].state {
+                if closed_idx != Some(idx) {
+                    state.open();
+                }
+            }
+        }
+        false
+    }
+
     pub fn is_apply(&self) -> bool {
         matches!(self.highlight, Some(FormHighlight::Apply))
     }
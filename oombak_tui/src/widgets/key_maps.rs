@@ -1,6 +1,9 @@
-use std::collections::{btree_map, hash_map, BTreeMap, HashMap};
+use std::{
+    collections::{btree_map, hash_map, BTreeMap, HashMap},
+    str::FromStr,
+};
 
-use crossterm::event::{KeyCode, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::text::{Line, Span};
 
 use crate::styles::command_keys_help_bar::{DESCRIPTION_STYLE, KEY_ID_STYLE};
@@ -14,6 +17,8 @@ pub struct ReversedKeyMaps(BTreeMap<KeyDesc, Vec<KeyId>>);
 pub struct KeyId {
     pub key_code: KeyCode,
     pub key_modifiers: KeyModifiers,
+    /// Keys to press before this one, for multi-key chords
+    pub prefix: Vec<KeyId>,
 }
 
 #[derive(Eq, Hash, PartialEq, Clone, PartialOrd, Ord)]
@@ -87,6 +92,7 @@ impl std::fmt::Display for KeyId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let key = match self.key_code {
             KeyCode::Char(':') => "<colon>".to_string(),
+            KeyCode::Char(' ') => "<space>".to_string(),
             KeyCode::Char(c) => c.to_string(),
             KeyCode::Up => "↑".to_string(),
             KeyCode::Down => "↓".to_string(),
@@ -105,6 +111,9 @@ impl std::fmt::Display for KeyId {
             })
             .collect::<Vec<&'static str>>()
             .join("+");
+        for key_id in self.prefix.iter() {
+            write!(f, "{key_id} ")?;
+        }
         if modifiers.is_empty() {
             write!(f, "{key}")
         } else {
@@ -113,11 +122,107 @@ impl std::fmt::Display for KeyId {
     }
 }
 
+/// Parses a key such as `j`, `ctrl-d`, `<enter>` or `f5`. Characters are case-sensitive, while
+/// modifier and key names are not.
+impl FromStr for KeyId {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut key_modifiers = KeyModifiers::NONE;
+        let mut rest = text;
+        while let Some((modifier, key)) = rest
+            .split_once('-')
+            .filter(|(m, k)| !m.is_empty() && !k.is_empty())
+        {
+            key_modifiers |= match &modifier.to_lowercase()[..] {
+                "ctrl" | "c" => KeyModifiers::CONTROL,
+                "alt" | "a" | "m" => KeyModifiers::ALT,
+                "shift" | "s" => KeyModifiers::SHIFT,
+                _ => break,
+            };
+            rest = key;
+        }
+
+        let name = rest.trim_start_matches('<').trim_end_matches('>');
+        let name = if name.is_empty() { rest } else { name };
+        let mut chars = name.chars();
+        let key_code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match &name.to_lowercase().replace([' ', '_', '-'], "")[..] {
+                "enter" | "return" | "cr" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" | "bs" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "space" => KeyCode::Char(' '),
+                "colon" => KeyCode::Char(':'),
+                "minus" => KeyCode::Char('-'),
+                name => match name.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                    Some(n) => KeyCode::F(n),
+                    None => return Err(format!("unknown key \"{text}\"")),
+                },
+            },
+        };
+        Ok(KeyId::from((key_code, key_modifiers)).normalized())
+    }
+}
+
+impl KeyId {
+    /// Parses space-separated keys, e.g. `g g`, into a chord.
+    pub fn parse_chord(text: &str) -> Result<Vec<KeyId>, String> {
+        let keys: Vec<KeyId> = text
+            .split_whitespace()
+            .map(KeyId::from_str)
+            .collect::<Result<_, String>>()?;
+        if keys.is_empty() {
+            return Err("expected a key".to_string());
+        }
+        Ok(keys)
+    }
+
+    /// Gives the key pressed last in a chord, with the keys before it as its prefix.
+    pub fn from_chord(chord: &[KeyId]) -> Option<KeyId> {
+        let (last, prefix) = chord.split_last()?;
+        Some(KeyId {
+            prefix: prefix.to_vec(),
+            ..last.clone()
+        })
+    }
+
+    /// Leaves the shift modifier out of characters, which are already upper-case when shifted.
+    fn normalized(mut self) -> Self {
+        if let KeyCode::Char(c) = self.key_code {
+            if self.key_modifiers.contains(KeyModifiers::SHIFT) {
+                self.key_code = KeyCode::Char(c.to_ascii_uppercase());
+                self.key_modifiers.remove(KeyModifiers::SHIFT);
+            }
+        }
+        self
+    }
+}
+
+impl From<&KeyEvent> for KeyId {
+    fn from(key_event: &KeyEvent) -> Self {
+        KeyId::from((key_event.code, key_event.modifiers)).normalized()
+    }
+}
+
 impl From<char> for KeyId {
     fn from(ch: char) -> Self {
         KeyId {
             key_code: KeyCode::Char(ch),
             key_modifiers: KeyModifiers::NONE,
+            prefix: vec![],
         }
     }
 }
@@ -127,6 +232,7 @@ impl From<KeyCode> for KeyId {
         KeyId {
             key_code,
             key_modifiers: KeyModifiers::NONE,
+            prefix: vec![],
         }
     }
 }
@@ -136,6 +242,7 @@ impl From<(KeyCode, KeyModifiers)> for KeyId {
         KeyId {
             key_code: value.0,
             key_modifiers: value.1,
+            prefix: vec![],
         }
     }
 }
@@ -27,8 +27,10 @@ pub use key_maps::KeyId;
 pub use key_maps::KeyMaps;
 pub use key_maps::ReversedKeyMaps;
 pub use scroll_state::ScrollState;
+pub use terminal::OutputKind;
 pub use terminal::Terminal;
 pub use terminal::TerminalOutput;
 pub use terminal::TerminalState;
+pub use time_bar::format_time;
 pub use time_bar::TimeBar;
 pub use waveform::Waveform;
@@ -56,6 +56,23 @@ impl ScrollState {
         }
     }
 
+    pub fn select(&mut self, position: usize) {
+        let position = position.min(self.content_length.saturating_sub(1));
+        if position < self.start_position || position >= self.start_position + self.viewport_length
+        {
+            self.start_position = position;
+        }
+        self.selected_position = position - self.start_position;
+    }
+
+    pub fn shift(&mut self, offset: isize) {
+        let max_start_position = self.content_length.saturating_sub(self.viewport_length);
+        self.start_position = self
+            .start_position
+            .saturating_add_signed(offset)
+            .min(max_start_position);
+    }
+
     pub fn last(&mut self) {
         let effective_viewport_length = usize::min(self.viewport_length, self.content_length);
         self.start_position = self.content_length - effective_viewport_length;
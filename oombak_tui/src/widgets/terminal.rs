@@ -1,11 +1,17 @@
+use std::{collections::HashMap, fs, fs::File, io::Write, path::Path};
+
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Layout, Rect},
-    style::Styled,
-    widgets::{List, ListDirection, ListItem, ListState, StatefulWidget},
+    style::Style,
+    text::{Line, Span},
+    widgets::{StatefulWidget, Widget},
 };
 
-use crate::styles::terminal::{ERROR_OUTPUT_STYLE, NORMAL_OUTPUT_STYLE, NOTIFICATION_OUTPUT_STYLE};
+use crate::styles::terminal::{
+    ERROR_OUTPUT_STYLE, NORMAL_OUTPUT_STYLE, NOTIFICATION_OUTPUT_STYLE, SEARCH_MATCH_STYLE,
+    TERMINAL_STATUS_STYLE,
+};
 
 use super::{CommandLine, CommandLineState};
 
@@ -15,8 +21,16 @@ pub struct Terminal {}
 #[derive(Default)]
 pub struct TerminalState {
     command_line_state: CommandLineState,
-    output_history: Vec<TerminalOutput>,
-    history_list_state: ListState,
+    output_history: Vec<OutputEntry>,
+    log_file: Option<File>,
+    /// Kinds of output shown, all of them if empty
+    shown_kinds: Vec<OutputKind>,
+    search: Option<String>,
+    /// Shows only the latest notification of each request
+    is_grouped: bool,
+    /// Number of lines scrolled back from the latest output
+    scroll_offset: usize,
+    viewport_height: usize,
 }
 
 pub enum TerminalOutput {
@@ -25,6 +39,18 @@ pub enum TerminalOutput {
     Error(String),
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OutputKind {
+    Normal,
+    Notification,
+    Error,
+}
+
+struct OutputEntry {
+    output: TerminalOutput,
+    request_id: Option<usize>,
+}
+
 impl StatefulWidget for Terminal {
     type State = TerminalState;
 
@@ -37,29 +63,83 @@ impl StatefulWidget for Terminal {
 
 impl Terminal {
     fn render_output_history(area: Rect, buf: &mut Buffer, state: &mut TerminalState) {
-        let list_items = Self::new_list_items_from_output_history(&state.output_history);
-        let list = List::new(list_items).direction(ListDirection::BottomToTop);
-        StatefulWidget::render(list, area, buf, &mut state.history_list_state);
+        let lines = state.shown_lines();
+        state.viewport_height = area.height as usize;
+        let max_scroll_offset = lines.len().saturating_sub(state.viewport_height);
+        state.scroll_offset = state.scroll_offset.min(max_scroll_offset);
+
+        let status = state.status();
+        let height =
+            state.viewport_height - usize::from(status.is_some()).min(area.height as usize);
+        let end = lines.len() - state.scroll_offset;
+        let start = end.saturating_sub(height);
+        let top = area.bottom() - (end - start) as u16;
+        for (i, line) in lines[start..end].iter().enumerate() {
+            let line_area = Rect::new(area.x, top + i as u16, area.width, 1);
+            line.render(line_area, buf);
+        }
+        if let (Some(status), true) = (status, area.height > 0) {
+            let status_area = Rect::new(area.x, area.y, area.width, 1);
+            Line::from(status)
+                .style(TERMINAL_STATUS_STYLE)
+                .right_aligned()
+                .render(status_area, buf);
+        }
     }
+}
 
-    fn new_list_items_from_output_history<'a>(
-        output_history: &[TerminalOutput],
-    ) -> Vec<ListItem<'a>> {
-        output_history
-            .iter()
-            .rev()
-            .map(|h| match h {
-                TerminalOutput::Normal(output) => {
-                    ListItem::from(format!("> {output}")).set_style(NORMAL_OUTPUT_STYLE)
-                }
-                TerminalOutput::Notification(output) => {
-                    ListItem::from(format!("> {output}")).set_style(NOTIFICATION_OUTPUT_STYLE)
-                }
-                TerminalOutput::Error(output) => {
-                    ListItem::from(format!("> {output}")).set_style(ERROR_OUTPUT_STYLE)
-                }
-            })
-            .collect()
+impl TerminalOutput {
+    pub fn kind(&self) -> OutputKind {
+        match self {
+            TerminalOutput::Normal(_) => OutputKind::Normal,
+            TerminalOutput::Notification(_) => OutputKind::Notification,
+            TerminalOutput::Error(_) => OutputKind::Error,
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        match self {
+            TerminalOutput::Normal(text)
+            | TerminalOutput::Notification(text)
+            | TerminalOutput::Error(text) => text,
+        }
+    }
+
+    fn style(&self) -> Style {
+        match self {
+            TerminalOutput::Normal(_) => NORMAL_OUTPUT_STYLE.get(),
+            TerminalOutput::Notification(_) => NOTIFICATION_OUTPUT_STYLE.get(),
+            TerminalOutput::Error(_) => ERROR_OUTPUT_STYLE.get(),
+        }
+    }
+
+    /// The output as written to log files.
+    fn log_line(&self) -> String {
+        match self {
+            TerminalOutput::Error(text) => format!("error: {text}"),
+            _ => self.text().to_string(),
+        }
+    }
+}
+
+impl OutputKind {
+    pub const NAMES: [&str; 3] = ["normal", "notification", "error"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "normal" => Some(Self::Normal),
+            "notification" => Some(Self::Notification),
+            "error" => Some(Self::Error),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Notification => "notification",
+            Self::Error => "error",
+        }
     }
 }
 
@@ -72,11 +152,222 @@ impl TerminalState {
         &mut self.command_line_state
     }
 
-    pub fn output_history(&self) -> &Vec<TerminalOutput> {
-        &self.output_history
+    pub fn last_output(&self) -> Option<&TerminalOutput> {
+        self.output_history.last().map(|entry| &entry.output)
+    }
+
+    /// Copies every output to the file from now on.
+    pub fn set_log_file(&mut self, log_file: File) {
+        self.log_file = Some(log_file);
     }
 
     pub fn append_output_history(&mut self, output: TerminalOutput) {
-        self.output_history.push(output);
+        self.append(output, None);
+    }
+
+    /// Appends an output about a simulator request, grouped with the other notifications of the
+    /// request when grouping is on.
+    pub fn append_request_output(&mut self, request_id: usize, output: TerminalOutput) {
+        self.append(output, Some(request_id));
+    }
+
+    /// Writes the whole output history, regardless of what is shown, to the file.
+    pub fn save_log(&self, path: &Path) -> Result<(), String> {
+        let mut content = String::new();
+        for entry in self.output_history.iter() {
+            content += &entry.output.log_line();
+            content += "\n";
+        }
+        fs::write(path, content).map_err(|e| format!("cannot write {}: {e}", path.display()))
+    }
+
+    pub fn set_shown_kinds(&mut self, kinds: Vec<OutputKind>) {
+        self.shown_kinds = kinds;
+        self.scroll_offset = 0;
+    }
+
+    pub fn set_search(&mut self, search: Option<String>) {
+        self.search = search.map(|text| text.to_ascii_lowercase());
+        self.scroll_offset = 0;
+    }
+
+    pub fn set_grouped(&mut self, is_grouped: bool) {
+        self.is_grouped = is_grouped;
+        self.scroll_offset = 0;
+    }
+
+    pub fn scroll_up(&mut self, num_of_lines: usize) {
+        self.scroll_offset += num_of_lines;
+    }
+
+    pub fn scroll_down(&mut self, num_of_lines: usize) {
+        self.scroll_offset = self.scroll_offset.saturating_sub(num_of_lines);
+    }
+
+    pub fn scroll_page_up(&mut self) {
+        self.scroll_up(self.viewport_height.saturating_sub(1).max(1));
+    }
+
+    pub fn scroll_page_down(&mut self) {
+        self.scroll_down(self.viewport_height.saturating_sub(1).max(1));
+    }
+
+    fn append(&mut self, output: TerminalOutput, request_id: Option<usize>) {
+        if let Some(log_file) = &mut self.log_file {
+            // Losing the log is no reason to stop the session
+            let _ = writeln!(log_file, "{}", output.log_line());
+        }
+        let entry = OutputEntry { output, request_id };
+        // Keeps the lines in view while scrolled back
+        if self.scroll_offset > 0 && self.is_shown(&entry) && !self.is_regrouped(&entry) {
+            self.scroll_offset += entry.output.text().lines().count().max(1);
+        }
+        self.output_history.push(entry);
+    }
+
+    fn is_shown(&self, entry: &OutputEntry) -> bool {
+        let kind = entry.output.kind();
+        let is_kind_shown = self.shown_kinds.is_empty() || self.shown_kinds.contains(&kind);
+        let is_matched = self.search.as_ref().is_none_or(|search| {
+            entry
+                .output
+                .text()
+                .to_ascii_lowercase()
+                .contains(&search[..])
+        });
+        is_kind_shown && is_matched
+    }
+
+    /// Whether the entry takes the place of an earlier notification of its request.
+    fn is_regrouped(&self, entry: &OutputEntry) -> bool {
+        let is_notification = |e: &OutputEntry| e.output.kind() == OutputKind::Notification;
+        self.is_grouped
+            && is_notification(entry)
+            && entry.request_id.is_some_and(|id| {
+                self.output_history
+                    .iter()
+                    .any(|e| e.request_id == Some(id) && is_notification(e))
+            })
+    }
+
+    fn shown_lines(&self) -> Vec<Line<'static>> {
+        // Number of notifications of each request, and the index of the latest one
+        let mut notifications: HashMap<usize, (usize, usize)> = HashMap::new();
+        if self.is_grouped {
+            for (idx, entry) in self.output_history.iter().enumerate() {
+                if let (Some(id), OutputKind::Notification) =
+                    (entry.request_id, entry.output.kind())
+                {
+                    let (count, latest_idx) = notifications.entry(id).or_default();
+                    *count += 1;
+                    *latest_idx = idx;
+                }
+            }
+        }
+
+        let mut lines = vec![];
+        for (idx, entry) in self.output_history.iter().enumerate() {
+            if !self.is_shown(entry) {
+                continue;
+            }
+            let mut text = entry.output.text().to_string();
+            let group = entry
+                .request_id
+                .filter(|_| entry.output.kind() == OutputKind::Notification)
+                .and_then(|id| notifications.get(&id));
+            match group {
+                Some((_, latest_idx)) if *latest_idx != idx => continue,
+                Some((count, _)) if *count > 1 => text += &format!(" (+{} earlier)", count - 1),
+                _ => (),
+            }
+            for (i, line) in text.split('\n').enumerate() {
+                let prefix = if i == 0 { "> " } else { "  " };
+                lines.push(self.new_line(prefix, line, entry.output.style()));
+            }
+        }
+        lines
+    }
+
+    fn new_line(&self, prefix: &str, text: &str, style: Style) -> Line<'static> {
+        let mut spans = vec![Span::from(prefix.to_string())];
+        match self.search.as_deref().filter(|search| !search.is_empty()) {
+            Some(search) => {
+                let lowercase_text = text.to_ascii_lowercase();
+                let mut start = 0;
+                for (match_start, _) in lowercase_text.match_indices(search) {
+                    spans.push(Span::from(text[start..match_start].to_string()));
+                    let match_end = match_start + search.len();
+                    let matched = text[match_start..match_end].to_string();
+                    spans.push(Span::styled(matched, SEARCH_MATCH_STYLE));
+                    start = match_end;
+                }
+                spans.push(Span::from(text[start..].to_string()));
+            }
+            None => spans.push(Span::from(text.to_string())),
+        }
+        Line::from(spans).style(style)
+    }
+
+    fn status(&self) -> Option<String> {
+        let mut parts = vec![];
+        if !self.shown_kinds.is_empty() {
+            let kinds: Vec<&str> = self.shown_kinds.iter().map(OutputKind::name).collect();
+            parts.push(format!("showing {}", kinds.join(", ")));
+        }
+        if let Some(search) = &self.search {
+            parts.push(format!("search \"{search}\""));
+        }
+        if self.is_grouped {
+            parts.push("grouped by request".to_string());
+        }
+        if self.scroll_offset > 0 {
+            parts.push(format!("{} more lines below", self.scroll_offset));
+        }
+        if parts.is_empty() {
+            None
+        } else {
+            Some(format!(" {} ", parts.join(" | ")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{OutputKind, TerminalOutput, TerminalState};
+
+    fn shown_texts(state: &TerminalState) -> Vec<String> {
+        state
+            .shown_lines()
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_shown_lines() {
+        let mut state = TerminalState::default();
+        state.append_output_history(TerminalOutput::Normal("load fifo.sv".to_string()));
+        for progress in ["Creating probe...", "Running CMake build..."] {
+            let notification = TerminalOutput::Notification(format!("[ID: a] {progress}"));
+            state.append_request_output(0xa, notification);
+        }
+        state.append_request_output(0xa, TerminalOutput::Error("[ID: a] Error: x".to_string()));
+        state.append_output_history(TerminalOutput::Normal("multi\nline".to_string()));
+        assert_eq!(shown_texts(&state).len(), 6);
+
+        state.set_grouped(true);
+        assert_eq!(
+            shown_texts(&state)[1],
+            "> [ID: a] Running CMake build... (+1 earlier)"
+        );
+        assert_eq!(shown_texts(&state)[4], "  line");
+
+        state.set_shown_kinds(vec![OutputKind::Error]);
+        assert_eq!(shown_texts(&state), ["> [ID: a] Error: x"]);
+
+        state.set_shown_kinds(vec![]);
+        state.set_grouped(false);
+        state.set_search(Some("CMAKE".to_string()));
+        assert_eq!(shown_texts(&state), ["> [ID: a] Running CMake build..."]);
     }
 }
@@ -6,21 +6,39 @@ use ratatui::{
     widgets::{StatefulWidget, Widget},
 };
 
-use crate::styles::wave_viewer::{CURSOR_STYLE, TIMEBAR_STYLE, TIME_INDICATOR_STYLE};
+use crate::styles::wave_viewer::{
+    CURSOR_STYLE, MARKER_INDICATOR_STYLE, MARKER_STYLE, TIMEBAR_STYLE, TIME_INDICATOR_STYLE,
+};
 
 use super::ScrollState;
 
-#[derive(Default)]
 pub struct TimeBar {
     tick_count: usize,
     tick_period: f64,
-    time_unit: TimeUnit,
+    time_step_ps: usize,
+    markers: Vec<usize>,
+    reference_marker: Option<(String, usize)>,
 }
 
-#[derive(Default)]
+#[derive(Clone, Copy)]
 pub enum TimeUnit {
-    #[default]
     Picoseconds,
+    Nanoseconds,
+    Microseconds,
+    Milliseconds,
+    Seconds,
+}
+
+impl Default for TimeBar {
+    fn default() -> Self {
+        Self {
+            tick_count: 0,
+            tick_period: 0.0,
+            time_step_ps: 1,
+            markers: vec![],
+            reference_marker: None,
+        }
+    }
 }
 
 impl TimeBar {
@@ -33,6 +51,21 @@ impl TimeBar {
         self.tick_period = tick_period;
         self
     }
+
+    pub fn time_step_ps(mut self, time_step_ps: usize) -> Self {
+        self.time_step_ps = time_step_ps;
+        self
+    }
+
+    pub fn markers(mut self, positions: Vec<usize>) -> Self {
+        self.markers = positions;
+        self
+    }
+
+    pub fn reference_marker(mut self, name: &str, position: usize) -> Self {
+        self.reference_marker = Some((name.to_string(), position));
+        self
+    }
 }
 
 impl StatefulWidget for TimeBar {
@@ -45,7 +78,8 @@ impl StatefulWidget for TimeBar {
             let lines = self.plot_into_lines(state);
             buf.set_string(area.x, area.y + 1, &lines[0], TIMEBAR_STYLE);
             buf.set_string(area.x, area.y + 2, &lines[1], TIMEBAR_STYLE);
-            Self::set_highlight(buf, area, state, CURSOR_STYLE);
+            self.set_marker_highlights(buf, area, state);
+            Self::set_highlight(buf, area, state, CURSOR_STYLE.get());
         }
     }
 }
@@ -55,7 +89,31 @@ impl TimeBar {
         let time_indicator_area = Rect::new(area.x, area.y, area.width, 1);
         let span = Span::from(format!(" {:10} ", self.format(self.current_time(state))))
             .style(TIME_INDICATOR_STYLE);
+        let offset = u16::min(span.width() as u16, area.width);
         span.render(time_indicator_area, buf);
+        if let Some((name, position)) = &self.reference_marker {
+            let marker_time = self.time_from_position(*position);
+            let delta = self.current_time(state) - marker_time;
+            let marker_indicator_area = Rect::new(area.x + offset, area.y, area.width - offset, 1);
+            Span::from(format!(
+                " {name}: {} Δ {} ",
+                self.format(marker_time),
+                self.format(delta)
+            ))
+            .style(MARKER_INDICATOR_STYLE)
+            .render(marker_indicator_area, buf);
+        }
+    }
+
+    fn set_marker_highlights(&self, buf: &mut Buffer, area: Rect, state: &ScrollState) {
+        let start_position = state.start_position();
+        let end_position = start_position + state.viewport_length().min(area.width as usize);
+        for position in self.markers.iter() {
+            if (start_position..end_position).contains(position) {
+                let x = area.x + (position - start_position) as u16;
+                buf.set_style(Rect::new(x, area.y + 1, 1, 2), MARKER_STYLE);
+            }
+        }
     }
 
     fn plot_into_lines(&self, state: &ScrollState) -> [String; 2] {
@@ -121,7 +179,41 @@ impl TimeBar {
     }
 
     fn format(&self, time: f64) -> String {
-        format!("{:3.2} {}", time, self.time_unit)
+        format_time(time * self.time_step_ps as f64)
+    }
+}
+
+/// Formats a time in picoseconds in the largest unit in which it is at least 1.
+pub fn format_time(time_ps: f64) -> String {
+    let unit = TimeUnit::fit(time_ps);
+    format!("{:3.2} {}", time_ps / unit.picoseconds(), unit)
+}
+
+impl TimeUnit {
+    const ALL: [TimeUnit; 5] = [
+        TimeUnit::Picoseconds,
+        TimeUnit::Nanoseconds,
+        TimeUnit::Microseconds,
+        TimeUnit::Milliseconds,
+        TimeUnit::Seconds,
+    ];
+
+    fn fit(time_ps: f64) -> Self {
+        Self::ALL
+            .into_iter()
+            .rev()
+            .find(|unit| time_ps.abs() >= unit.picoseconds())
+            .unwrap_or(TimeUnit::Picoseconds)
+    }
+
+    fn picoseconds(self) -> f64 {
+        match self {
+            TimeUnit::Picoseconds => 1.0,
+            TimeUnit::Nanoseconds => 1e3,
+            TimeUnit::Microseconds => 1e6,
+            TimeUnit::Milliseconds => 1e9,
+            TimeUnit::Seconds => 1e12,
+        }
     }
 }
 
@@ -129,6 +221,10 @@ impl std::fmt::Display for TimeUnit {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             TimeUnit::Picoseconds => f.write_str("ps"),
+            TimeUnit::Nanoseconds => f.write_str("ns"),
+            TimeUnit::Microseconds => f.write_str("us"),
+            TimeUnit::Milliseconds => f.write_str("ms"),
+            TimeUnit::Seconds => f.write_str("s"),
         }
     }
 }
@@ -137,9 +233,11 @@ impl std::fmt::Display for TimeUnit {
 mod test {
     use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget};
 
-    use crate::styles::wave_viewer::{CURSOR_STYLE, TIMEBAR_STYLE, TIME_INDICATOR_STYLE};
+    use crate::styles::wave_viewer::{
+        CURSOR_STYLE, MARKER_INDICATOR_STYLE, MARKER_STYLE, TIMEBAR_STYLE, TIME_INDICATOR_STYLE,
+    };
 
-    use super::{ScrollState, TimeBar};
+    use super::{format_time, ScrollState, TimeBar};
 
     const X0: u16 = 10;
     const Y0: u16 = 10;
@@ -284,6 +382,40 @@ mod test {
         assert_eq!(buf, expected);
     }
 
+    #[test]
+    pub fn test_render_with_reference_marker() {
+        let (time_bar, mut state, mut buf, area) = setup(50);
+        let time_bar = time_bar.markers(vec![4]).reference_marker("M1", 4);
+
+        for _ in 0..10 {
+            state.next();
+        }
+
+        time_bar.render(buf.area, &mut buf, &mut state);
+
+        let mut expected = Buffer::with_lines(vec![
+            " 10.00 ps    M1: 4.00 ps Δ 6.00 ps                ",
+            "╻0.00 ps  ╻10.00 ps ╻20.00 ps ╻30.00 ps ╻40.00 ps ",
+            "┻┷┷┷┷┻┷┷┷┷┻┷┷┷┷┻┷┷┷┷┻┷┷┷┷┻┷┷┷┷┻┷┷┷┷┻┷┷┷┷┻┷┷┷┷┻┷┷┷┷",
+        ]);
+        expected.area = area;
+        expected.set_style(Rect::new(X0, Y0, 12, 1), TIME_INDICATOR_STYLE);
+        expected.set_style(Rect::new(X0 + 12, Y0, 23, 1), MARKER_INDICATOR_STYLE);
+        expected.set_style(Rect::new(X0, Y0 + 1, area.width, 2), TIMEBAR_STYLE);
+        expected.set_style(Rect::new(X0 + 4, Y0 + 1, 1, 2), MARKER_STYLE);
+        expected.set_style(Rect::new(X0 + 10, Y0 + 1, 1, 2), CURSOR_STYLE);
+
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    pub fn test_format_time() {
+        assert_eq!(format_time(0.0), "0.00 ps");
+        assert_eq!(format_time(1500.0), "1.50 ns");
+        assert_eq!(format_time(-2e6), "-2.00 us");
+        assert_eq!(format_time(3e12), "3.00 s");
+    }
+
     fn setup(viewport_length: usize) -> (TimeBar, ScrollState, Buffer, Rect) {
         let time_bar = TimeBar::default().tick_period(10.0).tick_count(10);
         let mut state = ScrollState::default();
@@ -10,8 +10,8 @@ use ratatui::{
 
 use crate::{
     components::models::{PlotType, WaveSpec},
-    styles::wave_viewer::{CURSOR_STYLE, WAVEFORM_STYLE},
-    utils::bitvec_str::{self, i128_from_bitvec},
+    styles::wave_viewer::{AXIS_LEGEND_STYLE, CURSOR_STYLE, WAVEFORM_STYLE},
+    utils::bitvec_str::{self, f64_from_bitvec, Radix},
 };
 
 use super::ScrollState;
@@ -19,6 +19,9 @@ use super::ScrollState;
 const NUMBER_OF_CELLS_PER_UNIT_TIME: usize = 1;
 const BLOCK_SYMBOL_1: char = '▓';
 const BLOCK_SYMBOL_2: char = '░';
+const BRAILLE_BLANK: u32 = 0x2800;
+/// Bit of each braille dot, indexed by the dot's row (from the top) and column.
+const BRAILLE_DOTS: [[u8; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
 
 pub struct Waveform<'a> {
     wave_spec: &'a WaveSpec,
@@ -76,9 +79,12 @@ impl StatefulWidget for Waveform<'_> {
     {
         state.set_viewport_length(area.width as usize);
         let (compact_values, plot_offset) = self.slice_wave(&self.wave_spec.wave, state);
+        let mut axis_legend = None;
         let lines = match (&self.wave_spec.plot_type, self.wave_spec.wave.width) {
             (PlotType::Analog, _) => {
-                self.analog_plot(compact_values, plot_offset, state.viewport_length())
+                let scale = AnalogScale::new(self.wave_spec, &compact_values);
+                axis_legend = Some(scale.legend());
+                self.analog_plot(compact_values, plot_offset, state.viewport_length(), &scale)
             }
             (PlotType::Digital, 1) => {
                 self.single_bit_plot(compact_values, plot_offset, state.viewport_length())
@@ -89,6 +95,9 @@ impl StatefulWidget for Waveform<'_> {
         };
         let inner_area = self.block.inner_if_some(area);
         self.render_lines(&lines, inner_area, buf);
+        if let Some(axis_legend) = axis_legend {
+            Self::render_axis_legend(axis_legend, inner_area, buf);
+        }
         self.add_cursor_highlight(
             buf,
             inner_area,
@@ -180,22 +189,64 @@ impl Waveform<'_> {
         )
     }
 
+    /// Plots the values as a line of braille dots, each character cell holding 2 x 4 dots.
     fn analog_plot(
         &self,
         compact_values: Vec<CompactWaveValue>,
         plot_offset: usize,
         viewport_length: usize,
+        scale: &AnalogScale,
     ) -> Vec<String> {
-        let height = self.wave_spec.height as usize;
-        let num_of_levels = 2 * height + 1;
-        let level_mapper =
-            AnalogLevelMapper::new(&compact_values, num_of_levels, self.wave_spec.signed);
-        self.analog_plot_with_level_mapper(
-            compact_values,
-            plot_offset,
-            viewport_length,
-            level_mapper,
-        )
+        let num_of_rows = 2 * self.wave_spec.height as usize + 1;
+        let num_of_dot_rows = 4 * num_of_rows;
+        let dot_rows = self.analog_dot_rows(&compact_values, scale, num_of_dot_rows);
+
+        let mut cells = vec![vec![0u8; dot_rows.len().div_ceil(2)]; num_of_rows];
+        let mut prev_dot_row = dot_rows.first().copied().unwrap_or(0);
+        for (x, dot_row) in dot_rows.into_iter().enumerate() {
+            // Fill the gap to the previous dot so that the line stays connected
+            for y in dot_row.min(prev_dot_row)..=dot_row.max(prev_dot_row) {
+                let row_from_top = num_of_dot_rows - y - 1;
+                cells[row_from_top / 4][x / 2] |= BRAILLE_DOTS[row_from_top % 4][x % 2];
+            }
+            prev_dot_row = dot_row;
+        }
+
+        let lines: Vec<String> = cells
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|dots| match dots {
+                        0 => ' ',
+                        dots => char::from_u32(BRAILLE_BLANK + *dots as u32).unwrap_or(' '),
+                    })
+                    .collect()
+            })
+            .collect();
+        Self::trim_plot_start(&lines, plot_offset, viewport_length)
+    }
+
+    /// Gives the dot row, counted from the bottom, of every dot column of an analog plot.
+    fn analog_dot_rows(
+        &self,
+        compact_values: &[CompactWaveValue],
+        scale: &AnalogScale,
+        num_of_dot_rows: usize,
+    ) -> Vec<usize> {
+        let mut dot_rows = vec![];
+        for (i, compact_value) in compact_values.iter().enumerate() {
+            let level = scale.level(compact_value.value());
+            let next_level = compact_values
+                .get(i + 1)
+                .filter(|_| self.wave_spec.interpolate)
+                .map_or(level, |next_value| scale.level(next_value.value()));
+            let num_of_dot_columns = 2 * self.unit_width() * compact_value.duration();
+            for x in 0..num_of_dot_columns {
+                let level = level + (next_level - level) * x as f64 / num_of_dot_columns as f64;
+                dot_rows.push((level * (num_of_dot_rows - 1) as f64).round() as usize);
+            }
+        }
+        dot_rows
     }
 
     fn analog_plot_with_level_mapper(
@@ -276,20 +327,27 @@ impl Waveform<'_> {
             .collect()
     }
 
+    fn render_axis_legend((max, min): (String, String), area: Rect, buf: &mut Buffer) {
+        if area.height < 2 {
+            return;
+        }
+        buf.set_string(area.x, area.y, max, AXIS_LEGEND_STYLE);
+        buf.set_string(area.x, area.y + area.height - 1, min, AXIS_LEGEND_STYLE);
+    }
+
     fn render_lines(&self, lines: &[String], area: Rect, buf: &mut Buffer) {
         let selected_style = if self.is_selected {
             self.selected_style
         } else {
             Style::default()
         };
+        let style = match self.wave_spec.color {
+            Some(color) => WAVEFORM_STYLE.get().fg(color),
+            None => WAVEFORM_STYLE.get(),
+        };
         for (i, line) in lines.iter().enumerate() {
             let i = i as u16;
-            buf.set_string(
-                area.x,
-                area.y + i,
-                line,
-                WAVEFORM_STYLE.set_style(selected_style),
-            )
+            buf.set_string(area.x, area.y + i, line, style.set_style(selected_style))
         }
     }
 
@@ -310,11 +368,11 @@ impl Waveform<'_> {
         let option = bitvec_str::Option::from(self.wave_spec);
         let value = bitvec_str::from(value, &option);
         let str_width = self.unit_width() * count + 1;
-        let res = if str_width - 2 >= value.len() {
+        let res = if str_width - 2 >= value.chars().count() {
             format!("{value:^str_width$}")
         } else {
             let snip_size = usize::saturating_sub(str_width, 3);
-            let snip = &value[0..snip_size];
+            let snip: String = value.chars().take(snip_size).collect();
             format!(" {snip}… ")
         };
         res.chars().take(str_width).collect()
@@ -405,59 +463,114 @@ impl Waveform<'_> {
 
 struct AnalogLevelMapper {
     limits: Vec<f64>,
-    min_value: f64,
-    is_signed: bool,
 }
 
 impl AnalogLevelMapper {
-    fn new(compact_values: &[CompactWaveValue], num_of_levels: usize, is_signed: bool) -> Self {
-        let (limits, min_value) = Self::calculate_limits(compact_values, num_of_levels, is_signed);
-        Self {
-            limits,
-            min_value,
-            is_signed,
-        }
-    }
-
     fn digital(num_of_levels: usize) -> Self {
         let limits = vec![0.5; num_of_levels - 1];
-        let min_value = 0.0;
-        Self {
-            limits,
-            min_value,
-            is_signed: false,
-        }
+        Self { limits }
     }
 
     fn num_of_levels(&self) -> usize {
         self.limits.len() + 1
     }
 
-    fn calculate_limits(
-        compact_values: &[CompactWaveValue],
-        num_of_levels: usize,
-        is_signed: bool,
-    ) -> (Vec<f64>, f64) {
-        if compact_values.is_empty() {
-            return (vec![], 0f64);
+    fn map(&self, value: &BitVec<u32>) -> usize {
+        self.limits
+            .partition_point(|l| *l <= f64_from_bitvec(value, false))
+    }
+}
+
+/// Maps values to heights between 0 and 1 of an analog plot, between the bounds set on the
+/// wave spec or, if unset, the smallest and largest values in view.
+struct AnalogScale {
+    min: f64,
+    max: f64,
+    is_signed: bool,
+    /// Weight of the least significant bit, as shown by the fixed-point radix.
+    unit: f64,
+}
+
+impl AnalogScale {
+    fn new(wave_spec: &WaveSpec, compact_values: &[CompactWaveValue]) -> Self {
+        let mut scale = Self {
+            min: 0.0,
+            max: 0.0,
+            is_signed: wave_spec.signed,
+            unit: match wave_spec.radix {
+                Radix::FixedPoint => 2f64.powi(-(wave_spec.fraction_bits as i32)),
+                _ => 1.0,
+            },
+        };
+        let values = compact_values.iter().map(|v| scale.value(v.value()));
+        let min = values.clone().fold(f64::INFINITY, f64::min);
+        let max = values.fold(f64::NEG_INFINITY, f64::max);
+        scale.min = wave_spec
+            .analog_min
+            .or(min.is_finite().then_some(min))
+            .unwrap_or(0.0);
+        scale.max = wave_spec
+            .analog_max
+            .or(max.is_finite().then_some(max))
+            .unwrap_or(0.0);
+        scale
+    }
+
+    fn value(&self, value: &BitVec<u32>) -> f64 {
+        f64_from_bitvec(value, self.is_signed) * self.unit
+    }
+
+    fn level(&self, value: &BitVec<u32>) -> f64 {
+        if self.max <= self.min {
+            return 0.0;
         }
+        ((self.value(value) - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+    }
 
-        let values = compact_values
-            .iter()
-            .map(|v| i128_from_bitvec(v.value(), is_signed));
-        let max_value = values.clone().max().unwrap();
-        let min_value = values.min().unwrap();
-        let limits = (1..num_of_levels)
-            .map(|i| i as i128)
-            .map(|i| (i * (max_value - min_value)) as f64 / num_of_levels as f64)
-            .collect();
+    fn legend(&self) -> (String, String) {
+        (Self::format(self.max), Self::format(self.min))
+    }
 
-        (limits, min_value as f64)
+    fn format(value: f64) -> String {
+        if value == 0.0 || (1e-3..1e9).contains(&value.abs()) {
+            format!("{}", (value * 1000.0).round() / 1000.0)
+        } else {
+            format!("{value:.3e}")
+        }
     }
+}
 
-    fn map(&self, value: &BitVec<u32>) -> usize {
-        self.limits.partition_point(|l| {
-            *l <= i128_from_bitvec(value, self.is_signed) as f64 - self.min_value
-        })
+#[cfg(test)]
+mod test {
+    use bitvec::{bitvec, order::Lsb0};
+    use oombak_sim::response::{CompactWaveValue, Wave};
+
+    use crate::components::models::{PlotType, WaveSpec};
+
+    use super::{AnalogScale, Waveform};
+
+    #[test]
+    fn test_analog_plot() {
+        let mut wave_spec = WaveSpec::new(Wave::new("top.count".to_string(), 2));
+        wave_spec.plot_type = PlotType::Analog;
+        wave_spec.height = 0;
+        wave_spec.signed = false;
+        let compact_values = vec![
+            CompactWaveValue::new(bitvec![u32, Lsb0; 0, 0], 1),
+            CompactWaveValue::new(bitvec![u32, Lsb0; 1, 1], 1),
+        ];
+        let plot = |wave_spec: &WaveSpec| {
+            let scale = AnalogScale::new(wave_spec, &compact_values);
+            Waveform::new(wave_spec).analog_plot(compact_values.clone(), 0, 10, &scale)
+        };
+
+        assert_eq!(plot(&wave_spec), vec!["⣀⡏"]);
+        wave_spec.interpolate = true;
+        assert_eq!(plot(&wave_spec), vec!["⣰⠋"]);
+
+        wave_spec.analog_max = Some(6.0);
+        let scale = AnalogScale::new(&wave_spec, &compact_values);
+        assert_eq!(scale.legend(), ("6".to_string(), "0".to_string()));
+        assert_eq!(scale.level(compact_values[1].value()), 0.5);
     }
 }